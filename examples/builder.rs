@@ -0,0 +1,21 @@
+extern crate komodo_rpc_client;
+
+use komodo_rpc_client::Client;
+use komodo_rpc_client::KomodoRpcApi;
+
+pub fn main() {
+    let komodo_client = Client::builder()
+        .host("127.0.0.1")
+        .port(7771)
+        .rpc_user("user")
+        .rpc_password("password")
+        .build()
+        .expect("Failed to build KMD client");
+
+    let info = komodo_client.get_info();
+
+    match info {
+        Err(api_error) => println!("{:?}", api_error),
+        Ok(client_response) => println!("{:?}", client_response),
+    }
+}
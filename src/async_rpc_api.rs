@@ -0,0 +1,13 @@
+use Txid;
+use ChannelSecret;
+use BlockHash;
+use arguments::*;
+use types::*;
+use types::arguments::address::{Address, Amounts, FromAddresses};
+use rpcconn::ApiFuture;
+
+rpc_methods!(
+    /// The asynchronous counterpart of `KomodoRpcApi`. Every call returns a future that resolves
+    /// to the same response type as its blocking counterpart.
+    trait AsyncKomodoRpcApi, ApiFuture
+);
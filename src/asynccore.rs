@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+use futures::{future, Future};
+use serde::{de::DeserializeOwned, ser::Serialize};
+use serde_json::Value;
+
+use RpcRequest;
+use rpcconn::{AsyncRpcClient, ApiFuture, BatchResponse, RpcBatch};
+
+use Txid;
+use ChannelSecret;
+use BlockHash;
+use AsyncKomodoRpcApi;
+
+use arguments::*;
+use types::*;
+
+use error::ApiError;
+use types::arguments::address::{Address, Amounts};
+use arguments::address::{AddrType, FromAddresses};
+
+/// The asynchronous counterpart of `Client`, built on the futures 0.1 based `reqwest::async` client.
+/// It needs to be polled on a tokio 0.1 runtime.
+///
+/// An `AsyncClient` is created through `ClientBuilder::build_async`.
+///
+/// ```no_run
+/// # extern crate futures;
+/// # extern crate komodo_rpc_client;
+/// # use futures::Future;
+/// # use komodo_rpc_client::{AsyncKomodoRpcApi, Chain, Client};
+/// # fn main() {
+/// let client = Client::builder()
+///     .chain(Chain::KMD)
+///     .build_async()
+///     .unwrap();
+///
+/// let info = client.get_info()
+///     .map(|info| println!("{:?}", info));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncClient {
+    client: AsyncRpcClient,
+}
+
+impl AsyncClient {
+    pub(crate) fn new(client: AsyncRpcClient) -> Self {
+        AsyncClient {
+            client,
+        }
+    }
+
+    /// Sends all requests in `batch` to komodod in a single round trip.
+    /// See `RpcBatch` for how to retrieve the responses.
+    pub fn send_batch(&self, batch: RpcBatch) -> ApiFuture<BatchResponse> {
+        self.client.send_batch(batch)
+    }
+
+    /// Calls `method` with `params` and deserializes its result into `R`, see `Client::call`.
+    pub fn call<R: DeserializeOwned + Debug + Send + 'static>(&self, method: &str, params: Vec<Value>) -> ApiFuture<R> {
+        self.send(&RpcRequest::with_params(method, params))
+    }
+
+    /// Calls `method` with `params` and returns its result as JSON, `Value::Null` if it has none.
+    pub fn call_raw(&self, method: &str, params: Vec<Value>) -> ApiFuture<Value> {
+        self.call(method, params)
+    }
+
+    fn send<R: DeserializeOwned + Debug + Send + 'static, P: Serialize + Debug>(
+        &self,
+        request: &RpcRequest<P>
+    ) -> ApiFuture<R> {
+        self.client.send(request)
+    }
+
+    /// Sends a request to a CC RPC, see `cc_result`.
+    fn send_cc<R: DeserializeOwned + Debug + Send + 'static, P: Serialize + Debug>(
+        &self,
+        request: &RpcRequest<P>
+    ) -> ApiFuture<R> {
+        Box::new(self.send::<Value, P>(request).and_then(cc_result))
+    }
+
+    /// Sends a request to a CC RPC that answers with a transaction for `send_raw_transaction`.
+    fn send_cc_transaction<P: Serialize + Debug>(&self, request: &RpcRequest<P>) -> ApiFuture<SignedRawTransaction> {
+        let tx: ApiFuture<CCTransaction> = self.send_cc(request);
+
+        Box::new(tx.map(SignedRawTransaction::from))
+    }
+}
+
+/// A future that fails with `error` right away, for arguments that are rejected before a request is made.
+fn failed<R: Send + 'static>(error: ApiError) -> ApiFuture<R> {
+    Box::new(future::err(error))
+}
+
+impl AsyncKomodoRpcApi for AsyncClient {
+    rpc_methods!(ApiFuture, failed);
+}
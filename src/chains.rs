@@ -53,7 +53,7 @@ pub enum Chain {
 impl fmt::Display for Chain {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
          match self {
-             Chain::Custom(name) => fmt::Debug::fmt(name, f),
+             Chain::Custom(name) => fmt::Display::fmt(name, f),
              _ => fmt::Debug::fmt(self, f)
          }
     }
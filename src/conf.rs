@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use os_info::Type as OSType;
+use dirs;
+
+use chains::Chain;
+use error::ApiError;
+
+type Result<T> = std::result::Result<T, ApiError>;
+
+/// Guards against `includeconf` cycles.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// A parsed `komodo.conf` (or `<assetchain>.conf`) file.
+///
+/// The format is the bitcoin-style `key=value` format:
+/// - whitespace around keys and values and `\r` line endings are ignored,
+/// - everything after a `#` is a comment,
+/// - a key may occur more than once. Single-valued keys return the first occurrence,
+///   like komodod does, while multi-valued keys such as `rpcbind` and `rpcallowip` return all of them,
+/// - `includeconf=<file>` includes another config file, relative to the including file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KomodoConf {
+    entries: HashMap<String, Vec<String>>,
+}
+
+impl KomodoConf {
+    /// Parses the contents of a config file. `includeconf` entries are kept as values, but not followed.
+    pub fn parse(contents: &str) -> Result<KomodoConf> {
+        let mut conf = KomodoConf::default();
+        conf.parse_into(contents)?;
+
+        Ok(conf)
+    }
+
+    /// Reads and parses a config file, following `includeconf` entries.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<KomodoConf> {
+        let mut conf = KomodoConf::default();
+        conf.read_into(path.as_ref(), 0)?;
+
+        Ok(conf)
+    }
+
+    /// Reads the config file of `chain` from `datadir`, or from the chain's default data directory
+    /// if `datadir` is `None`.
+    pub fn read_for(chain: &Chain, datadir: Option<&Path>) -> Result<KomodoConf> {
+        let datadir = match datadir {
+            Some(path) => path.to_path_buf(),
+            None => default_datadir(chain)?,
+        };
+
+        KomodoConf::read(datadir.join(file_name(chain)))
+    }
+
+    fn read_into(&mut self, path: &Path, depth: usize) -> Result<()> {
+        if depth > MAX_INCLUDE_DEPTH {
+            return Err(ApiError::Config(format!("too many nested includeconf files at {}", path.display())))
+        }
+
+        let contents = fs::read_to_string(path)?;
+        let includes = self.parse_into(&contents)?;
+
+        for include in includes {
+            let mut include_path = PathBuf::from(&include);
+            if include_path.is_relative() {
+                if let Some(parent) = path.parent() {
+                    include_path = parent.join(include_path);
+                }
+            }
+
+            self.read_into(&include_path, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds all entries in `contents` and returns the files it includes.
+    fn parse_into(&mut self, contents: &str) -> Result<Vec<String>> {
+        let mut includes = vec![];
+
+        for (n, line) in contents.lines().enumerate() {
+            let line = match line.find('#') {
+                Some(pos) => &line[..pos],
+                None => line,
+            };
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut split = line.splitn(2, '=');
+            let (key, value) = match (split.next(), split.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => return Err(ApiError::Config(format!("parse error on line {}: {}, missing '='", n + 1, line))),
+            };
+
+            if key.is_empty() {
+                return Err(ApiError::Config(format!("parse error on line {}: {}, missing key", n + 1, line)))
+            }
+
+            if key == "includeconf" {
+                includes.push(value.to_string());
+            }
+
+            self.entries
+                .entry(key.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+
+        Ok(includes)
+    }
+
+    /// The first value of `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key)
+            .and_then(|values| values.first())
+            .map(|value| value.as_str())
+    }
+
+    /// All values of `key`, in the order they appear in the file.
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        match self.entries.get(key) {
+            Some(values) => values.iter().map(|value| value.as_str()).collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// All keys in this config, in no particular order.
+    pub fn keys(&self) -> Vec<&str> {
+        self.entries.keys().map(|key| key.as_str()).collect()
+    }
+
+    /// Interprets the first value of `key` like komodod does: an empty value or any non-zero number is `true`.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).map(|value| {
+            if value.is_empty() {
+                return true
+            }
+
+            let digits: String = value.chars()
+                .enumerate()
+                .take_while(|(i, c)| c.is_ascii_digit() || (*i == 0 && (*c == '-' || *c == '+')))
+                .map(|(_, c)| c)
+                .collect();
+
+            digits.parse::<i64>().map(|n| n != 0).unwrap_or(false)
+        })
+    }
+
+    fn get_parsed<T: FromStr>(&self, key: &str) -> Result<Option<T>> {
+        match self.get(key) {
+            Some(value) => value.parse::<T>()
+                .map(Some)
+                .map_err(|_| ApiError::Config(format!("invalid value for {}: {}", key, value))),
+            None => Ok(None),
+        }
+    }
+
+    pub fn rpc_user(&self) -> Option<&str> {
+        self.get("rpcuser")
+    }
+
+    pub fn rpc_password(&self) -> Option<&str> {
+        self.get("rpcpassword")
+    }
+
+    pub fn rpc_port(&self) -> Result<Option<u16>> {
+        self.get_parsed("rpcport")
+    }
+
+    /// The host `komodo-cli` connects to.
+    pub fn rpc_connect(&self) -> Option<&str> {
+        self.get("rpcconnect")
+    }
+
+    /// The addresses komodod listens on for RPC connections.
+    pub fn rpc_bind(&self) -> Vec<&str> {
+        self.get_all("rpcbind")
+    }
+
+    /// The IPs or subnets that are allowed to connect to the RPC server.
+    pub fn rpc_allow_ip(&self) -> Vec<&str> {
+        self.get_all("rpcallowip")
+    }
+
+    pub fn rpc_threads(&self) -> Result<Option<u32>> {
+        self.get_parsed("rpcthreads")
+    }
+
+    pub fn rpc_work_queue(&self) -> Result<Option<u32>> {
+        self.get_parsed("rpcworkqueue")
+    }
+
+    pub fn rpc_cookie_file(&self) -> Option<&str> {
+        self.get("rpccookiefile")
+    }
+
+    /// The cookie file komodod writes when it runs without `rpcpassword`: `rpccookiefile`, or `.cookie`,
+    /// in the data directory of its network. On mainnet that is `datadir` itself.
+    pub fn cookie_file(&self, datadir: &Path) -> PathBuf {
+        let network_dir = if self.testnet() {
+            datadir.join("testnet3")
+        } else if self.regtest() {
+            datadir.join("regtest")
+        } else {
+            datadir.to_path_buf()
+        };
+
+        // an absolute rpccookiefile replaces the directory
+        network_dir.join(self.rpc_cookie_file().unwrap_or(".cookie"))
+    }
+
+    pub fn server(&self) -> bool {
+        self.get_bool("server").unwrap_or(false)
+    }
+
+    pub fn testnet(&self) -> bool {
+        self.get_bool("testnet").unwrap_or(false)
+    }
+
+    pub fn regtest(&self) -> bool {
+        self.get_bool("regtest").unwrap_or(false)
+    }
+
+    pub fn include_conf(&self) -> Vec<&str> {
+        self.get_all("includeconf")
+    }
+}
+
+/// The default data directory of `chain`. Assetchains live in a subdirectory
+/// of the Komodo data directory.
+pub(crate) fn default_datadir(chain: &Chain) -> Result<PathBuf> {
+    let mut datadir: PathBuf;
+
+    // find location of configuration file:
+    match os_info::get().os_type() {
+        OSType::Ubuntu | OSType::Linux => {
+            // Linux: /home/$USER/
+            if let Some(mut path) = dirs::home_dir() {
+                path.push(".komodo");
+                datadir = path;
+            } else {
+                return Err(ApiError::Other(String::from("no komodod installation found")))
+            }
+        },
+        OSType::Macos | OSType::Windows => {
+            // MacOS: /Users/Alice/Library/Application Support
+            // Windows: C:\Users\Alice\AppData\Roaming
+            if let Some(mut path) = dirs::data_dir() {
+                path.push("Komodo");
+                datadir = path;
+            } else {
+                return Err(ApiError::Other(String::from("no komodod installation found")))
+            }
+        },
+        _ => return Err(ApiError::Other(String::from("unknown or unsupported operating system")))
+    }
+
+    // assetchain configuration files live in their own directory:
+    match chain {
+        Chain::KMD => {},
+        _ => datadir.push(chain.to_string()),
+    }
+
+    Ok(datadir)
+}
+
+/// The name of the config file of `chain`.
+pub(crate) fn file_name(chain: &Chain) -> String {
+    match chain {
+        Chain::KMD => String::from("komodo.conf"), // conf name is lowercase
+        _ => format!("{}.conf", chain), // conf name is capitalized
+    }
+}
@@ -1,4 +1,4 @@
-use {RpcError, ClientError};
+use {CCErrorKind, RpcError, RpcErrorCode, ClientError};
 use core::fmt;
 use std::fmt::Formatter;
 use std::error::Error;
@@ -14,6 +14,8 @@ pub enum ApiError {
     IO(io::Error),
     ParseInt(ParseIntError),
     Hex(bitcoin::util::hash::HexError),
+    /// A CC RPC answered with `"result": "error"` and this message.
+    CC(String),
     Other(String),
 }
 
@@ -21,20 +23,101 @@ impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match *self {
             ApiError::RPC(ref cause) => write!(f, "RPC error: {}", cause.message ),
-            ApiError::Client(ref _cause) =>
-                match _cause {
-                    ClientError::Json(json_err) => fmt::Display::fmt(json_err, f),
-                    ClientError::Transport(transport_error) => fmt::Display::fmt(transport_error, f),
-                },
+            ApiError::Client(ref cause) => fmt::Display::fmt(cause, f),
             ApiError::Config(ref err) => write!(f, "{}", err),
             ApiError::IO(ref cause) => write!(f, "IO error: {:?}", cause.kind()),
             ApiError::ParseInt(ref err) => write!(f, "Parse error: {:?}", err.to_string()),
             ApiError::Hex(ref err) => write!(f, "Parse error: {:?}", err.to_string()),
+            ApiError::CC(ref err) => write!(f, "CC error: {}", err),
             ApiError::Other(ref err) => write!(f, "{}", err)
         }
     }
 }
 
+impl ApiError {
+    /// The code of a RPC error, `None` for other errors.
+    pub fn rpc_error_code(&self) -> Option<RpcErrorCode> {
+        match *self {
+            ApiError::RPC(ref err) => Some(err.error_code()),
+            _ => None,
+        }
+    }
+
+    /// komodod is starting and does not answer RPCs until it has loaded and verified its blocks.
+    pub fn is_warming_up(&self) -> bool {
+        self.rpc_error_code() == Some(RpcErrorCode::InWarmup)
+    }
+
+    /// The request was not executed and can be sent again, whatever it does: komodod refused the connection,
+    /// so it never got the request, or it is still starting up.
+    pub fn is_retryable(&self) -> bool {
+        self.is_warming_up() || self.is_connection_refused()
+    }
+
+    /// The same request may succeed later, but komodod may have executed it already: it timed out, the
+    /// connection failed after it was sent, or komodod answered with a HTTP 5xx status or is still downloading
+    /// the chain. Only requests that do not change anything, such as `getinfo`, should be sent again.
+    pub fn is_retryable_if_read_only(&self) -> bool {
+        self.is_retryable() || match *self {
+            ApiError::RPC(ref err) => err.error_code() == RpcErrorCode::ClientInInitialDownload,
+            ApiError::Client(ClientError::Transport(ref err)) => err.is_http() || err.is_timeout(),
+            ApiError::Client(ClientError::Status(status)) => status >= 500,
+            _ => false,
+        }
+    }
+
+    /// komodod refused the connection, so the request was never sent.
+    pub fn is_connection_refused(&self) -> bool {
+        let refused = |err: &io::Error| err.kind() == io::ErrorKind::ConnectionRefused;
+
+        match *self {
+            ApiError::IO(ref err) => refused(err),
+            ApiError::Client(ClientError::Transport(ref err)) => {
+                let mut source = err.source();
+                while let Some(err) = source {
+                    if err.downcast_ref::<io::Error>().is_some_and(refused) {
+                        return true;
+                    }
+                    source = err.source();
+                }
+
+                false
+            },
+            _ => false,
+        }
+    }
+
+    /// komodod rejected a transaction because its inputs are spent or unknown.
+    pub fn is_missing_inputs(&self) -> bool {
+        match *self {
+            ApiError::RPC(ref err) => err.is_missing_inputs(),
+            _ => false,
+        }
+    }
+
+    /// The kind of a CC error, `None` for other errors.
+    pub fn cc_error_kind(&self) -> Option<CCErrorKind> {
+        match *self {
+            ApiError::CC(ref message) => Some(CCErrorKind::from_message(message)),
+            _ => None,
+        }
+    }
+
+    /// The wallet cannot pay for a transaction, or a CC contract cannot pay out.
+    pub fn is_insufficient_funds(&self) -> bool {
+        self.rpc_error_code() == Some(RpcErrorCode::WalletInsufficientFunds)
+            || self.cc_error_kind() == Some(CCErrorKind::InsufficientFunds)
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(*self, ApiError::Client(ClientError::Unauthorized))
+    }
+
+    pub fn is_forbidden(&self) -> bool {
+        matches!(*self, ApiError::Client(ClientError::Forbidden))
+    }
+}
+
 impl Error for ApiError {
     fn cause(&self) -> Option<&dyn Error> {
         match *self {
@@ -44,6 +127,7 @@ impl Error for ApiError {
             ApiError::IO(ref cause) => Some(cause),
             ApiError::ParseInt(ref err) => Some(err),
             ApiError::Hex(ref err) => Some(err),
+            ApiError::CC(_) => None,
             ApiError::Other(_) => None,
         }
     }
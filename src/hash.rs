@@ -0,0 +1,127 @@
+//! Transaction ids, block hashes and the secrets of payment channels.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::util::hash::Sha256dHash;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use ApiError;
+
+macro_rules! sha256d_newtype {
+    ($(#[$attr:meta])* $name:ident, $what:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name([u8; 32]);
+
+        impl $name {
+            /// From the bytes in the order they are hashed and serialized in, which is the reverse of the hex
+            /// that komodod displays.
+            pub fn from_bytes(bytes: [u8; 32]) -> Self {
+                $name(bytes)
+            }
+
+            /// The bytes in the order they are hashed and serialized in.
+            pub fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+
+            /// From the hex that komodod displays. The same as `str::parse`.
+            pub fn from_hex(hex: &str) -> Result<Self, ApiError> {
+                let hash = Sha256dHash::from_hex(hex)
+                    .map_err(|e| ApiError::Other(format!("invalid {} {:?}: {}", $what, hex, e)))?;
+
+                Ok($name::from(hash))
+            }
+        }
+
+        impl From<Sha256dHash> for $name {
+            fn from(hash: Sha256dHash) -> Self {
+                let mut bytes = [0u8; 32];
+                bytes.copy_from_slice(&hash[..]);
+
+                $name(bytes)
+            }
+        }
+
+        impl From<$name> for Sha256dHash {
+            fn from(hash: $name) -> Self {
+                Sha256dHash::from(&hash.0[..])
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ApiError;
+
+            fn from_str(s: &str) -> Result<Self, ApiError> {
+                $name::from_hex(s)
+            }
+        }
+
+        /// The hex that komodod displays, with the bytes reversed.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for byte in self.0.iter().rev() {
+                    write!(f, "{:02x}", byte)?;
+                }
+
+                Ok(())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let hex = String::deserialize(deserializer)?;
+
+                $name::from_hex(&hex).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+sha256d_newtype!(
+    /// The id of a transaction: the double SHA-256 of its serialization.
+    ///
+    /// ```
+    /// # use komodo_rpc_client::Txid;
+    /// let txid: Txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b".parse().unwrap();
+    /// assert_eq!(txid.as_bytes()[0], 0x3b);
+    /// assert_eq!(txid.to_string(), "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b");
+    ///
+    /// assert!("4a5e1e4b".parse::<Txid>().is_err());
+    /// ```
+    Txid,
+    "txid"
+);
+
+impl Txid {
+    /// The double SHA-256 of a serialized transaction.
+    pub(crate) fn hash(data: &[u8]) -> Self {
+        Txid::from(Sha256dHash::from_data(data))
+    }
+}
+
+sha256d_newtype!(
+    /// The hash of a block: the double SHA-256 of its header.
+    BlockHash,
+    "block hash"
+);
+
+sha256d_newtype!(
+    /// A link of the hash chain of a payment channel, see `HashChain`. Unlike a `Txid`, a single SHA-256.
+    ChannelSecret,
+    "channel secret"
+);
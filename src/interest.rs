@@ -0,0 +1,153 @@
+//! KMD active user rewards ("interest"), as komodod calculates them in `komodo_interest`.
+
+use Amount;
+use Txid;
+use COIN;
+
+/// No rewards are paid for outputs from this height on.
+pub const KOMODO_ENDOFERA: u32 = 7_777_777;
+/// KIP-0001 lowered the rewards from 5% to 0.01% a year, for outputs from this height on.
+pub const KIP0001_ACTIVATION_HEIGHT: u32 = 3_484_958;
+
+/// A locktime below this is a block height, not a timestamp, and earns no rewards.
+const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+/// Rewards start once an output is an hour old, counted from the locktime of its transaction.
+const MIN_AGE_MINUTES: u64 = 60;
+const MINUTES_PER_YEAR: u64 = 365 * 24 * 60;
+const MINUTES_PER_MONTH: u64 = 31 * 24 * 60;
+/// 5% a year of `COIN`, for the calculations before height 250000.
+const KOMODO_INTEREST: u64 = 5_000_000;
+/// 5% a year, as a fraction per minute: 1 / (20 * 365 * 24 * 60).
+const PER_MINUTE_DIVISOR: u64 = 10_512_000;
+/// Tips before this time (5 April 2017) still use the original calculation.
+const ACTIVATION_TIME: u32 = 1_491_350_400;
+
+/// Outputs of more than 25000 KMD below height 155949 that komodod treats as exceptions.
+const EXCEPTIONS: [(u32, u64); 12] = [
+    (116_607, 2_502_721_100_000),
+    (126_891, 2_879_650_000_000),
+    (129_510, 3_000_000_000_000),
+    (141_549, 3_500_000_000_000),
+    (154_473, 3_983_399_350_000),
+    (154_736, 3_983_406_748_175),
+    (155_013, 3_983_414_006_565),
+    (155_492, 3_983_427_592_291),
+    (155_613, 9_997_409_999_999_797),
+    (157_927, 9_997_410_667_451_072),
+    (155_613, 2_590_000_000_000),
+    (155_949, 4_000_000_000_000),
+];
+
+/// The reward a KMD output has accrued: what komodod adds to its value when it is spent in a block
+/// with time `tip_time`.
+///
+/// `tx_height` is the height of the block that holds the output, `lock_time` the locktime of its transaction.
+/// Rewards are 5% a year, for outputs of at least 10 KMD whose transaction has a timestamp as locktime.
+/// They start an hour after the locktime and stop growing after a month (a year below height 1000000).
+/// KIP-0001 lowered them to 0.01% from height 3484958, and they end at height 7777777.
+///
+/// Only KMD pays rewards, assetchains do not.
+///
+/// ```
+/// # use komodo_rpc_client::{komodo_interest, Amount};
+/// let value = Amount::from_coins(100).unwrap();
+/// let lock_time = 1_570_000_000;
+///
+/// // a day after the locktime
+/// let interest = komodo_interest(1_600_000, value, lock_time, lock_time + 24 * 60 * 60);
+/// assert_eq!(interest, Amount::from_sat(1_313_331));
+///
+/// // not yet an hour
+/// assert_eq!(komodo_interest(1_600_000, value, lock_time, lock_time + 59 * 60), Amount::ZERO);
+/// ```
+pub fn komodo_interest(tx_height: u32, value: Amount, lock_time: u32, tip_time: u32) -> Amount {
+    if tx_height >= KOMODO_ENDOFERA
+        || lock_time < LOCKTIME_THRESHOLD
+        || tip_time == 0
+        || lock_time >= tip_time
+        || value.as_sat() < 10 * COIN {
+        return Amount::ZERO;
+    }
+
+    let n_value = value.as_sat() as u64;
+    let mut minutes = u64::from(tip_time - lock_time) / 60;
+    if minutes < MIN_AGE_MINUTES {
+        return Amount::ZERO;
+    }
+
+    if minutes > MINUTES_PER_YEAR {
+        minutes = MINUTES_PER_YEAR;
+    }
+    // `_komodo_interestnew` caps at a month
+    if tx_height >= 1_000_000 && minutes > MINUTES_PER_MONTH {
+        minutes = MINUTES_PER_MONTH;
+    }
+    if tx_height >= 250_000 {
+        minutes -= MIN_AGE_MINUTES - 1;
+    }
+
+    // at least once a year, as the reward of a longer period is capped at a year
+    let denominator = (MINUTES_PER_YEAR / minutes).max(1);
+
+    // komodod does this arithmetic in `uint64_t`, so wrapping is part of the old rules
+    let interest = if n_value > 25_000 * COIN as u64 {
+        let exception = tx_height <= 155_949 && EXCEPTIONS.contains(&(tx_height, n_value));
+
+        if !exception {
+            let numerator = n_value / 20;
+
+            if tx_height < 250_000 {
+                numerator / denominator
+            } else if tx_height < 1_000_000 {
+                numerator.wrapping_mul(minutes) / MINUTES_PER_YEAR
+            } else {
+                interest_since_1m(tx_height, n_value, minutes)
+            }
+        } else if tx_height < 1_000_000 {
+            n_value.wrapping_mul(KOMODO_INTEREST) / denominator / COIN as u64
+        } else {
+            interest_since_1m(tx_height, n_value, minutes)
+        }
+    } else {
+        let numerator = n_value.wrapping_mul(KOMODO_INTEREST);
+
+        if tx_height < 250_000 || tip_time < ACTIVATION_TIME {
+            if tx_height < 250_000 || numerator.wrapping_mul(minutes) < MINUTES_PER_YEAR {
+                numerator / denominator / COIN as u64
+            } else {
+                numerator.wrapping_mul(minutes) / MINUTES_PER_YEAR / COIN as u64
+            }
+        } else if tx_height < 1_000_000 {
+            (n_value / 20).wrapping_mul(minutes) / MINUTES_PER_YEAR
+        } else {
+            interest_since_1m(tx_height, n_value, minutes)
+        }
+    };
+
+    Amount::from_sat(interest as i64)
+}
+
+/// The result of `Client::claim_rewards`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimedRewards {
+    /// The transaction that sent the UTXOs back to their address.
+    pub txid: Txid,
+    /// The rewards the transaction claimed.
+    pub interest: Amount,
+    /// The value of its single output: the UTXOs plus `interest`, minus the fee.
+    pub amount: Amount,
+    /// The number of UTXOs it spent.
+    pub inputs: usize,
+}
+
+/// `_komodo_interestnew`, the calculation from height 1000000 on. `minutes` is already capped and
+/// reduced by the minimum age.
+fn interest_since_1m(tx_height: u32, n_value: u64, minutes: u64) -> u64 {
+    let interest = (n_value / PER_MINUTE_DIVISOR) * minutes;
+
+    if tx_height >= KIP0001_ACTIVATION_HEIGHT {
+        interest / 500
+    } else {
+        interest
+    }
+}
@@ -1,23 +1,36 @@
-use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::fmt::Debug;
-use std::collections::HashMap;
+use std::time::Duration;
 
-use os_info::Type as OSType;
 //use base64;
-use dirs;
 
-use HTTPClient;
 use RpcClient;
 use RpcRequest;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use rpcconn::{BatchResponse, ClientError, RetryPolicy, RpcBatch, RpcResponse, Transport};
+use rpcconn::retry::is_read_only;
+use rpcconn::client::{Credentials, HttpSettings};
+use serde_json::{self, Value};
+use serde_json::value::RawValue;
+#[cfg(feature = "async")]
+use reqwest::header::{HeaderMap, AUTHORIZATION};
+#[cfg(feature = "async")]
+use reqwest::async::Client as AsyncHttpClient;
+#[cfg(feature = "async")]
+use rpcconn::AsyncRpcClient;
+#[cfg(feature = "async")]
+use asynccore::AsyncClient;
 
 use serde::{de::DeserializeOwned, ser::Serialize};
 
-use TransactionId;
+use Txid;
+use ChannelSecret;
 use BlockHash;
 use KomodoRpcApi;
+use TransactionBuilder;
+use interest::ClaimedRewards;
 use chains::Chain;
+use conf::{default_datadir, KomodoConf};
 
 use arguments::*;
 use types::*;
@@ -25,13 +38,19 @@ use types::*;
 use error::ApiError;
 use types::arguments::address::{Address, Amounts};
 use arguments::address::{AddrType, FromAddresses};
-use std::io::{Error as IOError, ErrorKind};
 
 type Result<T> = std::result::Result<T, ApiError>;
 
+/// A client for a komodod instance. By default it talks to komodod over HTTP through a `RpcClient`,
+/// but any `Transport` can be used, such as a `MockTransport` for testing.
+///
+/// Requests that fail because komodod refused the connection or is starting up, and read-only requests that
+/// fail in a way komodod may have executed them, are retried according to its `RetryPolicy`, which does not
+/// retry unless one is set.
 #[derive(Debug)]
-pub struct Client {
-    client: RpcClient,
+pub struct Client<T = RpcClient> {
+    client: T,
+    retry_policy: RetryPolicy,
 }
 
 impl Client {
@@ -39,12 +58,9 @@ impl Client {
     /// been installed and run at least once, since it fetches the needed RPC authentication parameters
     /// from the config file which is created upon initialisation.
     pub fn new_komodo_client() -> Result<Client> {
-        let config = Config::get_for(&Chain::KMD)?;
-        let rpc_client = Client::construct_rpc_client(&config);
-
-        Ok(Client {
-            client: rpc_client
-        })
+        Client::builder()
+            .chain(Chain::KMD)
+            .build()
     }
 
     /// Constructs a new `Client` that talks to the specified assetchain. It assumes Komodo has
@@ -54,1287 +70,426 @@ impl Client {
     /// If `Custom(String)` is used as parameter, this function will throw a std::io::Error::NotFound if
     /// the defined config folder does not exist.
     pub fn new_assetchain_client(ac: &Chain) -> Result<Self> {
-        let config = Config::get_for(&ac)?;
-        let rpc_client = Client::construct_rpc_client(&config);
-
-        Ok(Client {
-            client: rpc_client,
-        })
-    }
-
-    fn construct_rpc_client(config: &Config) -> RpcClient {
-        let mut headers = HeaderMap::new();
-
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!(
-                "Basic {}",
-                base64::encode(&format!("{}:{}", config.rpc_user, config.rpc_password))
-            )).unwrap(),
-        );
-
-        let client = HTTPClient::builder()
-            .default_headers(headers)
+        Client::builder()
+            .chain(ac.clone())
             .build()
-            .expect("unable to create http client");
-
-        RpcClient::new(client, &format!("http://127.0.0.1:{}", config.rpc_port))
     }
 
-    fn send<R: DeserializeOwned + Debug, P: Serialize + Debug>(
-        &self,
-        request: &RpcRequest<P>
-    ) -> Result<R> {
-//        dbg!(&request);
-        self.client.send(request)
+    /// Returns a `ClientBuilder` to configure the connection to a komodod instance manually,
+    /// for example when it runs in a container or on a remote host.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
     }
-}
-
-struct Config {
-    rpc_user: String,
-    rpc_password: String,
-    rpc_port: u16,
-}
-
-impl Config {
-    pub fn get_for(chain: &Chain) -> Result<Self> {
-        let mut config_path: PathBuf;
-
-        // find location of configuration file:
-        match os_info::get().os_type() {
-            OSType::Ubuntu | OSType::Linux => {
-                // Linux: /home/$USER/
-                if let Some(mut path) = dirs::home_dir() {
-                    path.push(".komodo");
-                    config_path = path;
-                } else {
-                    return Err(ApiError::Other(String::from("no komodod installation found")))
-                }
-            },
-            OSType::Macos | OSType::Windows => {
-                // MacOS: /Users/Alice/Library/Application Support
-                // Windows: C:\Users\Alice\AppData\Roaming
-                if let Some(mut path) = dirs::data_dir() {
-                    path.push("Komodo");
-                    config_path = path;
-                } else {
-                    return Err(ApiError::Other(String::from("no komodod installation found")))
-                }
-            },
-            _ => return Err(ApiError::Other(String::from("unknown or unsupported operating system")))
-        }
-
-        // push the actual configuration file:
-        match chain {
-            Chain::KMD => {
-                config_path.push("komodo.conf"); // conf name is lowercase
-            },
-            Chain::Custom(chain) => {
-                config_path.push(chain);
-
-                if !config_path.is_dir() {
-                    return Err(ApiError::IO(IOError::from(ErrorKind::NotFound)));
-                }
 
-                config_path.push(format!("{}.conf", chain.to_string()));
-            },
-            // assetchain configuration files live in their own directory:
-            _ => {
-                config_path.push(chain.to_string());
-                config_path.push(format!("{}.conf", chain.to_string())); // conf name is capitalized
-            }
-        }
-
-        let contents = fs::read_to_string(config_path.to_str().unwrap())?;
-
-        let map: HashMap<String, String> = contents.as_str()
-            .split('\n')
-            .map(|line| line.splitn(2, '=').collect::<Vec<&str>>())
-            .filter(|vec| vec.len() == 2)
-            .map(|vec| (
-                vec[0].to_string(),
-                vec[1].to_string()
-            ))
-            .collect::<HashMap<String, String>>();
-
-        let _rpc_user = map.get("rpcuser").ok_or(ApiError::Config(String::from("No rpcuser in config")))?;
-        let _rpc_password = map.get("rpcpassword").ok_or(ApiError::Config(String::from("no rpcpassword in config file")))?;
-        let _rpc_port =
-            match chain {
-                Chain::KMD => "7771", // KMD doesn't put rpcport in conf file at install
-                _ => map.get("rpcport").ok_or(ApiError::Config(String::from("no rpcport in config file")))?,
-            };
-
-        Ok(Config {
-            rpc_user:       _rpc_user.to_owned(),
-            rpc_password:   _rpc_password.to_owned(),
-            rpc_port:       _rpc_port.parse::<u16>()?
+    /// The same client, but with `timeout` for every method, for a call that takes longer than usual.
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use komodo_rpc_client::{Client, KomodoRpcApi};
+    /// let client = Client::new_komodo_client().unwrap();
+    /// let info = client.with_timeout(Duration::from_secs(1800)).unwrap().get_tx_out_set_info().unwrap();
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Result<Client> {
+        Ok(Client {
+            client: self.client.with_timeout(timeout)?,
+            retry_policy: self.retry_policy,
         })
     }
 }
 
-impl KomodoRpcApi for Client {
-    fn get_address_balance(&self, addresses: &arguments::AddressList) -> Result<AddressBalance> {
-        self.send(&RpcRequest::new1(
-            "getaddressbalance",
-            addresses
-        ))
-    }
-
-    fn get_address_deltas(&self, addresses: &AddressList) -> Result<AddressDeltas> {
-        self.send(&RpcRequest::new1(
-            "getaddressdeltas",
-            addresses
-        ))
-    }
-
-    fn get_address_mempool(&self, addresses: &AddressList) -> Result<AddressMempool> {
-        self.send(&RpcRequest::new1(
-            "getaddressmempool",
-            addresses
-        ))
-    }
-
-    fn get_address_tx_ids(&self, addresses: &AddressList) -> Result<AddressTxIDs> {
-        self.send(&RpcRequest::new1(
-            "getaddresstxids",
-            addresses
-        ))
-    }
-
-    fn get_address_utxos(&self, addresses: &AddressList) -> Result<AddressUtxos> {
-        self.send(&RpcRequest::new1(
-            "getaddressutxos",
-            addresses
-        ))
-    }
-
-    fn get_snapshot_max(&self, n: u32) -> Result<Snapshot> {
-        // parameter must be string:
-        let n = n.to_string();
-        self.send(&RpcRequest::new1(
-            "getsnapshot",
-            n
-        ))
-    }
-
-    fn get_snapshot(&self) -> Result<Snapshot> {
-        self.send(&RpcRequest::new0(
-            "getsnapshot"
-        ))
-    }
-
-    fn coinsupply(&self, n: u32) -> Result<Coinsupply> {
-        let n = n.to_string();
-        self.send(&RpcRequest::new1(
-            "coinsupply",
-            n
-        ))
-    }
-
-    fn get_best_block_hash(&self) -> Result<BlockHash> {
-        self.send(&RpcRequest::new0(
-            "getbestblockhash",
-        ))
-    }
-
-    fn get_block(&self, hashorheight: String) -> Result<Block> {
-        self.send(&RpcRequest::new1(
-            "getblock",
-            hashorheight
-        ))
-    }
-
-    fn get_blockchain_info(&self) -> Result<BlockchainInfo> {
-        self.send(&RpcRequest::new0(
-            "getblockchaininfo"
-        ))
-    }
-
-    fn get_block_count(&self) -> Result<Blockcount> {
-        self.send(&RpcRequest::new0(
-            "getblockcount"
-        ))
-    }
-
-    fn get_block_hash(&self, n: u32) -> Result<BlockHash> {
-        self.send(&RpcRequest::new1(
-            "getblockhash",
-            n
-        ))
-    }
-
-    fn get_block_header(&self, hash: String) -> Result<BlockHeader> {
-        self.send(&RpcRequest::new1(
-            "getblockheader",
-            hash
-        ))
-    }
-
-    fn get_chaintips(&self) -> Result<ChainTips> {
-        self.send(&RpcRequest::new0(
-            "getchaintips"
-        ))
-    }
-
-    fn get_difficulty(&self) -> Result<f64> {
-        self.send(&RpcRequest::new0(
-            "getdifficulty",
-        ))
-    }
-
-    fn get_mempool_info(&self) -> Result<MempoolInfo> {
-        self.send(&RpcRequest::new0(
-            "getmempoolinfo"
-        ))
-    }
-
-    fn get_raw_mempool(&self) -> Result<RawMempool> {
-        self.send(&RpcRequest::new0(
-            "getrawmempool"
-        ))
-    }
-
-    fn get_raw_mempool_verbose(&self) -> Result<RawMempoolVerbose> {
-        self.send(&RpcRequest::new1(
-            "getrawmempool",
-            true
-        ))
-    }
-
-    fn get_tx_out(&self, txid: String, index: u8) -> Result<TxOut> {
-        self.send(&RpcRequest::new2(
-            "gettxout",
-            txid,
-            index
-        ))
-    }
-
-    fn get_tx_out_set_info(&self) -> Result<TxOutSetInfo> {
-        self.send(&RpcRequest::new0(
-            "gettxoutsetinfo"
-        ))
-    }
-
-    fn minerids(&self, height: String) -> Result<MinerIDs> { // why is height a string?
-        self.send(&RpcRequest::new1(
-            "minerids",
-            height
-        ))
-    }
-
-    fn notaries(&self, height: String) -> Result<Notaries> { // why is height a string?
-        self.send(&RpcRequest::new1(
-            "notaries",
-            height
-        ))
-    }
-
-    fn get_info(&self) -> Result<Info> {
-        self.send(&RpcRequest::new0(
-            "getinfo"
-        ))
-    }
-
-    fn get_block_subsidy(&self, height: Option<u64>) -> Result<BlockSubsidy> {
-        match height {
-            Some(n) => {
-                self.send(&RpcRequest::new1(
-                    "getblocksubsidy",
-                    n
-                ))
-            },
-            None => {
-                self.send(&RpcRequest::new0(
-                    "getblocksubsidy",
-                ))
-            }
+impl<T: Transport> Client<T> {
+    /// Constructs a `Client` that sends its requests through `transport`.
+    pub fn with_transport(transport: T) -> Client<T> {
+        Client {
+            client: transport,
+            retry_policy: RetryPolicy::none(),
         }
     }
 
-    fn get_block_template(&self, jsonrequestobject: Option<&RequestObject>) -> Result<BlockTemplate> {
-        match jsonrequestobject {
-            Some(request) => {
-                self.send(&RpcRequest::new1(
-                    "getblocktemplate",
-                    request
-                ))
-            },
-            None => {
-                self.send(&RpcRequest::new0(
-                    "getblocktemplate",
-                ))
-            }
-        }    }
-
-    fn get_local_sol_ps(&self) -> Result<f64> {
-        self.send(&RpcRequest::new0(
-            "getlocalsolps"
-        ))
-    }
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = retry_policy;
 
-    fn get_mining_info(&self) -> Result<MiningInfo> {
-        self.send(&RpcRequest::new0(
-            "getmininginfo"
-        ))
+        self
     }
 
-    fn get_network_hash_ps(&self) -> Result<u64> {
-        self.send(&RpcRequest::new0(
-            "getnetworkhashps"
-        ))
-    }
+    /// Polls `getinfo` until komodod answers it, which it does once it has loaded and verified its blocks,
+    /// and returns its answer. Waits as the `RetryPolicy` of this client does, but for up to `timeout`.
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<Info> {
+        let policy = RetryPolicy {
+            max_elapsed_time: timeout,
+            ..self.retry_policy
+        };
 
-    fn get_network_sol_ps(&self) -> Result<u64> {
-        self.send(&RpcRequest::new0(
-            "getnetworksolps"
-        ))
+        policy.retry(true, || self.send_once(&RpcRequest::new0("getinfo")))
     }
 
-    // todo untested
-    fn prioritise_transaction(&self, txid: TransactionId, prio_delta: f64, fee_delta: u32) -> Result<bool> {
-        self.send(&RpcRequest::new3(
-            "prioritisetransaction",
-            txid,
-            prio_delta,
-            fee_delta
-        ))
+    /// The transport this client sends its requests through.
+    pub fn transport(&self) -> &T {
+        &self.client
     }
 
-    // todo untested
-    fn submit_block(&self, hexdata: String, jsonparametersobject: Option<ParametersObject>) -> Result<SubmitBlockResult> {
-        match jsonparametersobject {
-            Some(object) => {
-                self.send(&RpcRequest::new2(
-                    "submitblock",
-                    hexdata,
-                    object
-                ))
-            },
-            None => {
-                self.send(&RpcRequest::new1(
-                    "submitblock",
-                    hexdata
-                ))
-            }
+    /// Sends all requests in `batch` to komodod in a single round trip.
+    /// See `RpcBatch` for how to retrieve the responses.
+    ///
+    /// The batch is retried as `RetryPolicy` describes for a read-only request if all of its requests are
+    /// read-only. Otherwise it is not retried at all, not even when komodod could not be reached.
+    pub fn send_batch(&self, batch: &RpcBatch) -> Result<BatchResponse> {
+        let body = serde_json::to_string(batch.requests()).map_err(ClientError::Json)?;
+        let read_only = batch.methods().iter().all(|method| is_read_only(method));
+        let responses = if read_only {
+            self.retry_policy.retry(true, || self.client.send_text(&body))?
+        } else {
+            self.client.send_text(&body)?
+        };
+        let responses: Vec<RpcResponse<Box<RawValue>>> = serde_json::from_str(&responses).map_err(ClientError::Json)?;
+
+        Ok(batch.match_responses(responses))
+    }
+
+    /// Claims the KMD rewards of `address` by sending all its spendable UTXOs back to it, minus `fee`.
+    ///
+    /// The UTXOs come from `listunspent`, so `address` must be in the wallet, which also signs the transaction.
+    /// The rewards are those `listunspent` reports for each UTXO: komodod rejects a transaction that claims more
+    /// than it allows, so they are not calculated here. The transaction gets a locktime of the current time - 777
+    /// seconds, so its own outputs earn rewards again, and expires like one komodod makes.
+    ///
+    /// Fails on assetchains, which pay no rewards, and when the rewards do not cover `fee`.
+    pub fn claim_rewards(&self, address: &Address, fee: Amount) -> Result<ClaimedRewards> {
+        let info = self.get_info()?;
+        if info.name != "KMD" {
+            return Err(ApiError::Other(format!("rewards can only be claimed on KMD, not on {}", info.name)));
         }
-    }
 
-//    fn addnode(&self, node: String, action: AddNodeCommand) -> Result<()> {
-//        self.send(&RpcRequest::new2(
-//            "addnode",
-//            node,
-//            action
-//        ))
-//    }
-
-//    fn clear_banned(&self) -> Result<()> {
-//        self.send(&RpcRequest::new0(
-//            "clearbanned",
-//        ))
-//    }
-
-//    fn disconnect_node(&self, node: String) -> Result<()> {
-//        self.send(&RpcRequest::new1(
-//            "addnode",
-//            node
-//        ))
-//    }
-
-    fn get_added_node_info(&self, dns: bool, node: Option<&str>) -> Result<Vec<AddedNodeInfo>> {
-        match node {
-            Some(address) => {
-                self.send(&RpcRequest::new2(
-                    "getaddednodeinfo",
-                    dns,
-                    address
-                ))
-            },
-            None => {
-                self.send(&RpcRequest::new1(
-                    "getaddednodeinfo",
-                    dns
-                ))
-            }
+        let unspents = self.list_unspent(None, None, Some(vec![address.clone()]))?;
+        let overflow = || ApiError::Other(format!("the UTXOs of {} add up to more than an amount can hold", address));
+
+        let mut builder = TransactionBuilder::new();
+        let mut total = Amount::ZERO;
+        let mut interest = Amount::ZERO;
+        let mut inputs = 0;
+        for unspent in unspents.iter().filter(|unspent| unspent.spendable) {
+            interest = interest.checked_add(unspent.interest.unwrap_or(Amount::ZERO)).ok_or_else(overflow)?;
+            total = total.checked_add(unspent.amount).ok_or_else(overflow)?;
+            inputs += 1;
+            builder.add_unspent(unspent);
         }
-    }
 
-    fn get_connection_count(&self) -> Result<u32> {
-        self.send(&RpcRequest::new0(
-            "getconnectioncount"
-        ))
-    }
-
-    fn get_deprecation_info(&self) -> Result<DeprecationInfo> {
-        self.send(&RpcRequest::new0(
-            "getdeprecationinfo"
-        ))
-    }
+        if inputs == 0 {
+            return Err(ApiError::Other(format!("no spendable UTXOs for {}", address)));
+        }
+        if interest <= fee {
+            return Err(ApiError::Other(format!("rewards of {} do not cover the fee of {}", interest, fee)));
+        }
 
-    fn get_net_totals(&self) -> Result<NetTotals> {
-        self.send(&RpcRequest::new0(
-            "getnettotals"
-        ))
-    }
+        let amount = total.checked_add(interest)
+            .and_then(|amount| amount.checked_sub(fee))
+            .ok_or_else(overflow)?;
+        let unsigned = builder
+            .add_output(address, amount)
+            .set_rewards(interest)
+            .expire_after(info.blocks + 1)
+            .build()?;
+
+        let signed = self.sign_raw_transaction_with_wallet(SerializedRawTransaction(unsigned.to_hex()))?;
+        if !signed.complete {
+            return Err(ApiError::Other(format!("wallet could not sign all inputs of {}", address)));
+        }
+        let txid = self.send_raw_transaction(&signed)?;
 
-    fn get_network_info(&self) -> Result<NetworkInfo> {
-        self.send(&RpcRequest::new0(
-            "getnetworkinfo"
-        ))
+        Ok(ClaimedRewards {
+            txid,
+            interest,
+            amount,
+            inputs,
+        })
     }
 
-    fn get_peer_info(&self) -> Result<Vec<Peer>> {
-        self.send(&RpcRequest::new0(
-            "getpeerinfo"
-        ))
+    /// The open orders of the assets DEX, of `tokenid` or of all tokens, grouped by token.
+    pub fn order_book(&self, tokenid: Option<&Txid>) -> Result<OrderBook> {
+        Ok(OrderBook::from(self.token_orders(tokenid)?))
     }
 
-    fn list_banned(&self) -> Result<Vec<Option<BannedNode>>> {
-        self.send(&RpcRequest::new0(
-            "listbanned"
-        ))
+    /// Calls `method` with `params` and deserializes its result into `R`. This reaches the RPCs that
+    /// `KomodoRpcApi` does not cover, such as those of a single assetchain or a new version of komodod.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate komodo_rpc_client;
+    /// # use komodo_rpc_client::{Client, MockTransport};
+    /// # fn main() {
+    /// # let mut transport = MockTransport::new();
+    /// # transport.add_method_response("addnode", json!(null));
+    /// # let client = Client::with_transport(transport);
+    /// client.call::<()>("addnode", vec![json!("5.9.102.210"), json!("onetry")]).unwrap();
+    /// # }
+    /// ```
+    pub fn call<R: DeserializeOwned + Debug>(&self, method: &str, params: Vec<Value>) -> Result<R> {
+        self.send(&RpcRequest::with_params(method, params))
+    }
+
+    /// Calls `method` with `params` and returns its result as JSON, `Value::Null` if it has none.
+    pub fn call_raw(&self, method: &str, params: Vec<Value>) -> Result<Value> {
+        self.call(method, params)
     }
 
-//    fn ping(&self) -> Result<()> {
-//        self.send(&RpcRequest::new0(
-//            "ping"
-//        ))
-//    }
-
-    fn set_ban(
+    fn send<R: DeserializeOwned + Debug, P: Serialize + Debug>(
         &self,
-        ip: String,
-        command: String,
-        bantime: Option<u32>,
-        absolute: Option<bool>
-    ) -> Result<()> {
-        match bantime {
-            Some(time) => {
-                match absolute {
-                    Some(true) => {
-                        self.send(&RpcRequest::new4(
-                            "setban",
-                            ip,
-                            command,
-                            time,
-                            true
-                        ))
-                    },
-                    Some(false) => {
-                        self.send(&RpcRequest::new4(
-                            "setban",
-                            ip,
-                            command,
-                            time,
-                            false
-                        ))
-                    },
-                    None => unreachable!()
-                }
-            },
-            None => {
-                self.send(&RpcRequest::new2(
-                    "setban",
-                    ip,
-                    command,
-                ))
-            }
-        }
-    }
-
-    fn create_raw_transaction(&self, inputs: CreateRawTransactionInputs, outputs: CreateRawTransactionOutputs) -> Result<SerializedRawTransaction> {
-        self.send(&RpcRequest::new2(
-            "createrawtransaction",
-            inputs,
-            outputs
-        ))
+        request: &RpcRequest<P>
+    ) -> Result<R> {
+        self.retry_policy.retry(is_read_only(request.method()), || self.send_once(request))
     }
 
-    fn decode_raw_transaction(&self, hexstring: &str) -> Result<RawTransaction> {
-        self.send(&RpcRequest::new1(
-            "decoderawtransaction",
-            hexstring
-        ))
-    }
+    fn send_once<R: DeserializeOwned + Debug, P: Serialize + Debug>(
+        &self,
+        request: &RpcRequest<P>
+    ) -> Result<R> {
+        let body = serde_json::to_string(request).map_err(ClientError::Json)?;
+        let response = self.client.send_text(&body)?;
+        let response: RpcResponse<Box<RawValue>> = serde_json::from_str(&response).map_err(ClientError::Json)?;
 
-    fn decode_script(&self, hexstring: &str) -> Result<DecodedScript> {
-        self.send(&RpcRequest::new1(
-            "decodescript",
-            hexstring
-        ))
+        response.into_typed_result()
     }
 
-    fn get_raw_transaction(&self, txid: arguments::TransactionId) -> Result<SerializedRawTransaction> {
-        self.send(&RpcRequest::new1(
-            "getrawtransaction",
-            txid
-        ))
+    /// Sends a request to a CC RPC, see `cc_result`.
+    fn send_cc<R: DeserializeOwned, P: Serialize + Debug>(&self, request: &RpcRequest<P>) -> Result<R> {
+        cc_result(self.send(request)?)
     }
 
-    fn get_raw_transaction_verbose(&self, txid: arguments::TransactionId) -> Result<RawTransaction> {
-        self.send(&RpcRequest::new2(
-            "getrawtransaction",
-            txid,
-            1
-        ))
-    }
+    /// Sends a request to a CC RPC that answers with a transaction for `send_raw_transaction`.
+    fn send_cc_transaction<P: Serialize + Debug>(&self, request: &RpcRequest<P>) -> Result<SignedRawTransaction> {
+        let tx: CCTransaction = self.send_cc(request)?;
 
-    fn sign_raw_transaction_with_wallet(&self, hexstring: SerializedRawTransaction) -> Result<SignedRawTransaction> {
-        self.send(&RpcRequest::new1(
-            "signrawtransaction",
-            hexstring
-        ))
+        Ok(tx.into())
     }
+}
 
-    fn sign_raw_transaction_with_key(
-        &self,
-        hexstring: &SerializedRawTransaction,
-        txoutput_detail: Option<&P2SHInputSet>,
-        private_keys: Option<Vec<&PrivateKey>>,
-        signature_hash_type: Option<SigHashType>
-    ) -> Result<SignedRawTransaction> {
-        self.send(&RpcRequest::new4(
-            "signrawtransaction",
-            hexstring,
-            txoutput_detail,
-            private_keys,
-            signature_hash_type
-        ))
-    }
+/// Builds a `Client` from explicit connection parameters, a chain's config file, or both.
+///
+/// Values that are set explicitly take precedence over the values found in the config file.
+/// When a `Chain` is set, its config file is read from the default location, or from `datadir`
+/// if that is set. Without a `Chain`, at least the RPC credentials and port need to be supplied.
+///
+/// ```no_run
+/// # use komodo_rpc_client::{Client, Chain};
+/// let client = Client::builder()
+///     .host("10.0.0.12")
+///     .port(7771)
+///     .rpc_user("user")
+///     .rpc_password("password")
+///     .build();
+///
+/// let assetchain_client = Client::builder()
+///     .chain(Chain::Custom(String::from("MYCHAIN")))
+///     .datadir("/var/lib/komodo/MYCHAIN")
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    chain: Option<Chain>,
+    host: Option<String>,
+    port: Option<u16>,
+    rpc_user: Option<String>,
+    rpc_password: Option<String>,
+    cookie_file: Option<PathBuf>,
+    datadir: Option<PathBuf>,
+    retry_policy: Option<RetryPolicy>,
+    http: HttpSettings,
+}
 
-    fn send_raw_transaction(&self, signed_tx: &SignedRawTransaction) -> Result<TransactionId> {
-        self.send(&RpcRequest::new1(
-            "sendrawtransaction",
-            &signed_tx.hex
-        ))
+impl ClientBuilder {
+    pub fn new() -> Self {
+        ClientBuilder::default()
     }
 
-    fn create_multisig(&self, nrequired: u8, keys: Vec<&str>) -> Result<Multisig> {
-        self.send(&RpcRequest::new2(
-            "createmultisig",
-            nrequired,
-            keys
-        ))
-    }
+    /// Reads the RPC parameters of this chain from its config file.
+    pub fn chain(&mut self, chain: Chain) -> &mut Self {
+        self.chain = Some(chain);
 
-    fn backup_wallet(&self, file_name: &str) -> Result<String> {
-        self.send(&RpcRequest::new1(
-            "backupwallet",
-            file_name
-        ))
+        self
     }
 
-    fn dump_privkey(&self, address: &str) -> Result<String> {
-        self.send(&RpcRequest::new1(
-            "dumpprivkey",
-            address
-        ))
-    }
+    /// Defaults to `rpcconnect` in the config file, or `127.0.0.1`.
+    pub fn host(&mut self, host: &str) -> &mut Self {
+        self.host = Some(host.to_string());
 
-    fn dump_wallet(&self, filename: &str) -> Result<String> {
-        self.send(&RpcRequest::new1(
-            "dumpwallet",
-            filename
-        ))
+        self
     }
 
-    fn get_balance(&self, minconf: Option<u32>, include_watchonly: Option<bool>) -> Result<f64> {
-        let second;
-        let third;
-
-        match (minconf, include_watchonly) {
-            (Some(minconf), Some(wo)) => {
-                second = minconf;
-                third = wo;
-            },
-            (Some(minconf), _) => {
-                second = minconf;
-                third = false;
-            },
-            (_, Some(wo)) => {
-                second = 1;
-                third = wo;
-            },
-            _ => {
-                second = 1;
-                third = false;
-            }
-        }
-        self.send(&RpcRequest::new3(
-            "getbalance",
-            "*",
-            second,
-            third
-        ))
-    }
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.port = Some(port);
 
-    fn get_new_address(&self) -> Result<String> {
-        self.send(&RpcRequest::new0(
-            "getnewaddress",
-        ))
+        self
     }
 
-    fn get_raw_change_address(&self) -> Result<Address> {
-        self.send(&RpcRequest::new0(
-            "getrawchangeaddress",
-        ))
-    }
+    pub fn rpc_user(&mut self, rpc_user: &str) -> &mut Self {
+        self.rpc_user = Some(rpc_user.to_string());
 
-    fn get_received_by_address(&self, address: &Address) -> Result<f64> {
-        self.send(&RpcRequest::new1(
-            "getreceivedbyaddress",
-            &address.addr
-        ))
+        self
     }
 
-    fn get_transaction(
-        &self,
-        tx: &TransactionId,
-    ) -> Result<Transaction> {
-        self.send(&RpcRequest::new1(
-            "gettransaction",
-            tx,
-        ))
-    }
+    pub fn rpc_password(&mut self, rpc_password: &str) -> &mut Self {
+        self.rpc_password = Some(rpc_password.to_string());
 
-    fn get_unconfirmed_balance(&self) -> Result<f64> {
-        self.send(&RpcRequest::new0(
-            "getunconfirmedbalance"
-        ))
+        self
     }
 
-    fn get_wallet_info(&self) -> Result<WalletInfo> {
-        self.send(&RpcRequest::new0(
-            "getwalletinfo"
-        ))
-    }
+    /// A cookie file contains `user:password` on a single line. If set, its credentials are
+    /// used instead of `rpc_user` and `rpc_password`.
+    ///
+    /// Without credentials, the cookie file of `chain` is used: `.cookie` in its data directory, or
+    /// `rpccookiefile` of its config file. Without a chain, `.cookie` in `datadir` is used. komodod writes it when it runs without `rpcpassword`.
+    /// The blocking `Client` reads it again when komodod answers with 401, for example after a restart.
+    pub fn cookie_file<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.cookie_file = Some(path.into());
 
-    // fn import_address(&self, address: &Address, label: Option<String>, rescan: bool) -> Result<()> {
-    //    match label {
-    //        Some(label) => self.send(&RpcRequest::new3(
-    //            "importaddress",
-    //            &label,
-    //            rescan
-    //        )),
-    //        None => self.send(&RpcRequest::new3(
-    //            "importaddress",
-    //            "",
-    //            rescan
-    //        ))
-    //    }
-    //     unimplemented!()
-    // }
-
-    fn import_privkey(&self, key: &str, label: Option<&str>, rescan: bool) -> Result<Address> {
-        match label {
-            Some(label) => self.send(&RpcRequest::new3(
-                "importaddress",
-                key,
-                &label,
-                rescan
-            )),
-            None => self.send(&RpcRequest::new3(
-                "importaddress",
-                key,
-                "",
-                rescan
-            ))
-        }
+        self
     }
 
+    /// Overrides the directory the config file of `chain` is read from. This is the directory
+    /// that would be passed to komodod as `-datadir`.
+    pub fn datadir<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.datadir = Some(path.into());
 
-    fn import_wallet(&self, _path: &str) -> Result<()> {
-        unimplemented!()
+        self
     }
 
-    fn list_address_groupings(&self) -> Result<AddressGrouping> {
-//        self.send(&RpcRequest::new0(
-//            "listaddressgroupings"
-//        ))
-        unimplemented!()
-    }
-
-    fn list_lock_unspent(&self) -> Result<Vec<LockedUnspent>> {
-        self.send(&RpcRequest::new0(
-            "listlockunspent"
-        ))
-    }
+    /// How long a request may take. Defaults to `DEFAULT_TIMEOUT`, 30 seconds.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http.timeout = Some(timeout);
 
-    fn list_received_by_address(&self, minconf: Option<u32>, include_empty: Option<bool>, include_watch_only: Option<bool>) -> Result<Vec<ReceivedByAddress>> {
-        match (minconf, include_empty, include_watch_only) {
-            (Some(min), None, None) => self.send(&RpcRequest::new1(
-                "listreceivedbyaddress",
-                min
-            )),
-            (None, Some(b), None) => self.send(&RpcRequest::new2(
-                "listreceivedbyaddress",
-                1,
-                b
-            )),
-            (Some(min), Some(b), None) => self.send(&RpcRequest::new2(
-                "listreceivedbyaddress",
-                min,
-                b
-            )),
-            (None, None, Some(wo)) => self.send(&RpcRequest::new3(
-                "listreceivedbyaddress",
-                1,
-                false,
-                wo
-            )),
-            (None, Some(b), Some(wo)) => self.send(&RpcRequest::new3(
-                "listreceivedbyaddress",
-                1,
-                b,
-                wo
-            )),
-            (Some(min), None, Some(wo)) => self.send(&RpcRequest::new3(
-                "listreceivedbyaddress",
-                min,
-                false,
-                wo
-            )),
-            (Some(min), Some(b), Some(wo)) => self.send(&RpcRequest::new3(
-                "listreceivedbyaddress",
-                min,
-                b,
-                wo
-            )),
-            _ => self.send(&RpcRequest::new0(
-                "listreceivedbyaddress",
-            ))
-        }
+        self
     }
 
-    fn list_since_block(&self, blockhash: Option<&str>, confs: Option<u64>, include_watch_only: Option<bool>) -> Result<TxListSinceBlock> {
-        match (blockhash, confs, include_watch_only) {
-            (Some(hash), None, None) => self.send(&RpcRequest::new1(
-                "listsinceblock",
-                hash
-            )),
-            (Some(hash), Some(confs), None) => self.send(&RpcRequest::new2(
-                "listsinceblock",
-                hash,
-                confs
-            )),
-            (Some(hash), None, Some(watchonly)) => self.send(&RpcRequest::new3(
-                "listsinceblock",
-                hash,
-                1,
-                watchonly
-            )),
-            (Some(hash), Some(confs), Some(watchonly)) => self.send(&RpcRequest::new3(
-                "listsinceblock",
-                hash,
-                confs,
-                watchonly
-            )),
-            (None, Some(confs), None) => self.send(&RpcRequest::new2(
-                "listsinceblock",
-                "",
-                confs
-            )),
-            (None, Some(confs), Some(watchonly)) => self.send(&RpcRequest::new3(
-                "listsinceblock",
-                "",
-                confs,
-                watchonly
-            )),
-            (None, None, Some(watchonly)) => self.send(&RpcRequest::new3(
-                "listsinceblock",
-                "",
-                1,
-                watchonly
-            )),
-            _ => self.send(&RpcRequest::new0(
-                "listsinceblock"
-            )),
-        }
-    }
+    /// How long connecting to komodod may take. By default only `timeout` applies.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http.connect_timeout = Some(timeout);
 
-    fn list_transactions(&self, count: Option<u32>, from: Option<u32>, include_watch_only: Option<bool>) -> Result<ListTransactions> {
-        match (count, from, include_watch_only) {
-            (Some(count), None, None) => self.send(&RpcRequest::new2(
-                "listtransactions",
-                "",
-                count
-            )),
-            (Some(count), Some(from), None) => self.send(&RpcRequest::new3(
-                "listtransactions",
-                "",
-                count,
-                from
-            )),
-            (Some(count), None, Some(watchonly)) => self.send(&RpcRequest::new4(
-                "listtransactions",
-                "",
-                count,
-                0,
-                watchonly
-            )),
-            (Some(count), Some(from), Some(watchonly)) => self.send(&RpcRequest::new4(
-                "listtransactions",
-                "",
-                count,
-                from,
-                watchonly
-            )),
-            (None, Some(from), None) => self.send(&RpcRequest::new3(
-                "listtransactions",
-                "",
-                10,
-                from
-            )),
-            (None, None, Some(watchonly)) => self.send(&RpcRequest::new4(
-                "listtransactions",
-                "",
-                10,
-                0,
-                watchonly
-            )),
-            (None, Some(from), Some(watchonly)) => self.send(&RpcRequest::new4(
-                "listtransactions",
-                "",
-                10,
-                from,
-                watchonly
-            )),
-            _ => self.send(&RpcRequest::new0(
-                "listtransactions"
-            )),
-        }
+        self
     }
 
-    fn list_unspent(&self, minconf: Option<u32>, maxconf: Option<u32>, addr_filter: Option<Vec<Address>>) -> Result<Vec<Unspent>> {
-        match (minconf, maxconf, addr_filter) {
-            (None, None, Some(filter)) => self.send(&RpcRequest::new3(
-                "listunspent",
-                1,
-                9999999,
-                filter
-            )),
-            (Some(min), None, Some(filter)) => self.send(&RpcRequest::new3(
-                "listunspent",
-                min,
-                9999999,
-                filter
-            )),
-            (None, Some(max), Some(filter)) => self.send(&RpcRequest::new3(
-                "listunspent",
-                1,
-                max,
-                filter
-            )),
-            (Some(min), Some(max), Some(filter)) => self.send(&RpcRequest::new3(
-                "listunspent",
-                min,
-                max,
-                filter
-            )),
-            (Some(min), None, None) => self.send(&RpcRequest::new2(
-                "listunspent",
-                min,
-                9999999
-            )),
-            (None, Some(max), None) => self.send(&RpcRequest::new2(
-                "listunspent",
-                1,
-                max
-            )),
-            (Some(min), Some(max), None) => self.send(&RpcRequest::new2(
-                "listunspent",
-                min,
-                max
-            )),
-            _ => self.send(&RpcRequest::new0(
-                "listunspent"
-            ))
-        }
-    }
+    /// The timeout of requests of `method`, such as `"gettxoutsetinfo"`. The methods in `SLOW_METHODS` have a
+    /// timeout of `SLOW_METHOD_TIMEOUT` by default. A batch gets the longest timeout of its requests.
+    /// Only the blocking `Client` has method timeouts.
+    pub fn method_timeout(&mut self, method: &str, timeout: Duration) -> &mut Self {
+        self.http.method_timeouts.insert(method.to_string(), Some(timeout));
 
-    fn lock_unspent(&self, unlock: bool, txns: Vec<LockedUnspent>) -> Result<bool> {
-        self.send(&RpcRequest::new2(
-            "lockunspent",
-            unlock,
-            txns
-        ))
+        self
     }
 
-    fn resend_wallet_transactions(&self) -> Result<ResentWalletTransactions> {
-        self.send(&RpcRequest::new0(
-            "resendwallettransactions"
-        ))
-    }
+    /// Whether connections are kept open to be reused for later requests. Defaults to true.
+    pub fn keep_alive(&mut self, keep_alive: bool) -> &mut Self {
+        self.http.keep_alive = keep_alive;
 
-    fn send_many(&self, amounts: SendManyAmounts, minconf: Option<u32>, comment: Option<&str>, subtractfeefromaddresses: Option<Vec<Address>>) -> Result<TransactionId> {
-        match (minconf, comment, subtractfeefromaddresses) {
-            (Some(minconf), None, None) => self.send(&RpcRequest::new3(
-                "sendmany",
-                "",
-                amounts,
-                minconf
-            )),
-            (Some(minconf), Some(comment), None) => self.send(&RpcRequest::new4(
-                "sendmany",
-                "",
-                amounts,
-                minconf,
-                comment
-            )),
-            (Some(minconf), None, Some(fees)) => self.send(&RpcRequest::new5(
-                "sendmany",
-                "",
-                amounts,
-                minconf,
-                "",
-                fees
-            )),
-            (Some(minconf), Some(comment), Some(fees)) => self.send(&RpcRequest::new5(
-                "sendmany",
-                "",
-                amounts,
-                minconf,
-                comment,
-                fees
-            )),
-            (None, Some(comment), None) => self.send(&RpcRequest::new4(
-                "sendmany",
-                "",
-                amounts,
-                1,
-                comment
-            )),
-            (None, None, Some(fees)) => self.send(&RpcRequest::new5(
-                "sendmany",
-                "",
-                amounts,
-                1,
-                "",
-                fees
-            )),
-            (None, Some(comment), Some(fees)) => self.send(&RpcRequest::new5(
-                "sendmany",
-                "",
-                amounts,
-                1,
-                comment,
-                fees
-            )),
-            _ => self.send(&RpcRequest::new2(
-                "sendmany",
-                "",
-                amounts
-            )),
-        }
+        self
     }
 
-    fn send_to_address(&self, address: Address, amount: f64, comment: Option<&str>, comment_to: Option<&str>, subtractfee: Option<bool>) -> Result<TransactionId> {
-        match address.addr_type {
-            AddrType::Transparent => {
-                match (comment, comment_to, subtractfee) {
-                    (Some(comment), None, None) => self.send(&RpcRequest::new3(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        comment
-                    )),
-                    (Some(comment), Some(comment_to), None) => self.send(&RpcRequest::new4(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        comment,
-                        comment_to
-                    )),
-                    (Some(comment), None, Some(subtractfee)) => self.send(&RpcRequest::new5(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        comment,
-                        "",
-                        subtractfee
-                    )),
-                    (Some(comment), Some(comment_to), Some(subtractfee)) => self.send(&RpcRequest::new5(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        comment,
-                        comment_to,
-                        subtractfee
-                    )),
-                    (None, Some(comment_to), None) => self.send(&RpcRequest::new4(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        "",
-                        comment_to
-                    )),
-                    (None, None, Some(subtractfee)) => self.send(&RpcRequest::new5(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        "",
-                        "",
-                        subtractfee
-                    )),
-                    (None, Some(comment_to), Some(subtractfee)) => self.send(&RpcRequest::new5(
-                        "sendtoaddress",
-                        address,
-                        amount,
-                        "",
-                        comment_to,
-                        subtractfee
-                    )),
-                    _ => self.send(&RpcRequest::new2(
-                        "sendtoaddress",
-                        address,
-                        amount
-                    ))
-                }
-            },
-            _ => Err(ApiError::Other(String::from("address is not transparent")))
-        }
-    }
+    /// The most idle connections that are kept open. Unlimited by default.
+    pub fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.http.max_idle_per_host = Some(max);
 
-    fn set_pubkey(&self, _pubkey: &str) -> Result<SetPubkey> {
-//        self.send(&RpcRequest::new1(
-//            "setpubkey",
-//            pubkey
-//        ))
-        unimplemented!()
+        self
     }
 
-    fn set_txfee(&self, fee: f64) -> Result<bool> {
-        self.send(&RpcRequest::new1(
-            "settxfee",
-            fee
-        ))
-    }
+    /// Retries failed requests, see `RetryPolicy`. The `AsyncClient` does not retry.
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = Some(retry_policy);
 
-    fn sign_message(&self, address: Address, message: &str) -> Result<String> {
-        self.send(&RpcRequest::new2(
-            "signmessage",
-            address,
-            message
-        ))
+        self
     }
 
-    fn z_exportkey(&self, a: &Address) -> Result<PrivateKey> {
-        match a.addr_type {
-            AddrType::Shielded => self.send(&RpcRequest::new1(
-                "z_exportkey",
-                &a.addr
-            )),
-            AddrType::Transparent => Err(ApiError::Other("transparent key not supported in z_exportkey".to_string()))
-        }
-    }
+    /// Resolves the url and credentials of the connection.
+    fn connection(&self) -> Result<(String, Credentials)> {
+        let datadir = match (&self.chain, &self.datadir) {
+            (_, Some(datadir)) => Some(datadir.clone()),
+            (Some(chain), None) => Some(default_datadir(chain)?),
+            (None, None) => None,
+        };
+        let conf = match self.chain {
+            Some(ref chain) => Some(KomodoConf::read_for(chain, datadir.as_deref())?),
+            None => None,
+        };
 
-    fn z_exportviewingkey(&self, _a: &Address) -> Result<ViewingKey> {
-//        match a.addr_type {
-//            AddrType::Shielded => self.send(&RpcRequest::new1(
-//                "z_exportviewingkey",
-//                &a.addr
-//            )),
-//            AddrType::Transparent => Err(ApiError::Other("transparent key not supported in z_exportviewingkey".to_string()))
-//        }
-        unimplemented!()
-    }
+        let rpc_user = self.rpc_user.clone()
+            .or_else(|| conf.as_ref().and_then(|c| c.rpc_user()).map(String::from));
+        let rpc_password = self.rpc_password.clone()
+            .or_else(|| conf.as_ref().and_then(|c| c.rpc_password()).map(String::from));
 
-    fn z_exportwallet(&self, s: &str) -> Result<String> {
-        self.send(&RpcRequest::new1(
-            "z_exportwallet",
-            s
-        ))
-    }
+        let default_cookie_file = match (&conf, &datadir) {
+            (Some(conf), Some(datadir)) => Some(conf.cookie_file(datadir)),
+            (None, Some(datadir)) => Some(datadir.join(".cookie")),
+            (_, None) => None,
+        };
 
-    fn z_getbalance(&self, addr: &Address, minconf: Option<u32>) -> Result<f64> {
-        match minconf {
-            Some(conf) => self.send(&RpcRequest::new2(
-                "z_getbalance",
-                &addr.addr,
-                conf
-            )),
-            None => self.send(&RpcRequest::new1(
-                "z_getbalance",
-                &addr.addr
-            ))
-        }
-    }
+        let credentials = match (&self.cookie_file, rpc_user, rpc_password, default_cookie_file) {
+            (Some(path), _, _, _) => Credentials::from_cookie_file(path)?,
+            (None, Some(rpc_user), Some(rpc_password), _) => Credentials::new(&rpc_user, &rpc_password)?,
+            (None, _, _, Some(path)) => {
+                Credentials::from_cookie_file(&path).map_err(|e| ApiError::Config(format!(
+                    "no rpcuser and rpcpassword in config file, and no cookie file at {}: {}", path.display(), e
+                )))?
+            },
+            (None, None, _, None) => return Err(ApiError::Config(String::from("No rpcuser in config"))),
+            (None, Some(_), None, None) => return Err(ApiError::Config(String::from("no rpcpassword in config file"))),
+        };
+
+        let conf_port = match conf {
+            Some(ref conf) => match conf.rpc_port()? {
+                Some(port) => Some(port),
+                None => match self.chain {
+                    Some(Chain::KMD) => Some(7771), // KMD doesn't put rpcport in conf file at install
+                    _ => None,
+                }
+            },
+            None => None,
+        };
 
-    fn z_getnewaddress(&self) -> Result<Address> {
-        self.send(&RpcRequest::new0(
-            "z_getnewaddress"
-        ))
-    }
+        let rpc_port = self.port
+            .or(conf_port)
+            .ok_or_else(|| ApiError::Config(String::from("no rpcport in config file")))?;
 
-    fn z_getoperationresult(&self, v: Vec<&str>) -> Result<Operations> {
-        self.send(&RpcRequest::new1(
-            "z_getoperationresult",
-            v
-        ))
-    }
+        let host = self.host.clone()
+            .or_else(|| conf.as_ref().and_then(|c| c.rpc_connect()).map(String::from))
+            .unwrap_or_else(|| String::from("127.0.0.1"));
 
-    fn z_getoperationstatus(&self, v: Vec<&str>) -> Result<Operations> {
-        self.send(&RpcRequest::new1(
-            "z_getoperationstatus",
-            v
-        ))
+        Ok((format!("http://{}:{}", host, rpc_port), credentials))
     }
 
-    fn z_gettotalbalance(&self) -> Result<TotalBalance> {
-        self.send(&RpcRequest::new0(
-            "z_gettotalbalance"
-        ))
-    }
+    pub fn build(&self) -> Result<Client> {
+        let (url, credentials) = self.connection()?;
 
-    fn z_importkey(&self) -> Result<()> {
-        unimplemented!()
+        Ok(Client {
+            client: RpcClient::with_settings(&url, Arc::new(credentials), self.http.clone())?,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+        })
     }
 
-    fn z_importviewingkey(&self) -> Result<()> {
-        unimplemented!()
-    }
+    /// Builds an `AsyncClient` with the same connection parameters as `build`.
+    #[cfg(feature = "async")]
+    pub fn build_async(&self) -> Result<AsyncClient> {
+        let (url, credentials) = self.connection()?;
 
-    fn z_importwallet(&self) -> Result<()> {
-        unimplemented!()
-    }
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, credentials.header());
 
-    fn z_listaddresses(&self, include_watch_only: Option<bool>) -> Result<Vec<Address>> {
-        match include_watch_only {
-            Some(b) => self.send(&RpcRequest::new1(
-                "z_listaddresses",
-                b
-            )),
-            None => self.send(&RpcRequest::new0(
-                "z_listaddresses"
-            ))
+        let mut builder = AsyncHttpClient::builder().default_headers(headers);
+        if let Some(timeout) = self.http.timeout {
+            builder = builder.timeout(timeout);
         }
-    }
-
-    fn z_listoperationids(&self) -> Result<Vec<String>> {
-        self.send(&RpcRequest::new0(
-            "z_listoperationids"
-        ))
-    }
-
-    fn z_listreceivedbyaddress(&self, a: &Address, minconf: Option<u32>) -> Result<ReceivedByAddress> {
-        match a.addr_type {
-            AddrType::Shielded => self.send(&RpcRequest::new2(
-                "z_listreceivedbyaddress",
-                &a.addr,
-                match minconf {
-                    Some(conf) => conf,
-                    None => 1
-                }
-            )),
-            _ => Err(ApiError::Other(format!("Not a Shielded address: {}", a.addr))) // ApiError::Parameter
+        if let Some(timeout) = self.http.connect_timeout {
+            builder = builder.connect_timeout(timeout);
         }
-    }
-
-    fn z_mergetoaddress(
-        &self,
-        from_addresses: &FromAddresses,
-        to_address: &Address,
-        fee: Option<f64>,
-        transparent_limit: Option<u32>,
-        shielded_limit: Option<u32>,
-        maximum_utxo_size: Option<u64>,
-        memo: Option<String>
-    ) -> Result<MergeResult> {
-        match (maximum_utxo_size, memo) {
-            (Some(size), None) => self.send(&RpcRequest::new6(
-                "z_mergetoaddress",
-                &from_addresses.0,
-                &to_address.addr,
-                match fee {
-                    Some(fee) => fee,
-                    None => 0.0001,
-                },
-                match transparent_limit {
-                    Some(limit) => limit,
-                    None => 50
-                },
-                match shielded_limit {
-                    Some(limit) => limit,
-                    None => 90
-                },
-                size
-            )),
-            (Some(size), Some(ref memo)) => self.send(&RpcRequest::new7(
-                "z_mergetoaddress",
-                &from_addresses.0,
-                &to_address.addr,
-                match fee {
-                    Some(fee) => fee,
-                    None => 0.0001,
-                },
-                match transparent_limit {
-                    Some(limit) => limit,
-                    None => 50
-                },
-                match shielded_limit {
-                    Some(limit) => limit,
-                    None => 90
-                },
-                size,
-                memo
-            )),
-            (None, Some(_)) =>
-                Err(ApiError::Other("cannot specify memo without specifying maximum_utxo_size".to_string())),
-            _ => self.send(&RpcRequest::new5(
-                "z_mergetoaddress",
-                &from_addresses.0,
-                &to_address.addr,
-                match fee {
-                    Some(fee) => fee,
-                    None => 0.0001,
-                },
-                match transparent_limit {
-                    Some(limit) => limit,
-                    None => 50
-                },
-                match shielded_limit {
-                    Some(limit) => limit,
-                    None => 90
-                }
-            ))
+        if let Some(max_idle) = self.http.max_idle() {
+            builder = builder.max_idle_per_host(max_idle);
         }
-    }
 
-    fn z_sendmany(
-        &self,
-        from_address: &Address,
-        amounts: &Amounts,
-        minconf: Option<u32>,
-        fee: Option<f64>,
-    ) -> Result<String> {
-        self.send(&RpcRequest::new4(
-            "z_sendmany",
-            &from_address.addr,
-            &amounts.0,
-            match minconf {
-                Some(conf) => conf,
-                None => 1,
-            },
-            match fee {
-                Some(fee) => fee,
-                None => 0.0001
-            }
-        ))
-    }
+        let client = builder
+            .build()
+            .map_err(|e| ApiError::Other(format!("unable to create http client: {}", e)))?;
 
-    fn z_shieldcoinbase(&self, from_address: &Address, to_address: &Address, fee: Option<f64>, limit: Option<u32>) -> Result<ShieldResult> {
-        self.send(&RpcRequest::new4(
-            "z_shieldcoinbase",
-            &from_address,
-            &to_address,
-            match fee {
-                Some(fee) => fee,
-                None => 0.0001,
-            },
-            match limit {
-                Some(limit) => limit,
-                None => 50
-            }
-        ))
+        Ok(AsyncClient::new(AsyncRpcClient::new(client, &url)))
     }
-}
\ No newline at end of file
+}
+
+impl<T: Transport> KomodoRpcApi for Client<T> {
+    rpc_methods!(Result, Err);
+}
@@ -1,41 +1,59 @@
 //extern crate base64;
+extern crate bech32;
 extern crate bitcoin;
-//extern crate hex as std_hex;
+extern crate blake2b_simd;
+extern crate crypto;
+extern crate hex as std_hex;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
 extern crate serde_json;
 extern crate core;
 extern crate dirs;
 extern crate reqwest;
 extern crate os_info;
+extern crate secp256k1;
+#[cfg(feature = "async")]
+extern crate futures;
 
 mod types;
+#[macro_use]
+mod rpc_methods;
 mod komodocore;
 mod komodo_rpc_api;
+#[cfg(feature = "async")]
+mod asynccore;
+#[cfg(feature = "async")]
+mod async_rpc_api;
 mod chains;
+mod conf;
 mod error;
+mod hash;
+mod interest;
 mod rpcconn;
+pub mod tx;
 
-pub type TransactionId = bitcoin::util::hash::Sha256dHash;
-
-//impl TransactionId {
-//    pub fn from_str(s: &str) -> Result<TransactionId, ApiError> {
-//        let hash = Sha256dHash::from_hex(s)?;
-//    }
-//}
-
-pub type BlockHash = bitcoin::util::hash::Sha256dHash;
+/// The name of `Txid` in earlier versions.
+pub type TransactionId = Txid;
 
 pub use komodo_rpc_api::KomodoRpcApi;
-pub use komodocore::Client;
+pub use komodocore::{Client, ClientBuilder};
+#[cfg(feature = "async")]
+pub use async_rpc_api::AsyncKomodoRpcApi;
+#[cfg(feature = "async")]
+pub use asynccore::AsyncClient;
 
 pub use error::ApiError;
+pub use hash::{BlockHash, ChannelSecret, Txid};
+pub use interest::{komodo_interest, ClaimedRewards, KIP0001_ACTIVATION_HEIGHT, KOMODO_ENDOFERA};
 
 pub use types::*;
 pub use rpcconn::*;
+pub use tx::{CoinSelector, FeePolicy, KomodoTransaction, SelectionStrategy, TransactionBuilder};
 
 pub use bitcoin::network::constants::Network;
 pub use bitcoin::util::privkey::Privkey;
 pub use bitcoin::Script;
 pub use chains::Chain;
+pub use conf::KomodoConf;
@@ -0,0 +1,1578 @@
+/// The methods of `KomodoRpcApi` and `AsyncKomodoRpcApi`, which only differ in what they return:
+/// a `Result` for `Client` and an `ApiFuture` for `AsyncClient`. Each method builds its request and
+/// hands it to `send`, `send_cc` or `send_cc_transaction` of the client, and fails with `$fail` if
+/// its arguments are invalid.
+///
+/// `rpc_methods!(trait Name, $result)` declares the trait and `rpc_methods!($result, $fail)` implements it,
+/// both from the methods below, so a method is only written once.
+macro_rules! rpc_methods {
+    (@declare [$(#[$attr:meta])* $name:ident]
+     $(fn $method:ident(&$self_:ident $(, $arg:ident: $ty:ty)* $(,)*) -> $ret:ty $body:block)*) => {
+        $(#[$attr])*
+        pub trait $name {
+            $(fn $method(&$self_ $(, $arg: $ty)*) -> $ret;)*
+        }
+    };
+    (@implement []
+     $(fn $method:ident(&$self_:ident $(, $arg:ident: $ty:ty)* $(,)*) -> $ret:ty $body:block)*) => {
+        $(fn $method(&$self_ $(, $arg: $ty)*) -> $ret $body)*
+    };
+    ($(#[$attr:meta])* trait $name:ident, $result:ident) => {
+        rpc_methods!(@methods [@declare [$(#[$attr])* $name]] $result, Err);
+    };
+    ($result:ident, $fail:path) => {
+        rpc_methods!(@methods [@implement []] $result, $fail);
+    };
+    (@methods [$($mode:tt)*] $result:ident, $fail:path) => { rpc_methods!($($mode)*
+        fn get_address_balance(&self, addresses: &arguments::AddressList) -> $result<AddressBalance> {
+            self.send(&RpcRequest::new1(
+                "getaddressbalance",
+                addresses
+            ))
+        }
+
+        fn get_address_deltas(&self, addresses: &AddressList) -> $result<AddressDeltas> {
+            self.send(&RpcRequest::new1(
+                "getaddressdeltas",
+                addresses
+            ))
+        }
+
+        fn get_address_mempool(&self, addresses: &AddressList) -> $result<AddressMempool> {
+            self.send(&RpcRequest::new1(
+                "getaddressmempool",
+                addresses
+            ))
+        }
+
+        fn get_address_tx_ids(&self, addresses: &AddressList) -> $result<AddressTxIDs> {
+            self.send(&RpcRequest::new1(
+                "getaddresstxids",
+                addresses
+            ))
+        }
+
+        fn get_address_utxos(&self, addresses: &AddressList) -> $result<AddressUtxos> {
+            self.send(&RpcRequest::new1(
+                "getaddressutxos",
+                addresses
+            ))
+        }
+
+        // getting a snapshot takes an optional parameter. need to create 2 API calls:
+        fn get_snapshot_max(&self, n: u32) -> $result<Snapshot> {
+            // parameter must be string:
+            let n = n.to_string();
+            self.send(&RpcRequest::new1(
+                "getsnapshot",
+                n
+            ))
+        }
+
+        fn get_snapshot(&self) -> $result<Snapshot> {
+            self.send(&RpcRequest::new0(
+                "getsnapshot"
+            ))
+        }
+
+        fn coinsupply(&self, n: u32) -> $result<Coinsupply> {
+            let n = n.to_string();
+            self.send(&RpcRequest::new1(
+                "coinsupply",
+                n
+            ))
+        }
+
+        fn get_best_block_hash(&self) -> $result<BlockHash> {
+            self.send(&RpcRequest::new0(
+                "getbestblockhash",
+            ))
+        }
+
+        fn get_block(&self, hashorheight: String) -> $result<Block> {
+            self.send(&RpcRequest::new1(
+                "getblock",
+                hashorheight
+            ))
+        }
+
+        fn get_blockchain_info(&self) -> $result<BlockchainInfo> {
+            self.send(&RpcRequest::new0(
+                "getblockchaininfo"
+            ))
+        }
+
+        fn get_block_count(&self) -> $result<Blockcount> {
+            self.send(&RpcRequest::new0(
+                "getblockcount"
+            ))
+        }
+
+        fn get_block_hash(&self, n: u32) -> $result<BlockHash> {
+            self.send(&RpcRequest::new1(
+                "getblockhash",
+                n
+            ))
+        }
+
+        fn get_block_header(&self, hash: &BlockHash) -> $result<BlockHeader> {
+            self.send(&RpcRequest::new1(
+                "getblockheader",
+                hash
+            ))
+        }
+
+        fn get_chaintips(&self) -> $result<ChainTips> {
+            self.send(&RpcRequest::new0(
+                "getchaintips"
+            ))
+        }
+
+        fn get_difficulty(&self) -> $result<f64> {
+            self.send(&RpcRequest::new0(
+                "getdifficulty",
+            ))
+        }
+
+        fn get_mempool_info(&self) -> $result<MempoolInfo> {
+            self.send(&RpcRequest::new0(
+                "getmempoolinfo"
+            ))
+        }
+
+        fn get_raw_mempool(&self) -> $result<RawMempool> {
+            self.send(&RpcRequest::new0(
+                "getrawmempool"
+            ))
+        }
+
+        fn get_raw_mempool_verbose(&self) -> $result<RawMempoolVerbose> {
+            self.send(&RpcRequest::new1(
+                "getrawmempool",
+                true
+            ))
+        }
+
+        // None if the output is spent or does not exist
+        fn get_tx_out(&self, txid: &Txid, index: u8) -> $result<Option<TxOut>> {
+            self.send(&RpcRequest::new2(
+                "gettxout",
+                txid,
+                index
+            ))
+        }
+
+        fn get_tx_out_set_info(&self) -> $result<TxOutSetInfo> {
+            self.send(&RpcRequest::new0(
+                "gettxoutsetinfo"
+            ))
+        }
+
+        fn minerids(&self, height: String) -> $result<MinerIDs> { // why is height a string?
+            self.send(&RpcRequest::new1(
+                "minerids",
+                height
+            ))
+        }
+
+        fn notaries(&self, height: String) -> $result<Notaries> { // why is height a string?
+            self.send(&RpcRequest::new1(
+                "notaries",
+                height
+            ))
+        }
+
+        fn get_info(&self) -> $result<Info> {
+            self.send(&RpcRequest::new0(
+                "getinfo"
+            ))
+        }
+
+        // mining
+
+        fn get_block_subsidy(&self, height: Option<u64>) -> $result<BlockSubsidy> {
+            match height {
+                Some(n) => {
+                    self.send(&RpcRequest::new1(
+                        "getblocksubsidy",
+                        n
+                    ))
+                },
+                None => {
+                    self.send(&RpcRequest::new0(
+                        "getblocksubsidy",
+                    ))
+                }
+            }
+        }
+
+        fn get_block_template(&self, jsonrequestobject: Option<&RequestObject>) -> $result<BlockTemplate> {
+            match jsonrequestobject {
+                Some(request) => {
+                    self.send(&RpcRequest::new1(
+                        "getblocktemplate",
+                        request
+                    ))
+                },
+                None => {
+                    self.send(&RpcRequest::new0(
+                        "getblocktemplate",
+                    ))
+                }
+            }    }
+
+        fn get_local_sol_ps(&self) -> $result<f64> {
+            self.send(&RpcRequest::new0(
+                "getlocalsolps"
+            ))
+        }
+
+        fn get_mining_info(&self) -> $result<MiningInfo> {
+            self.send(&RpcRequest::new0(
+                "getmininginfo"
+            ))
+        }
+
+        fn get_network_hash_ps(&self) -> $result<u64> {
+            self.send(&RpcRequest::new0(
+                "getnetworkhashps"
+            ))
+        }
+
+        fn get_network_sol_ps(&self) -> $result<u64> {
+            self.send(&RpcRequest::new0(
+                "getnetworksolps"
+            ))
+        }
+
+        // todo untested
+        fn prioritise_transaction(&self, txid: Txid, prio_delta: f64, fee_delta: u32) -> $result<bool> {
+            self.send(&RpcRequest::new3(
+                "prioritisetransaction",
+                txid,
+                prio_delta,
+                fee_delta
+            ))
+        }
+
+        // todo untested
+        // None if the block was accepted
+        fn submit_block(&self, hexdata: String, jsonparametersobject: Option<ParametersObject>) -> $result<Option<SubmitBlockResult>> {
+            match jsonparametersobject {
+                Some(object) => {
+                    self.send(&RpcRequest::new2(
+                        "submitblock",
+                        hexdata,
+                        object
+                    ))
+                },
+                None => {
+                    self.send(&RpcRequest::new1(
+                        "submitblock",
+                        hexdata
+                    ))
+                }
+            }
+        }
+
+        // network
+
+    //    fn addnode(&self, node: String, action: AddNodeCommand) -> $result<()> {
+    //        self.send(&RpcRequest::new2(
+    //            "addnode",
+    //            node,
+    //            action
+    //        ))
+    //    }
+
+    //    fn clear_banned(&self) -> $result<()> {
+    //        self.send(&RpcRequest::new0(
+    //            "clearbanned",
+    //        ))
+    //    }
+
+    //    fn disconnect_node(&self, node: String) -> $result<()> {
+    //        self.send(&RpcRequest::new1(
+    //            "addnode",
+    //            node
+    //        ))
+    //    }
+
+        fn get_added_node_info(&self, dns: bool, node: Option<&str>) -> $result<Vec<AddedNodeInfo>> {
+            match node {
+                Some(address) => {
+                    self.send(&RpcRequest::new2(
+                        "getaddednodeinfo",
+                        dns,
+                        address
+                    ))
+                },
+                None => {
+                    self.send(&RpcRequest::new1(
+                        "getaddednodeinfo",
+                        dns
+                    ))
+                }
+            }
+        }
+
+        fn get_connection_count(&self) -> $result<u32> {
+            self.send(&RpcRequest::new0(
+                "getconnectioncount"
+            ))
+        }
+
+        fn get_deprecation_info(&self) -> $result<DeprecationInfo> {
+            self.send(&RpcRequest::new0(
+                "getdeprecationinfo"
+            ))
+        }
+
+        fn get_net_totals(&self) -> $result<NetTotals> {
+            self.send(&RpcRequest::new0(
+                "getnettotals"
+            ))
+        }
+
+        fn get_network_info(&self) -> $result<NetworkInfo> {
+            self.send(&RpcRequest::new0(
+                "getnetworkinfo"
+            ))
+        }
+
+        fn get_peer_info(&self) -> $result<Vec<Peer>> {
+            self.send(&RpcRequest::new0(
+                "getpeerinfo"
+            ))
+        }
+
+        fn list_banned(&self) -> $result<Vec<Option<BannedNode>>> {
+            self.send(&RpcRequest::new0(
+                "listbanned"
+            ))
+        }
+
+    //    fn ping(&self) -> $result<()> {
+    //        self.send(&RpcRequest::new0(
+    //            "ping"
+    //        ))
+    //    }
+
+        // "ip(/netmask)" "add|remove" (bantime) (absolute)
+        fn set_ban(
+            &self,
+            ip: String,
+            command: String,
+            bantime: Option<u32>,
+            absolute: Option<bool>
+        ) -> $result<()> {
+            match bantime {
+                Some(time) => {
+                    match absolute {
+                        Some(true) => {
+                            self.send(&RpcRequest::new4(
+                                "setban",
+                                ip,
+                                command,
+                                time,
+                                true
+                            ))
+                        },
+                        Some(false) => {
+                            self.send(&RpcRequest::new4(
+                                "setban",
+                                ip,
+                                command,
+                                time,
+                                false
+                            ))
+                        },
+                        None => unreachable!()
+                    }
+                },
+                None => {
+                    self.send(&RpcRequest::new2(
+                        "setban",
+                        ip,
+                        command,
+                    ))
+                }
+            }
+        }
+
+        fn create_raw_transaction(&self, inputs: CreateRawTransactionInputs, outputs: CreateRawTransactionOutputs) -> $result<SerializedRawTransaction> {
+            self.send(&RpcRequest::new2(
+                "createrawtransaction",
+                inputs,
+                outputs
+            ))
+        }
+
+        fn decode_raw_transaction(&self, hexstring: &str) -> $result<RawTransaction> {
+            self.send(&RpcRequest::new1(
+                "decoderawtransaction",
+                hexstring
+            ))
+        }
+
+        fn decode_script(&self, hexstring: &str) -> $result<DecodedScript> {
+            self.send(&RpcRequest::new1(
+                "decodescript",
+                hexstring
+            ))
+        }
+
+        // todo returns serialized transaction
+        fn get_raw_transaction(&self, txid: arguments::Txid) -> $result<SerializedRawTransaction> {
+            self.send(&RpcRequest::new1(
+                "getrawtransaction",
+                txid
+            ))
+        }
+
+        fn get_raw_transaction_verbose(&self, txid: arguments::Txid) -> $result<RawTransaction> {
+            self.send(&RpcRequest::new2(
+                "getrawtransaction",
+                txid,
+                1
+            ))
+        }
+
+        fn sign_raw_transaction_with_wallet(&self, hexstring: SerializedRawTransaction) -> $result<SignedRawTransaction> {
+            self.send(&RpcRequest::new1(
+                "signrawtransaction",
+                hexstring
+            ))
+        }
+
+        fn sign_raw_transaction_with_key(
+            &self,
+            hexstring: &SerializedRawTransaction,
+            txoutput_detail: Option<&P2SHInputSet>,
+            private_keys: Option<Vec<&PrivateKey>>,
+            signature_hash_type: Option<SigHashType>
+        ) -> $result<SignedRawTransaction> {
+            self.send(&RpcRequest::new4(
+                "signrawtransaction",
+                hexstring,
+                txoutput_detail,
+                private_keys,
+                signature_hash_type
+            ))
+        }
+
+        fn send_raw_transaction(&self, signed_tx: &SignedRawTransaction) -> $result<Txid> {
+            self.send(&RpcRequest::new1(
+                "sendrawtransaction",
+                &signed_tx.hex
+            ))
+        }
+
+        fn create_multisig(&self, nrequired: u8, keys: Vec<&str>) -> $result<Multisig> {
+            self.send(&RpcRequest::new2(
+                "createmultisig",
+                nrequired,
+                keys
+            ))
+        }
+
+        fn backup_wallet(&self, file_name: &str) -> $result<()> {
+            self.send(&RpcRequest::new1(
+                "backupwallet",
+                file_name
+            ))
+        }
+
+        fn dump_privkey(&self, address: &str) -> $result<String> {
+            self.send(&RpcRequest::new1(
+                "dumpprivkey",
+                address
+            ))
+        }
+
+        fn dump_wallet(&self, filename: &str) -> $result<String> {
+            self.send(&RpcRequest::new1(
+                "dumpwallet",
+                filename
+            ))
+        }
+
+        fn get_balance(&self, minconf: Option<u32>, include_watchonly: Option<bool>) -> $result<Amount> {
+            let second;
+            let third;
+
+            match (minconf, include_watchonly) {
+                (Some(minconf), Some(wo)) => {
+                    second = minconf;
+                    third = wo;
+                },
+                (Some(minconf), _) => {
+                    second = minconf;
+                    third = false;
+                },
+                (_, Some(wo)) => {
+                    second = 1;
+                    third = wo;
+                },
+                _ => {
+                    second = 1;
+                    third = false;
+                }
+            }
+            self.send(&RpcRequest::new3(
+                "getbalance",
+                "*",
+                second,
+                third
+            ))
+        }
+
+        fn get_new_address(&self) -> $result<String> {
+            self.send(&RpcRequest::new0(
+                "getnewaddress",
+            ))
+        }
+
+        fn get_raw_change_address(&self) -> $result<Address> {
+            self.send(&RpcRequest::new0(
+                "getrawchangeaddress",
+            ))
+        }
+
+        fn get_received_by_address(&self, address: &Address) -> $result<Amount> {
+            self.send(&RpcRequest::new1(
+                "getreceivedbyaddress",
+                &address.addr
+            ))
+        }
+
+        fn get_transaction(
+            &self,
+            tx: &Txid,
+        ) -> $result<Transaction> {
+            self.send(&RpcRequest::new1(
+                "gettransaction",
+                tx,
+            ))
+        }
+
+        fn get_unconfirmed_balance(&self) -> $result<Amount> {
+            self.send(&RpcRequest::new0(
+                "getunconfirmedbalance"
+            ))
+        }
+
+        fn get_wallet_info(&self) -> $result<WalletInfo> {
+            self.send(&RpcRequest::new0(
+                "getwalletinfo"
+            ))
+        }
+
+        // fn import_address(&self, address: &Address, label: Option<String>, rescan: bool) -> $result<()> {
+        //    match label {
+        //        Some(label) => self.send(&RpcRequest::new3(
+        //            "importaddress",
+        //            &label,
+        //            rescan
+        //        )),
+        //        None => self.send(&RpcRequest::new3(
+        //            "importaddress",
+        //            "",
+        //            rescan
+        //        ))
+        //    }
+        //     unimplemented!()
+        // }
+
+        fn import_privkey(&self, key: &str, label: Option<&str>, rescan: bool) -> $result<Address> {
+            match label {
+                Some(label) => self.send(&RpcRequest::new3(
+                    "importprivkey",
+                    key,
+                    &label,
+                    rescan
+                )),
+                None => self.send(&RpcRequest::new3(
+                    "importprivkey",
+                    key,
+                    "",
+                    rescan
+                ))
+            }
+        }
+
+
+        fn import_wallet(&self, _path: &str) -> $result<()> {
+            $fail(ApiError::Other(String::from("not implemented")))
+        }
+
+        fn list_address_groupings(&self) -> $result<AddressGroupings> {
+            self.send(&RpcRequest::new0(
+                "listaddressgroupings"
+            ))
+        }
+
+        fn list_lock_unspent(&self) -> $result<Vec<LockedUnspent>> {
+            self.send(&RpcRequest::new0(
+                "listlockunspent"
+            ))
+        }
+
+        fn list_received_by_address(&self, minconf: Option<u32>, include_empty: Option<bool>, include_watch_only: Option<bool>) -> $result<Vec<ReceivedByAddress>> {
+            match (minconf, include_empty, include_watch_only) {
+                (Some(min), None, None) => self.send(&RpcRequest::new1(
+                    "listreceivedbyaddress",
+                    min
+                )),
+                (None, Some(b), None) => self.send(&RpcRequest::new2(
+                    "listreceivedbyaddress",
+                    1,
+                    b
+                )),
+                (Some(min), Some(b), None) => self.send(&RpcRequest::new2(
+                    "listreceivedbyaddress",
+                    min,
+                    b
+                )),
+                (None, None, Some(wo)) => self.send(&RpcRequest::new3(
+                    "listreceivedbyaddress",
+                    1,
+                    false,
+                    wo
+                )),
+                (None, Some(b), Some(wo)) => self.send(&RpcRequest::new3(
+                    "listreceivedbyaddress",
+                    1,
+                    b,
+                    wo
+                )),
+                (Some(min), None, Some(wo)) => self.send(&RpcRequest::new3(
+                    "listreceivedbyaddress",
+                    min,
+                    false,
+                    wo
+                )),
+                (Some(min), Some(b), Some(wo)) => self.send(&RpcRequest::new3(
+                    "listreceivedbyaddress",
+                    min,
+                    b,
+                    wo
+                )),
+                _ => self.send(&RpcRequest::new0(
+                    "listreceivedbyaddress",
+                ))
+            }
+        }
+
+        fn list_since_block(&self, blockhash: Option<&BlockHash>, confs: Option<u64>, include_watch_only: Option<bool>) -> $result<TxListSinceBlock> {
+            match (blockhash, confs, include_watch_only) {
+                (Some(hash), None, None) => self.send(&RpcRequest::new1(
+                    "listsinceblock",
+                    hash
+                )),
+                (Some(hash), Some(confs), None) => self.send(&RpcRequest::new2(
+                    "listsinceblock",
+                    hash,
+                    confs
+                )),
+                (Some(hash), None, Some(watchonly)) => self.send(&RpcRequest::new3(
+                    "listsinceblock",
+                    hash,
+                    1,
+                    watchonly
+                )),
+                (Some(hash), Some(confs), Some(watchonly)) => self.send(&RpcRequest::new3(
+                    "listsinceblock",
+                    hash,
+                    confs,
+                    watchonly
+                )),
+                (None, Some(confs), None) => self.send(&RpcRequest::new2(
+                    "listsinceblock",
+                    "",
+                    confs
+                )),
+                (None, Some(confs), Some(watchonly)) => self.send(&RpcRequest::new3(
+                    "listsinceblock",
+                    "",
+                    confs,
+                    watchonly
+                )),
+                (None, None, Some(watchonly)) => self.send(&RpcRequest::new3(
+                    "listsinceblock",
+                    "",
+                    1,
+                    watchonly
+                )),
+                _ => self.send(&RpcRequest::new0(
+                    "listsinceblock"
+                )),
+            }
+        }
+
+        fn list_transactions(&self, count: Option<u32>, from: Option<u32>, include_watch_only: Option<bool>) -> $result<ListTransactions> {
+            match (count, from, include_watch_only) {
+                (Some(count), None, None) => self.send(&RpcRequest::new2(
+                    "listtransactions",
+                    "",
+                    count
+                )),
+                (Some(count), Some(from), None) => self.send(&RpcRequest::new3(
+                    "listtransactions",
+                    "",
+                    count,
+                    from
+                )),
+                (Some(count), None, Some(watchonly)) => self.send(&RpcRequest::new4(
+                    "listtransactions",
+                    "",
+                    count,
+                    0,
+                    watchonly
+                )),
+                (Some(count), Some(from), Some(watchonly)) => self.send(&RpcRequest::new4(
+                    "listtransactions",
+                    "",
+                    count,
+                    from,
+                    watchonly
+                )),
+                (None, Some(from), None) => self.send(&RpcRequest::new3(
+                    "listtransactions",
+                    "",
+                    10,
+                    from
+                )),
+                (None, None, Some(watchonly)) => self.send(&RpcRequest::new4(
+                    "listtransactions",
+                    "",
+                    10,
+                    0,
+                    watchonly
+                )),
+                (None, Some(from), Some(watchonly)) => self.send(&RpcRequest::new4(
+                    "listtransactions",
+                    "",
+                    10,
+                    from,
+                    watchonly
+                )),
+                _ => self.send(&RpcRequest::new0(
+                    "listtransactions"
+                )),
+            }
+        }
+
+        fn list_unspent(&self, minconf: Option<u32>, maxconf: Option<u32>, addr_filter: Option<Vec<Address>>) -> $result<Vec<Unspent>> {
+            match (minconf, maxconf, addr_filter) {
+                (None, None, Some(filter)) => self.send(&RpcRequest::new3(
+                    "listunspent",
+                    1,
+                    9999999,
+                    filter
+                )),
+                (Some(min), None, Some(filter)) => self.send(&RpcRequest::new3(
+                    "listunspent",
+                    min,
+                    9999999,
+                    filter
+                )),
+                (None, Some(max), Some(filter)) => self.send(&RpcRequest::new3(
+                    "listunspent",
+                    1,
+                    max,
+                    filter
+                )),
+                (Some(min), Some(max), Some(filter)) => self.send(&RpcRequest::new3(
+                    "listunspent",
+                    min,
+                    max,
+                    filter
+                )),
+                (Some(min), None, None) => self.send(&RpcRequest::new2(
+                    "listunspent",
+                    min,
+                    9999999
+                )),
+                (None, Some(max), None) => self.send(&RpcRequest::new2(
+                    "listunspent",
+                    1,
+                    max
+                )),
+                (Some(min), Some(max), None) => self.send(&RpcRequest::new2(
+                    "listunspent",
+                    min,
+                    max
+                )),
+                _ => self.send(&RpcRequest::new0(
+                    "listunspent"
+                ))
+            }
+        }
+
+        fn lock_unspent(&self, unlock: bool, txns: Vec<LockedUnspent>) -> $result<bool> {
+            self.send(&RpcRequest::new2(
+                "lockunspent",
+                unlock,
+                txns
+            ))
+        }
+
+        fn resend_wallet_transactions(&self) -> $result<ResentWalletTransactions> {
+            self.send(&RpcRequest::new0(
+                "resendwallettransactions"
+            ))
+        }
+
+        fn send_many(&self, amounts: SendManyAmounts, minconf: Option<u32>, comment: Option<&str>, subtractfeefromaddresses: Option<Vec<Address>>) -> $result<Txid> {
+            match (minconf, comment, subtractfeefromaddresses) {
+                (Some(minconf), None, None) => self.send(&RpcRequest::new3(
+                    "sendmany",
+                    "",
+                    amounts,
+                    minconf
+                )),
+                (Some(minconf), Some(comment), None) => self.send(&RpcRequest::new4(
+                    "sendmany",
+                    "",
+                    amounts,
+                    minconf,
+                    comment
+                )),
+                (Some(minconf), None, Some(fees)) => self.send(&RpcRequest::new5(
+                    "sendmany",
+                    "",
+                    amounts,
+                    minconf,
+                    "",
+                    fees
+                )),
+                (Some(minconf), Some(comment), Some(fees)) => self.send(&RpcRequest::new5(
+                    "sendmany",
+                    "",
+                    amounts,
+                    minconf,
+                    comment,
+                    fees
+                )),
+                (None, Some(comment), None) => self.send(&RpcRequest::new4(
+                    "sendmany",
+                    "",
+                    amounts,
+                    1,
+                    comment
+                )),
+                (None, None, Some(fees)) => self.send(&RpcRequest::new5(
+                    "sendmany",
+                    "",
+                    amounts,
+                    1,
+                    "",
+                    fees
+                )),
+                (None, Some(comment), Some(fees)) => self.send(&RpcRequest::new5(
+                    "sendmany",
+                    "",
+                    amounts,
+                    1,
+                    comment,
+                    fees
+                )),
+                _ => self.send(&RpcRequest::new2(
+                    "sendmany",
+                    "",
+                    amounts
+                )),
+            }
+        }
+
+        fn send_to_address(&self, address: Address, amount: Amount, comment: Option<&str>, comment_to: Option<&str>, subtractfee: Option<bool>) -> $result<Txid> {
+            match address.addr_type {
+                AddrType::Transparent => {
+                    match (comment, comment_to, subtractfee) {
+                        (Some(comment), None, None) => self.send(&RpcRequest::new3(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            comment
+                        )),
+                        (Some(comment), Some(comment_to), None) => self.send(&RpcRequest::new4(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            comment,
+                            comment_to
+                        )),
+                        (Some(comment), None, Some(subtractfee)) => self.send(&RpcRequest::new5(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            comment,
+                            "",
+                            subtractfee
+                        )),
+                        (Some(comment), Some(comment_to), Some(subtractfee)) => self.send(&RpcRequest::new5(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            comment,
+                            comment_to,
+                            subtractfee
+                        )),
+                        (None, Some(comment_to), None) => self.send(&RpcRequest::new4(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            "",
+                            comment_to
+                        )),
+                        (None, None, Some(subtractfee)) => self.send(&RpcRequest::new5(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            "",
+                            "",
+                            subtractfee
+                        )),
+                        (None, Some(comment_to), Some(subtractfee)) => self.send(&RpcRequest::new5(
+                            "sendtoaddress",
+                            address,
+                            amount,
+                            "",
+                            comment_to,
+                            subtractfee
+                        )),
+                        _ => self.send(&RpcRequest::new2(
+                            "sendtoaddress",
+                            address,
+                            amount
+                        ))
+                    }
+                },
+                _ => $fail(ApiError::Other(String::from("address is not transparent")))
+            }
+        }
+
+        fn set_pubkey(&self, pubkey: &str) -> $result<SetPubkey> {
+            self.send(&RpcRequest::new1(
+                "setpubkey",
+                pubkey
+            ))
+        }
+
+        fn set_txfee(&self, fee: Amount) -> $result<bool> {
+            self.send(&RpcRequest::new1(
+                "settxfee",
+                fee
+            ))
+        }
+
+        fn sign_message(&self, address: Address, message: &str) -> $result<String> {
+            self.send(&RpcRequest::new2(
+                "signmessage",
+                address,
+                message
+            ))
+        }
+
+        fn z_exportkey(&self, a: &Address) -> $result<PrivateKey> {
+            match a.addr_type {
+                AddrType::Shielded => self.send(&RpcRequest::new1(
+                    "z_exportkey",
+                    &a.addr
+                )),
+                AddrType::Transparent => $fail(ApiError::Other("transparent key not supported in z_exportkey".to_string()))
+            }
+        }
+
+        // todo unsupported https://github.com/zcash/zcash/issues/3060
+        fn z_exportviewingkey(&self, _a: &Address) -> $result<ViewingKey> {
+    //        match a.addr_type {
+    //            AddrType::Shielded => self.send(&RpcRequest::new1(
+    //                "z_exportviewingkey",
+    //                &a.addr
+    //            )),
+    //            AddrType::Transparent => Err(ApiError::Other("transparent key not supported in z_exportviewingkey".to_string()))
+    //        }
+            $fail(ApiError::Other(String::from("not implemented")))
+        }
+
+        fn z_exportwallet(&self, s: &str) -> $result<String> {
+            self.send(&RpcRequest::new1(
+                "z_exportwallet",
+                s
+            ))
+        }
+
+        fn z_getbalance(&self, addr: &Address, minconf: Option<u32>) -> $result<Amount> {
+            match minconf {
+                Some(conf) => self.send(&RpcRequest::new2(
+                    "z_getbalance",
+                    &addr.addr,
+                    conf
+                )),
+                None => self.send(&RpcRequest::new1(
+                    "z_getbalance",
+                    &addr.addr
+                ))
+            }
+        }
+
+        // type parameter unsupported
+        fn z_getnewaddress(&self) -> $result<Address> {
+            self.send(&RpcRequest::new0(
+                "z_getnewaddress"
+            ))
+        }
+
+        fn z_getoperationresult(&self, v: Vec<&str>) -> $result<Operations> {
+            self.send(&RpcRequest::new1(
+                "z_getoperationresult",
+                v
+            ))
+        }
+
+        fn z_getoperationstatus(&self, v: Vec<&str>) -> $result<Operations> {
+            self.send(&RpcRequest::new1(
+                "z_getoperationstatus",
+                v
+            ))
+        }
+
+        fn z_gettotalbalance(&self) -> $result<TotalBalance> {
+            self.send(&RpcRequest::new0(
+                "z_gettotalbalance"
+            ))
+        }
+
+        fn z_importkey(&self) -> $result<()> {
+            $fail(ApiError::Other(String::from("not implemented")))
+        }
+
+        fn z_importviewingkey(&self) -> $result<()> {
+            $fail(ApiError::Other(String::from("not implemented")))
+        }
+
+        fn z_importwallet(&self) -> $result<()> {
+            $fail(ApiError::Other(String::from("not implemented")))
+        }
+
+        fn z_listaddresses(&self, include_watch_only: Option<bool>) -> $result<Vec<Address>> {
+            match include_watch_only {
+                Some(b) => self.send(&RpcRequest::new1(
+                    "z_listaddresses",
+                    b
+                )),
+                None => self.send(&RpcRequest::new0(
+                    "z_listaddresses"
+                ))
+            }
+        }
+
+        fn z_listoperationids(&self) -> $result<Vec<String>> {
+            self.send(&RpcRequest::new0(
+                "z_listoperationids"
+            ))
+        }
+
+        // todo check beforehand if addy is shielded!
+        fn z_listreceivedbyaddress(&self, a: &Address, minconf: Option<u32>) -> $result<ZReceivedByAddress> {
+            match a.addr_type {
+                AddrType::Shielded => self.send(&RpcRequest::new2(
+                    "z_listreceivedbyaddress",
+                    &a.addr,
+                    minconf.unwrap_or(1)
+                )),
+                _ => $fail(ApiError::Other(format!("Not a Shielded address: {}", a.addr))) // ApiError::Parameter
+            }
+        }
+
+        fn z_mergetoaddress(
+            &self,
+            from_addresses: &FromAddresses,
+            to_address: &Address,
+            fee: Option<Amount>,
+            transparent_limit: Option<u32>,
+            shielded_limit: Option<u32>,
+            maximum_utxo_size: Option<u64>,
+            memo: Option<String>
+        ) -> $result<MergeResult> {
+            match (maximum_utxo_size, memo) {
+                (Some(size), None) => self.send(&RpcRequest::new6(
+                    "z_mergetoaddress",
+                    &from_addresses.0,
+                    &to_address.addr,
+                    fee.unwrap_or(Amount::from_sat(10_000)),
+                    transparent_limit.unwrap_or(50),
+                    shielded_limit.unwrap_or(90),
+                    size
+                )),
+                (Some(size), Some(ref memo)) => self.send(&RpcRequest::new7(
+                    "z_mergetoaddress",
+                    &from_addresses.0,
+                    &to_address.addr,
+                    fee.unwrap_or(Amount::from_sat(10_000)),
+                    transparent_limit.unwrap_or(50),
+                    shielded_limit.unwrap_or(90),
+                    size,
+                    memo
+                )),
+                (None, Some(_)) =>
+                    $fail(ApiError::Other("cannot specify memo without specifying maximum_utxo_size".to_string())),
+                _ => self.send(&RpcRequest::new5(
+                    "z_mergetoaddress",
+                    &from_addresses.0,
+                    &to_address.addr,
+                    fee.unwrap_or(Amount::from_sat(10_000)),
+                    transparent_limit.unwrap_or(50),
+                    shielded_limit.unwrap_or(90)
+                ))
+            }
+        }
+
+        fn z_sendmany(
+            &self,
+            from_address: &Address,
+            amounts: &Amounts,
+            minconf: Option<u32>,
+            fee: Option<Amount>,
+        ) -> $result<String> {
+            self.send(&RpcRequest::new4(
+                "z_sendmany",
+                &from_address.addr,
+                &amounts.0,
+                minconf.unwrap_or(1),
+                fee.unwrap_or(Amount::from_sat(10_000))
+            ))
+        }
+
+        fn z_shieldcoinbase(&self, from_address: &Address, to_address: &Address, fee: Option<Amount>, limit: Option<u32>) -> $result<ShieldResult> {
+            self.send(&RpcRequest::new4(
+                "z_shieldcoinbase",
+                &from_address,
+                &to_address,
+                fee.unwrap_or(Amount::from_sat(10_000)),
+                limit.unwrap_or(50)
+            ))
+        }
+
+        // tokens: transactions are signed with the key of komodod's -pubkey and sent with send_raw_transaction
+
+        // supply in coins, 1 token per satoshi
+        fn token_create(&self, name: &str, supply: Amount, description: Option<&str>, data: Option<&str>) -> $result<SignedRawTransaction> {
+            // komodod reads the supply as a string
+            match (description, data) {
+                (None, None) => self.send_cc_transaction(&RpcRequest::new2(
+                    "tokencreate",
+                    name,
+                    supply.to_string()
+                )),
+                (Some(description), None) => self.send_cc_transaction(&RpcRequest::new3(
+                    "tokencreate",
+                    name,
+                    supply.to_string(),
+                    description
+                )),
+                (description, Some(data)) => self.send_cc_transaction(&RpcRequest::new4(
+                    "tokencreate",
+                    name,
+                    supply.to_string(),
+                    description.unwrap_or(""),
+                    data
+                )),
+            }
+        }
+
+        fn token_transfer(&self, tokenid: &Txid, destpubkey: &str, amount: u64) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "tokentransfer",
+                tokenid,
+                destpubkey,
+                amount.to_string()
+            ))
+        }
+
+        // pubkey defaults to -pubkey
+        fn token_balance(&self, tokenid: &Txid, pubkey: Option<&str>) -> $result<TokenBalance> {
+            match pubkey {
+                Some(pubkey) => self.send_cc(&RpcRequest::new2(
+                    "tokenbalance",
+                    tokenid,
+                    pubkey
+                )),
+                None => self.send_cc(&RpcRequest::new1(
+                    "tokenbalance",
+                    tokenid
+                ))
+            }
+        }
+
+        fn token_info(&self, tokenid: &Txid) -> $result<TokenInfo> {
+            self.send_cc(&RpcRequest::new1(
+                "tokeninfo",
+                tokenid
+            ))
+        }
+
+        fn token_list(&self) -> $result<Vec<Txid>> {
+            self.send(&RpcRequest::new0(
+                "tokenlist"
+            ))
+        }
+
+        // assets: prices are in coins per token
+
+        fn token_ask(&self, numtokens: u64, tokenid: &Txid, price: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "tokenask",
+                numtokens.to_string(),
+                tokenid,
+                price.to_string()
+            ))
+        }
+
+        fn token_bid(&self, numtokens: u64, tokenid: &Txid, price: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "tokenbid",
+                numtokens.to_string(),
+                tokenid,
+                price.to_string()
+            ))
+        }
+
+        fn token_fill_ask(&self, tokenid: &Txid, asktxid: &Txid, fillunits: u64) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "tokenfillask",
+                tokenid,
+                asktxid,
+                fillunits.to_string()
+            ))
+        }
+
+        // fillamount in tokens
+        fn token_fill_bid(&self, tokenid: &Txid, bidtxid: &Txid, fillamount: u64) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "tokenfillbid",
+                tokenid,
+                bidtxid,
+                fillamount.to_string()
+            ))
+        }
+
+        fn token_cancel_ask(&self, tokenid: &Txid, asktxid: &Txid) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new2(
+                "tokencancelask",
+                tokenid,
+                asktxid
+            ))
+        }
+
+        fn token_cancel_bid(&self, tokenid: &Txid, bidtxid: &Txid) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new2(
+                "tokencancelbid",
+                tokenid,
+                bidtxid
+            ))
+        }
+
+        fn token_orders(&self, tokenid: Option<&Txid>) -> $result<Vec<TokenOrder>> {
+            match tokenid {
+                Some(tokenid) => self.send(&RpcRequest::new1(
+                    "tokenorders",
+                    tokenid
+                )),
+                None => self.send(&RpcRequest::new0(
+                    "tokenorders"
+                ))
+            }
+        }
+
+        fn my_token_orders(&self) -> $result<Vec<TokenOrder>> {
+            self.send(&RpcRequest::new0(
+                "mytokenorders"
+            ))
+        }
+
+        // oracles
+
+        fn oracles_create(&self, name: &str, description: &str, format: &arguments::OracleFormat) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "oraclescreate",
+                name,
+                description,
+                format.as_str()
+            ))
+        }
+
+        // needed before oracles_register
+        fn oracles_fund(&self, oracletxid: &Txid) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new1(
+                "oraclesfund",
+                oracletxid
+            ))
+        }
+
+        fn oracles_register(&self, oracletxid: &Txid, datafee: Amount) -> $result<SignedRawTransaction> {
+            // komodod reads "1.00000000" as 1 satoshi, but satoshis as they are
+            self.send_cc_transaction(&RpcRequest::new2(
+                "oraclesregister",
+                oracletxid,
+                datafee.as_sat().to_string()
+            ))
+        }
+
+        fn oracles_subscribe(&self, oracletxid: &Txid, publisher: &str, amount: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "oraclessubscribe",
+                oracletxid,
+                publisher,
+                amount.to_string()
+            ))
+        }
+
+        // see OracleFormat::encode
+        fn oracles_data(&self, oracletxid: &Txid, data: &arguments::OracleData) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new2(
+                "oraclesdata",
+                oracletxid,
+                &data.0
+            ))
+        }
+
+        fn oracles_sample(&self, oracletxid: &Txid, txid: &Txid) -> $result<OracleSample> {
+            self.send_cc(&RpcRequest::new2(
+                "oraclessample",
+                oracletxid,
+                txid
+            ))
+        }
+
+        fn oracles_samples(&self, oracletxid: &Txid, batonutxo: &Txid, num: u32) -> $result<OracleSamples> {
+            self.send_cc(&RpcRequest::new3(
+                "oraclessamples",
+                oracletxid,
+                batonutxo,
+                num.to_string()
+            ))
+        }
+
+        fn oracles_info(&self, oracletxid: &Txid) -> $result<OracleInfo> {
+            self.send_cc(&RpcRequest::new1(
+                "oraclesinfo",
+                oracletxid
+            ))
+        }
+
+        fn oracles_list(&self) -> $result<Vec<Txid>> {
+            self.send(&RpcRequest::new0(
+                "oracleslist"
+            ))
+        }
+
+        // channels
+
+        fn channels_open(&self, destpubkey: &str, numpayments: u32, payment: Amount) -> $result<SignedRawTransaction> {
+            // komodod reads the amounts in satoshis
+            self.send_cc_transaction(&RpcRequest::new3(
+                "channelsopen",
+                destpubkey,
+                numpayments.to_string(),
+                payment.as_sat().to_string()
+            ))
+        }
+
+        // amount is a multiple of the payment
+        fn channels_payment(&self, opentxid: &Txid, amount: Amount, secret: Option<&ChannelSecret>) -> $result<SignedRawTransaction> {
+            match secret {
+                Some(secret) => self.send_cc_transaction(&RpcRequest::new3(
+                    "channelspayment",
+                    opentxid,
+                    amount.as_sat().to_string(),
+                    secret
+                )),
+                None => self.send_cc_transaction(&RpcRequest::new2(
+                    "channelspayment",
+                    opentxid,
+                    amount.as_sat().to_string()
+                ))
+            }
+        }
+
+        fn channels_close(&self, opentxid: &Txid) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new1(
+                "channelsclose",
+                opentxid
+            ))
+        }
+
+        fn channels_refund(&self, opentxid: &Txid, closetxid: &Txid) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new2(
+                "channelsrefund",
+                opentxid,
+                closetxid
+            ))
+        }
+
+        fn channels_info(&self, opentxid: &Txid) -> $result<ChannelInfo> {
+            self.send_cc(&RpcRequest::new1(
+                "channelsinfo",
+                opentxid
+            ))
+        }
+
+        fn channels_list(&self) -> $result<Vec<Txid>> {
+            self.send(&RpcRequest::new0(
+                "channelslist"
+            ))
+        }
+
+        // faucet
+
+        fn faucet_fund(&self, amount: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new1(
+                "faucetfund",
+                amount.to_string()
+            ))
+        }
+
+        // only for addresses without coins, takes a while as komodod searches for a valid txid
+        fn faucet_get(&self) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new0(
+                "faucetget"
+            ))
+        }
+
+        fn faucet_info(&self) -> $result<FaucetInfo> {
+            self.send_cc(&RpcRequest::new0(
+                "faucetinfo"
+            ))
+        }
+
+        // rewards: the Rewards CC module, not the KMD rewards of claim_rewards
+
+        // apr in percent
+        fn rewards_create_funding(&self, name: &str, amount: Amount, apr: f64, mindays: u32, maxdays: u32, mindeposit: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new6(
+                "rewardscreatefunding",
+                name,
+                amount.to_string(),
+                apr.to_string(),
+                mindays.to_string(),
+                maxdays.to_string(),
+                mindeposit.to_string()
+            ))
+        }
+
+        fn rewards_lock(&self, name: &str, fundingtxid: &Txid, amount: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "rewardslock",
+                name,
+                fundingtxid,
+                amount.to_string()
+            ))
+        }
+
+        // txid of the lock, or all locks
+        fn rewards_unlock(&self, name: &str, fundingtxid: &Txid, txid: Option<&Txid>) -> $result<SignedRawTransaction> {
+            match txid {
+                Some(txid) => self.send_cc_transaction(&RpcRequest::new3(
+                    "rewardsunlock",
+                    name,
+                    fundingtxid,
+                    txid
+                )),
+                None => self.send_cc_transaction(&RpcRequest::new2(
+                    "rewardsunlock",
+                    name,
+                    fundingtxid
+                ))
+            }
+        }
+
+        fn rewards_info(&self, fundingtxid: &Txid) -> $result<RewardsInfo> {
+            self.send_cc(&RpcRequest::new1(
+                "rewardsinfo",
+                fundingtxid
+            ))
+        }
+
+        fn rewards_list(&self) -> $result<Vec<Txid>> {
+            self.send(&RpcRequest::new0(
+                "rewardslist"
+            ))
+        }
+
+        // dice
+
+        fn dice_fund(&self, name: &str, funds: Amount, minbet: Amount, maxbet: Amount, maxodds: u32, timeoutblocks: u32) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new6(
+                "dicefund",
+                name,
+                funds.to_string(),
+                minbet.to_string(),
+                maxbet.to_string(),
+                maxodds.to_string(),
+                timeoutblocks.to_string()
+            ))
+        }
+
+        fn dice_add_funds(&self, name: &str, fundingtxid: &Txid, amount: Amount) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "diceaddfunds",
+                name,
+                fundingtxid,
+                amount.to_string()
+            ))
+        }
+
+        fn dice_bet(&self, name: &str, fundingtxid: &Txid, amount: Amount, odds: u32) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new4(
+                "dicebet",
+                name,
+                fundingtxid,
+                amount.to_string(),
+                odds.to_string()
+            ))
+        }
+
+        // by the house
+        fn dice_finish(&self, name: &str, fundingtxid: &Txid, bettxid: &Txid) -> $result<SignedRawTransaction> {
+            self.send_cc_transaction(&RpcRequest::new3(
+                "dicefinish",
+                name,
+                fundingtxid,
+                bettxid
+            ))
+        }
+
+        // without a bet, finishes the pending bets
+        fn dice_status(&self, name: &str, fundingtxid: &Txid, bettxid: Option<&Txid>) -> $result<DiceStatus> {
+            match bettxid {
+                Some(bettxid) => self.send_cc(&RpcRequest::new3(
+                    "dicestatus",
+                    name,
+                    fundingtxid,
+                    bettxid
+                )),
+                None => self.send_cc(&RpcRequest::new2(
+                    "dicestatus",
+                    name,
+                    fundingtxid
+                ))
+            }
+        }
+
+        fn dice_info(&self, fundingtxid: &Txid) -> $result<DiceInfo> {
+            self.send_cc(&RpcRequest::new1(
+                "diceinfo",
+                fundingtxid
+            ))
+        }
+
+        fn dice_list(&self) -> $result<Vec<Txid>> {
+            self.send(&RpcRequest::new0(
+                "dicelist"
+            ))
+        }
+    ); };
+}
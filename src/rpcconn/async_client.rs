@@ -0,0 +1,80 @@
+use reqwest::async::Client as AsyncHttpClient;
+use reqwest::header::CONTENT_TYPE;
+
+use futures::{future, Future, Stream};
+use serde_json;
+use serde_json::value::RawValue;
+use std::fmt::Debug;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use rpcconn::{BatchResponse, ClientError, RpcBatch, RpcRequest, RpcResponse};
+use rpcconn::client::parse_body;
+
+use error::ApiError;
+
+/// The future returned by every call of the `AsyncClient`.
+pub type ApiFuture<T> = Box<dyn Future<Item = T, Error = ApiError> + Send>;
+
+#[derive(Debug)]
+pub struct AsyncRpcClient {
+    client: AsyncHttpClient,
+    url: String,
+}
+
+impl AsyncRpcClient {
+    pub fn new(client: AsyncHttpClient, url: &str) -> Self {
+        AsyncRpcClient {
+            client,
+            url: url.to_string(),
+        }
+    }
+
+    /// The request is serialized before this function returns, so the returned future
+    /// does not borrow `request`.
+    pub fn send<R, T>(
+        &self,
+        request: &RpcRequest<T>,
+    ) -> ApiFuture<R>
+        where
+            T: Serialize + Debug,
+            R: DeserializeOwned + Debug + Send + 'static,
+    {
+        Box::new(self.post::<_, RpcResponse<Box<RawValue>>>(request)
+            .and_then(|res| res.into_typed_result()))
+    }
+
+    /// Sends all requests in `batch` in a single HTTP request.
+    pub fn send_batch(&self, batch: RpcBatch) -> ApiFuture<BatchResponse> {
+        Box::new(self.post::<_, Vec<RpcResponse<Box<RawValue>>>>(batch.requests())
+            .map(move |responses| batch.match_responses(responses)))
+    }
+
+    fn post<B, R>(&self, body: &B) -> ApiFuture<R>
+        where
+            B: Serialize + ?Sized,
+            R: DeserializeOwned + Send + 'static,
+    {
+        let body = match serde_json::to_vec(body) {
+            Ok(body) => body,
+            Err(err) => return Box::new(future::err(ApiError::Client(ClientError::Json(err)))),
+        };
+
+        let res = self
+            .client
+            .post(self.url.as_str())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .and_then(|res| {
+                let status = res.status().as_u16();
+
+                res.into_body().concat2().map(move |buf| (status, buf))
+            })
+            .map_err(ClientError::Transport)
+            .and_then(|(status, buf)| parse_body::<R>(status, &buf))
+            .map_err(ApiError::Client);
+
+        Box::new(res)
+    }
+}
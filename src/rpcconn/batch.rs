@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::value::{to_raw_value, RawValue};
+
+use rpcconn::{ClientError, RpcRequest, RpcResponse};
+use error::ApiError;
+
+/// A set of requests that is sent to komodod as a single JSON-RPC batch, in one round trip.
+///
+/// Every request that is added returns a `BatchEntry`, which remembers the type of its response.
+/// After the batch has been sent, each entry is looked up in the `BatchResponse` and deserialized on its own,
+/// so a failing request does not fail the other requests in the batch.
+///
+/// ```no_run
+/// # use komodo_rpc_client::{Client, BlockHash, RpcBatch, RpcRequest};
+/// let client = Client::new_komodo_client().unwrap();
+///
+/// let mut batch = RpcBatch::new();
+/// let entries = (1_000_000..1_000_100)
+///     .map(|height| batch.add::<BlockHash, _>(&RpcRequest::new1("getblockhash", height)))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// let response = client.send_batch(&batch).unwrap();
+///
+/// for entry in entries {
+///     match response.get(&entry) {
+///         Ok(hash) => println!("{}", hash),
+///         Err(e) => println!("{}", e),
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct RpcBatch {
+    requests: Vec<Box<RawValue>>,
+    methods: Vec<String>,
+    ids: Vec<String>,
+}
+
+impl RpcBatch {
+    pub fn new() -> Self {
+        RpcBatch::default()
+    }
+
+    /// Queues `request`. `R` is the type its response is deserialized into.
+    ///
+    /// Fails for a request whose id is already in the batch, such as the same request added twice: their
+    /// responses could not be told apart.
+    pub fn add<R, P>(&mut self, request: &RpcRequest<P>) -> Result<BatchEntry<R>, ApiError>
+        where
+            R: DeserializeOwned,
+            P: Serialize + Debug,
+    {
+        if self.ids.iter().any(|id| id == request.id()) {
+            return Err(ApiError::Other(format!(
+                "request {} with id {} is already in the batch", request.method(), request.id()
+            )));
+        }
+
+        let value = to_raw_value(request).map_err(ClientError::Json)?;
+
+        self.requests.push(value);
+        self.methods.push(request.method().to_string());
+        self.ids.push(request.id().to_string());
+
+        Ok(BatchEntry {
+            index: self.requests.len() - 1,
+            response_type: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// The JSON array that is sent to komodod.
+    pub fn requests(&self) -> &[Box<RawValue>] {
+        &self.requests
+    }
+
+    /// The methods of the requests, in the order they were added.
+    pub fn methods(&self) -> &[String] {
+        &self.methods
+    }
+
+    /// Matches the responses of a batch with the requests in this batch, by their ids.
+    /// komodod may return the responses in any order.
+    ///
+    /// Responses without an id, or with an id that is not in this batch, end up in `BatchResponse::unmatched`.
+    pub fn match_responses(&self, responses: Vec<RpcResponse<Box<RawValue>>>) -> BatchResponse {
+        let mut by_id: HashMap<String, RpcResponse<Box<RawValue>>> = HashMap::new();
+        let mut unmatched = Vec::new();
+        for response in responses {
+            match response.id.clone() {
+                Some(ref id) if self.ids.contains(id) && !by_id.contains_key(id) => {
+                    by_id.insert(id.clone(), response);
+                },
+                _ => unmatched.push(response),
+            }
+        }
+
+        BatchResponse {
+            responses: self.ids
+                .iter()
+                .map(|id| by_id.remove(id))
+                .collect(),
+            unmatched,
+        }
+    }
+}
+
+/// A request in a `RpcBatch`, with `R` being the type of its response.
+#[derive(Debug)]
+pub struct BatchEntry<R> {
+    index: usize,
+    response_type: PhantomData<R>,
+}
+
+/// The responses of a `RpcBatch`, in the order their requests were added.
+#[derive(Debug)]
+pub struct BatchResponse {
+    responses: Vec<Option<RpcResponse<Box<RawValue>>>>,
+    unmatched: Vec<RpcResponse<Box<RawValue>>>,
+}
+
+impl BatchResponse {
+    /// The response to `entry`, deserialized into its response type.
+    pub fn get<R: DeserializeOwned>(&self, entry: &BatchEntry<R>) -> Result<R, ApiError> {
+        let response = match self.responses.get(entry.index) {
+            Some(Some(response)) => response,
+            _ => return Err(ApiError::Other(format!("no response for batch entry {}", entry.index))),
+        };
+
+        response.clone().into_typed_result()
+    }
+
+    /// The responses that belong to no request of the batch. These are usually errors with a `null` id,
+    /// which komodod answers with when it cannot tell which request failed.
+    pub fn unmatched(&self) -> &[RpcResponse<Box<RawValue>>] {
+        &self.unmatched
+    }
+
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
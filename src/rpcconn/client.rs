@@ -1,52 +1,281 @@
 use reqwest;
 use reqwest::Client as HttpClient;
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 
 use serde_json;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use std::io::Read;
 use rpcconn::RpcRequest;
 use rpcconn::RpcResponse;
+use rpcconn::{BatchResponse, RpcBatch, Transport};
+use serde_json::Value;
+use serde_json::value::RawValue;
 
 use std::error;
 use std::fmt;
 
 use error::ApiError;
 
+/// How long a request may take, unless its method has a timeout of its own.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// The timeout of the methods in `SLOW_METHODS`.
+pub const SLOW_METHOD_TIMEOUT: Duration = Duration::from_secs(600);
+/// Methods that take komodod minutes to answer: they go through the whole UTXO set, or, for `z_mergetoaddress`,
+/// select notes before returning an operation id. `faucetget` searches for a transaction with a valid txid.
+pub const SLOW_METHODS: [&str; 4] = ["getsnapshot", "gettxoutsetinfo", "z_mergetoaddress", "faucetget"];
+
 #[derive(Debug)]
 pub struct RpcClient {
     client: HttpClient,
+    /// The timeout of `client`, which is reqwest's default of 30 seconds for a client made with `new`.
+    timeout: Duration,
     url: String,
+    /// The clients of the methods with a timeout of their own, with that timeout.
+    method_clients: HashMap<String, (Duration, HttpClient)>,
+    /// `None` for a client made with `new`, which authenticates through the default headers of its HTTP client.
+    credentials: Option<Arc<Credentials>>,
+    settings: Option<HttpSettings>,
+}
+
+/// The `Authorization` header of the requests of a `RpcClient`, and the cookie file it was read from, if any.
+#[derive(Debug)]
+pub(crate) struct Credentials {
+    header: RwLock<HeaderValue>,
+    cookie_file: Option<PathBuf>,
+}
+
+impl Credentials {
+    pub fn new(rpc_user: &str, rpc_password: &str) -> Result<Self, ApiError> {
+        Ok(Credentials {
+            header: RwLock::new(basic_auth(rpc_user, rpc_password)?),
+            cookie_file: None,
+        })
+    }
+
+    /// A cookie file contains `user:password` on a single line. komodod writes a new one every time it starts.
+    pub fn from_cookie_file(path: &Path) -> Result<Self, ApiError> {
+        let (rpc_user, rpc_password) = read_cookie_file(path)?;
+
+        Ok(Credentials {
+            header: RwLock::new(basic_auth(&rpc_user, &rpc_password)?),
+            cookie_file: Some(path.to_path_buf()),
+        })
+    }
+
+    pub fn header(&self) -> HeaderValue {
+        self.header.read().unwrap().clone()
+    }
+
+    /// Reads the cookie file again. True if it holds other credentials than before.
+    fn refresh(&self) -> bool {
+        let path = match self.cookie_file {
+            Some(ref path) => path,
+            None => return false,
+        };
+
+        match Credentials::from_cookie_file(path) {
+            Ok(credentials) => {
+                let header = credentials.header();
+                let mut current = self.header.write().unwrap();
+                if *current == header {
+                    return false;
+                }
+
+                *current = header;
+
+                true
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+fn basic_auth(rpc_user: &str, rpc_password: &str) -> Result<HeaderValue, ApiError> {
+    HeaderValue::from_str(&format!(
+        "Basic {}",
+        base64::encode(&format!("{}:{}", rpc_user, rpc_password))
+    )).map_err(|_| ApiError::Config(String::from("rpc credentials contain invalid characters")))
+}
+
+fn read_cookie_file(path: &Path) -> Result<(String, String), ApiError> {
+    let contents = fs::read_to_string(path)?;
+    let mut split = contents.trim().splitn(2, ':');
+
+    match (split.next(), split.next()) {
+        (Some(user), Some(password)) => Ok((user.to_string(), password.to_string())),
+        _ => Err(ApiError::Config(format!("invalid cookie file: {}", path.display())))
+    }
+}
+
+/// The settings of the HTTP connection to komodod, set through `ClientBuilder`.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpSettings {
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub keep_alive: bool,
+    pub max_idle_per_host: Option<usize>,
+    pub method_timeouts: HashMap<String, Option<Duration>>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        HttpSettings {
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            keep_alive: true,
+            max_idle_per_host: None,
+            method_timeouts: SLOW_METHODS
+                .iter()
+                .map(|method| (method.to_string(), Some(SLOW_METHOD_TIMEOUT)))
+                .collect(),
+        }
+    }
+}
+
+impl HttpSettings {
+    /// The most idle connections per host: none without keep-alive.
+    pub fn max_idle(&self) -> Option<usize> {
+        if self.keep_alive {
+            self.max_idle_per_host
+        } else {
+            Some(0)
+        }
+    }
+
+    fn http_client(&self, timeout: Option<Duration>) -> Result<HttpClient, ApiError> {
+        let mut builder = HttpClient::builder()
+            .timeout(timeout)
+            .connect_timeout(self.connect_timeout);
+        if let Some(max_idle) = self.max_idle() {
+            builder = builder.max_idle_per_host(max_idle);
+        }
+
+        builder
+            .build()
+            .map_err(|e| ApiError::Other(format!("unable to create http client: {}", e)))
+    }
 }
 
 #[derive(Debug)]
 pub enum ClientError {
     Transport(reqwest::Error),
     Json(serde_json::Error),
+    /// HTTP 401: komodod did not accept the RPC credentials.
+    Unauthorized,
+    /// HTTP 403: komodod does not accept RPC connections from this IP, see `rpcallowip`.
+    Forbidden,
+    /// Any other HTTP status that came without a JSON-RPC response, such as 503 when komodod's work queue is full.
+    Status(u16),
+    /// A response without a result and without an error.
+    EmptyResponse,
 }
 
 impl error::Error for ClientError {
-    fn cause(&self) -> Option<&dyn error::Error> {
-        self.source()
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ClientError::Transport(ref err) => Some(err),
+            ClientError::Json(ref err) => Some(err),
+            _ => None,
+        }
     }
 }
 
 impl fmt::Display for ClientError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Something bad happened: {}", self.to_string())
+        match *self {
+            ClientError::Transport(ref err) => fmt::Display::fmt(err, f),
+            ClientError::Json(ref err) => fmt::Display::fmt(err, f),
+            ClientError::Unauthorized => write!(f, "HTTP 401: incorrect rpcuser or rpcpassword"),
+            ClientError::Forbidden => write!(f, "HTTP 403: this IP is not allowed by rpcallowip"),
+            ClientError::Status(status) => write!(f, "HTTP {} without a JSON-RPC response", status),
+            ClientError::EmptyResponse => write!(f, "response without a result or an error"),
+        }
+    }
+}
+
+/// No timeout is the longest timeout.
+fn longest(timeout: Option<Duration>) -> Duration {
+    timeout.unwrap_or(Duration::from_secs(u64::MAX))
+}
+
+/// Parses the body of a HTTP response of komodod. komodod answers RPC errors with a JSON-RPC error and
+/// a status of 404 or 500, so the status only matters when the body is not JSON.
+pub(crate) fn parse_body<R: DeserializeOwned>(status: u16, body: &[u8]) -> Result<R, ClientError> {
+    match status {
+        401 => Err(ClientError::Unauthorized),
+        403 => Err(ClientError::Forbidden),
+        _ => serde_json::from_slice(body).map_err(|err| match status {
+            200..=299 => ClientError::Json(err),
+            _ => ClientError::Status(status),
+        }),
     }
 }
 
 impl RpcClient {
+    /// A `RpcClient` that sends every request through `client`.
     pub fn new(client: HttpClient, url: &str) -> Self {
         RpcClient {
             client,
+            timeout: DEFAULT_TIMEOUT,
             url: url.to_string(),
+            method_clients: HashMap::new(),
+            credentials: None,
+            settings: None,
         }
     }
 
+    pub(crate) fn with_settings(url: &str, credentials: Arc<Credentials>, settings: HttpSettings) -> Result<Self, ApiError> {
+        // one HTTP client, with its own connection pool, for every distinct timeout
+        let mut clients: Vec<(Option<Duration>, HttpClient)> = vec![];
+        let mut method_clients = HashMap::new();
+        for (method, timeout) in &settings.method_timeouts {
+            if *timeout == settings.timeout {
+                continue;
+            }
+
+            let client = match clients.iter().find(|client| client.0 == *timeout) {
+                Some(client) => client.1.clone(),
+                None => {
+                    let client = settings.http_client(*timeout)?;
+                    clients.push((*timeout, client.clone()));
+
+                    client
+                },
+            };
+            method_clients.insert(method.clone(), (longest(*timeout), client));
+        }
+
+        Ok(RpcClient {
+            client: settings.http_client(settings.timeout)?,
+            timeout: longest(settings.timeout),
+            url: url.to_string(),
+            method_clients,
+            credentials: Some(credentials),
+            settings: Some(settings),
+        })
+    }
+
+    /// The same client, but with `timeout` for all methods.
+    pub fn with_timeout(&self, timeout: Duration) -> Result<Self, ApiError> {
+        let (credentials, settings) = match (&self.credentials, &self.settings) {
+            (Some(credentials), Some(settings)) => (credentials.clone(), settings.clone()),
+            _ => return Err(ApiError::Config(String::from("a RpcClient made with RpcClient::new has no settings"))),
+        };
+
+        RpcClient::with_settings(&self.url, credentials, HttpSettings {
+            timeout: Some(timeout),
+            method_timeouts: HashMap::new(),
+            ..settings
+        })
+    }
+
     pub fn send<R, T>(
         &self,
         request: &RpcRequest<T>,
@@ -55,34 +284,85 @@ impl RpcClient {
             T: Serialize + Debug,
             R: DeserializeOwned + Debug,
     {
-        let res = self
-            .client
-            .post(self.url.as_str())
-            // TODO: Avoid serializing twice
-            .json(request)
-            .send()
-            .map_err(|err| ClientError::Transport(err))
-            .and_then(|mut res| {
-                let mut buf = String::new();
-                let _ = res.read_to_string(&mut buf);
+        let body = serde_json::to_string(request).map_err(ClientError::Json)?;
+        let res: RpcResponse<Box<RawValue>> = serde_json::from_str(self.post(&body)?.get()).map_err(ClientError::Json)?;
 
-                let s = serde_json::from_str(&buf).map_err(|err| ClientError::Json(err));
+        res.into_typed_result()
+    }
 
-//                dbg!(&s);
+    /// Sends all requests in `batch` in a single HTTP request.
+    pub fn send_batch(&self, batch: &RpcBatch) -> Result<BatchResponse, ApiError> {
+        let body = serde_json::to_string(batch.requests()).map_err(ClientError::Json)?;
+        let responses: Vec<RpcResponse<Box<RawValue>>> = serde_json::from_str(self.post(&body)?.get())
+            .map_err(ClientError::Json)?;
 
-                s
-            });
+        Ok(batch.match_responses(responses))
+    }
 
-        let res = res.map(RpcResponse::into_result);
+    /// The HTTP client with the longest timeout of the methods in `body`.
+    fn http_client(&self, body: &Value) -> &HttpClient {
+        let requests = match body {
+            Value::Array(requests) => requests.iter().collect(),
+            request => vec![request],
+        };
 
-        match res {
-            Ok(one) => {
-                match one {
-                    Ok(two) => Ok(two),
-                    Err(rpc_error) => Err(ApiError::RPC(rpc_error))
+        requests
+            .iter()
+            .map(|request| {
+                match request["method"].as_str().and_then(|method| self.method_clients.get(method)) {
+                    Some((timeout, client)) => (*timeout, client),
+                    None => (self.timeout, &self.client),
                 }
+            })
+            .max_by_key(|client| client.0)
+            .map(|client| client.1)
+            .unwrap_or(&self.client)
+    }
+
+    /// Sends the JSON text `body` and returns the JSON text of the response. After a 401 the cookie file
+    /// is read again, since komodod writes a new one when it restarts.
+    fn post(&self, body: &str) -> Result<Box<RawValue>, ClientError> {
+        match self.post_once(body) {
+            Err(ClientError::Unauthorized) if self.credentials.as_ref().is_some_and(|c| c.refresh()) => {
+                self.post_once(body)
             },
-            Err(client_error) => Err(ApiError::Client(client_error)),
+            result => result,
         }
     }
-}
\ No newline at end of file
+
+    fn post_once(&self, body: &str) -> Result<Box<RawValue>, ClientError> {
+        // the methods decide the timeout
+        let requests = serde_json::from_str(body).unwrap_or(Value::Null);
+        let mut request = self
+            .http_client(&requests)
+            .post(self.url.as_str());
+        if let Some(ref credentials) = self.credentials {
+            request = request.header(AUTHORIZATION, credentials.header());
+        }
+
+        request
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .map_err(ClientError::Transport)
+            .and_then(|mut res| {
+                // the timeout also covers reading the body
+                let mut buf = Vec::new();
+                res.copy_to(&mut buf).map_err(ClientError::Transport)?;
+
+                parse_body(res.status().as_u16(), &buf)
+            })
+    }
+}
+
+impl Transport for RpcClient {
+    fn send(&self, body: &Value) -> Result<Value, ApiError> {
+        let response = self.send_text(&body.to_string())?;
+
+        Ok(serde_json::from_str(&response).map_err(ClientError::Json)?)
+    }
+
+    fn send_text(&self, body: &str) -> Result<String, ApiError> {
+        Ok(self.post(body)?.get().to_string())
+    }
+}
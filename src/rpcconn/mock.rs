@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::{self, Value};
+
+use rpcconn::Transport;
+use error::ApiError;
+
+/// A `Transport` that replays canned responses instead of talking to komodod.
+///
+/// Responses are keyed by method and params. A response added through `add_method_response` is returned
+/// for every call of that method whose params have no response of their own. A call without any matching
+/// response is answered with komodod's "Method not found" error.
+///
+/// ```
+/// # extern crate komodo_rpc_client;
+/// # #[macro_use] extern crate serde_json;
+/// # use komodo_rpc_client::{Client, KomodoRpcApi, MockTransport};
+/// # fn main() {
+/// let mut transport = MockTransport::new();
+/// transport.add_response("getconnectioncount", json!([]), json!(8));
+///
+/// let client = Client::with_transport(transport);
+///
+/// assert_eq!(client.get_connection_count().unwrap(), 8);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: HashMap<String, MockResponse>,
+    fallbacks: HashMap<String, MockResponse>,
+    requests: Mutex<Vec<Value>>,
+}
+
+#[derive(Debug, Clone)]
+enum MockResponse {
+    Result(Value),
+    Error(i32, String),
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        MockTransport::default()
+    }
+
+    /// Answers `method` called with `params` with `result`.
+    pub fn add_response<P: Serialize>(&mut self, method: &str, params: P, result: Value) -> &mut Self {
+        let key = MockTransport::key(method, &to_value(params));
+        self.responses.insert(key, MockResponse::Result(result));
+
+        self
+    }
+
+    /// Answers `method` called with `params` with a RPC error.
+    pub fn add_error<P: Serialize>(&mut self, method: &str, params: P, code: i32, message: &str) -> &mut Self {
+        let key = MockTransport::key(method, &to_value(params));
+        self.responses.insert(key, MockResponse::Error(code, message.to_string()));
+
+        self
+    }
+
+    /// Answers `method` with `result`, regardless of its params.
+    pub fn add_method_response(&mut self, method: &str, result: Value) -> &mut Self {
+        self.fallbacks.insert(method.to_string(), MockResponse::Result(result));
+
+        self
+    }
+
+    /// Answers `method` with a RPC error, regardless of its params.
+    pub fn add_method_error(&mut self, method: &str, code: i32, message: &str) -> &mut Self {
+        self.fallbacks.insert(method.to_string(), MockResponse::Error(code, message.to_string()));
+
+        self
+    }
+
+    /// All request objects that were sent through this transport, in order.
+    /// The requests of a batch are recorded one by one.
+    pub fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn key(method: &str, params: &Value) -> String {
+        // `Value`'s `Display` is compact JSON, which makes equal params equal keys
+        format!("{}{}", method, params)
+    }
+
+    fn respond(&self, request: &Value) -> Value {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let method = request["method"].as_str().unwrap_or("");
+        let params = match request["params"] {
+            Value::Null => Value::Array(vec![]),
+            ref params => params.clone(),
+        };
+
+        let response = self.responses.get(&MockTransport::key(method, &params))
+            .or_else(|| self.fallbacks.get(method))
+            .cloned()
+            .unwrap_or_else(|| MockResponse::Error(-32601, String::from("Method not found")));
+
+        match response {
+            MockResponse::Result(result) => json!({
+                "result": result,
+                "error": null,
+                "id": request["id"],
+            }),
+            MockResponse::Error(code, message) => json!({
+                "result": null,
+                "error": { "code": code, "message": message },
+                "id": request["id"],
+            }),
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, body: &Value) -> Result<Value, ApiError> {
+        match body {
+            Value::Array(requests) => Ok(Value::Array(
+                requests.iter().map(|request| self.respond(request)).collect()
+            )),
+            Value::Object(_) => Ok(self.respond(body)),
+            _ => Err(ApiError::Other(String::from("a JSON-RPC body is either an object or an array"))),
+        }
+    }
+}
+
+/// Params without a value, such as `()`, serialize to `null`, which komodod treats as no params.
+fn to_value<P: Serialize>(params: P) -> Value {
+    match serde_json::to_value(params) {
+        Ok(Value::Null) | Err(_) => Value::Array(vec![]),
+        Ok(value) => value,
+    }
+}
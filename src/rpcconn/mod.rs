@@ -1,9 +1,21 @@
+pub mod batch;
 pub mod client;
+pub mod mock;
+#[cfg(feature = "async")]
+pub mod async_client;
 pub mod request;
 pub mod response;
+pub mod retry;
+pub mod transport;
 
+pub use self::batch::{BatchEntry, BatchResponse, RpcBatch};
 pub use client::ClientError as ClientError;
-pub use client::RpcClient;
+pub use client::{RpcClient, DEFAULT_TIMEOUT, SLOW_METHODS, SLOW_METHOD_TIMEOUT};
+#[cfg(feature = "async")]
+pub use self::async_client::{ApiFuture, AsyncRpcClient};
 pub use request::{JsonRpcVersion, RpcRequest};
 pub use reqwest::{Client as HTTPClient, ClientBuilder as HTTPClientBuilder};
-pub use response::{RpcError, RpcResponse};
+pub use self::mock::MockTransport;
+pub use response::{RpcError, RpcErrorCode, RpcResponse};
+pub use self::retry::RetryPolicy;
+pub use self::transport::Transport;
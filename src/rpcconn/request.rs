@@ -1,4 +1,6 @@
 use serde::Serialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[derive(Serialize, Debug, Deserialize, PartialEq)]
 pub enum JsonRpcVersion {
@@ -21,14 +23,19 @@ pub struct RpcRequest<P>
 
 impl RpcRequest<()> {
     pub fn new0(method: &str) -> RpcRequest<()> {
-        RpcRequest::new(JsonRpcVersion::V1, "777", method, ())
+        RpcRequest::new(JsonRpcVersion::V1, method, ())
+    }
+
+    /// A request with any number of params, such as for `Client::call`.
+    pub fn with_params(method: &str, params: Vec<Value>) -> RpcRequest<Vec<Value>> {
+        RpcRequest::new(JsonRpcVersion::V1, method, params)
     }
 
     pub fn new1<A>(method: &str, first: A) -> RpcRequest<Vec<A>>
         where
             A: Serialize,
     {
-        RpcRequest::new(JsonRpcVersion::V1, "777", method, vec![first]) // Handles the special case of one parameter. A tuple would be serialized as a single value.
+        RpcRequest::new(JsonRpcVersion::V1, method, vec![first]) // Handles the special case of one parameter. A tuple would be serialized as a single value.
     }
 
     pub fn new2<A, B>(
@@ -40,7 +47,7 @@ impl RpcRequest<()> {
             A: Serialize,
             B: Serialize,
     {
-        RpcRequest::new(JsonRpcVersion::V1, "777", method, (first, second))
+        RpcRequest::new(JsonRpcVersion::V1, method, (first, second))
     }
 
     pub fn new3<A, B, C>(
@@ -54,7 +61,7 @@ impl RpcRequest<()> {
             B: Serialize,
             C: Serialize,
     {
-        RpcRequest::new(JsonRpcVersion::V1, "777", method, (first, second, third))
+        RpcRequest::new(JsonRpcVersion::V1, method, (first, second, third))
     }
 
     pub fn new4<A, B, C, D>(
@@ -70,7 +77,7 @@ impl RpcRequest<()> {
             C: Serialize,
             D: Serialize,
     {
-        RpcRequest::new(JsonRpcVersion::V1, "777", method, (first, second, third, fourth))
+        RpcRequest::new(JsonRpcVersion::V1, method, (first, second, third, fourth))
     }
 
     pub fn new5<A, B, C, D, E>(
@@ -88,7 +95,7 @@ impl RpcRequest<()> {
             D: Serialize,
             E: Serialize,
     {
-        RpcRequest::new(JsonRpcVersion::V1, "777", method, (first, second, third, fourth, fifth))
+        RpcRequest::new(JsonRpcVersion::V1, method, (first, second, third, fourth, fifth))
     }
 
     pub fn new6<A, B, C, D, E, F>(
@@ -109,7 +116,7 @@ impl RpcRequest<()> {
             F: Serialize,
     {
         RpcRequest::new(
-            JsonRpcVersion::V1, "777",
+            JsonRpcVersion::V1,
             method,
             (first, second, third, fourth, fifth, sixth),
         )
@@ -135,21 +142,41 @@ impl RpcRequest<()> {
             G: Serialize,
     {
         RpcRequest::new(
-            JsonRpcVersion::V1, "777",
+            JsonRpcVersion::V1,
             method,
             (first, second, third, fourth, fifth, sixth, seventh),
         )
     }
 
-    fn new<P>(version: JsonRpcVersion, id: &str, method: &str, params: P) -> RpcRequest<P>
+    fn new<P>(version: JsonRpcVersion, method: &str, params: P) -> RpcRequest<P>
         where
             P: Serialize,
     {
         RpcRequest {
             jsonrpc: version,
-            id: id.to_string(),
+            id: next_id(),
             method: method.to_string(),
-            params: params,
+            params,
         }
     }
+}
+
+impl<P> RpcRequest<P>
+    where
+        P: Serialize,
+{
+    /// Every request gets a unique id, so the responses of a batch can be matched to their requests.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+fn next_id() -> String {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed).to_string()
 }
\ No newline at end of file
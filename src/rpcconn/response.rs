@@ -1,21 +1,171 @@
 use std::result::Result as StdResult;
+use serde::de::DeserializeOwned;
+use serde_json::{self, Value};
+use serde_json::value::RawValue;
+
+use error::ApiError;
+use rpcconn::ClientError;
 use std::{error::Error as StdError, fmt};
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct RpcError {
     pub code: i32,
     pub message: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+/// The error codes of komodod, from bitcoind's `rpc/protocol.h`.
+///
+/// Several codes are shared by more than one error: komodod returns `VerifyError` for a transaction
+/// with missing inputs and `VerifyRejected` for one that is rejected by the mempool, with the reason
+/// in the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcErrorCode {
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    InternalError,
+    ParseError,
+
+    MiscError,
+    ForbiddenBySafeMode,
+    TypeError,
+    InvalidAddressOrKey,
+    OutOfMemory,
+    InvalidParameter,
+    DatabaseError,
+    DeserializationError,
+    VerifyError,
+    VerifyRejected,
+    VerifyAlreadyInChain,
+    /// komodod is still loading its block index or verifying blocks after it started.
+    InWarmup,
+
+    ClientNotConnected,
+    ClientInInitialDownload,
+    ClientNodeAlreadyAdded,
+    ClientNodeNotAdded,
+
+    WalletError,
+    WalletInsufficientFunds,
+    WalletInvalidAccountName,
+    WalletKeypoolRanOut,
+    WalletUnlockNeeded,
+    WalletPassphraseIncorrect,
+    WalletWrongEncState,
+    WalletEncryptionFailed,
+    WalletAlreadyUnlocked,
+
+    Unknown(i32),
+}
+
+impl RpcErrorCode {
+    pub fn code(self) -> i32 {
+        match self {
+            RpcErrorCode::InvalidRequest => -32600,
+            RpcErrorCode::MethodNotFound => -32601,
+            RpcErrorCode::InvalidParams => -32602,
+            RpcErrorCode::InternalError => -32603,
+            RpcErrorCode::ParseError => -32700,
+
+            RpcErrorCode::MiscError => -1,
+            RpcErrorCode::ForbiddenBySafeMode => -2,
+            RpcErrorCode::TypeError => -3,
+            RpcErrorCode::InvalidAddressOrKey => -5,
+            RpcErrorCode::OutOfMemory => -7,
+            RpcErrorCode::InvalidParameter => -8,
+            RpcErrorCode::DatabaseError => -20,
+            RpcErrorCode::DeserializationError => -22,
+            RpcErrorCode::VerifyError => -25,
+            RpcErrorCode::VerifyRejected => -26,
+            RpcErrorCode::VerifyAlreadyInChain => -27,
+            RpcErrorCode::InWarmup => -28,
+
+            RpcErrorCode::ClientNotConnected => -9,
+            RpcErrorCode::ClientInInitialDownload => -10,
+            RpcErrorCode::ClientNodeAlreadyAdded => -23,
+            RpcErrorCode::ClientNodeNotAdded => -24,
+
+            RpcErrorCode::WalletError => -4,
+            RpcErrorCode::WalletInsufficientFunds => -6,
+            RpcErrorCode::WalletInvalidAccountName => -11,
+            RpcErrorCode::WalletKeypoolRanOut => -12,
+            RpcErrorCode::WalletUnlockNeeded => -13,
+            RpcErrorCode::WalletPassphraseIncorrect => -14,
+            RpcErrorCode::WalletWrongEncState => -15,
+            RpcErrorCode::WalletEncryptionFailed => -16,
+            RpcErrorCode::WalletAlreadyUnlocked => -17,
+
+            RpcErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+impl From<i32> for RpcErrorCode {
+    fn from(code: i32) -> Self {
+        match code {
+            -32600 => RpcErrorCode::InvalidRequest,
+            -32601 => RpcErrorCode::MethodNotFound,
+            -32602 => RpcErrorCode::InvalidParams,
+            -32603 => RpcErrorCode::InternalError,
+            -32700 => RpcErrorCode::ParseError,
+
+            -1 => RpcErrorCode::MiscError,
+            -2 => RpcErrorCode::ForbiddenBySafeMode,
+            -3 => RpcErrorCode::TypeError,
+            -5 => RpcErrorCode::InvalidAddressOrKey,
+            -7 => RpcErrorCode::OutOfMemory,
+            -8 => RpcErrorCode::InvalidParameter,
+            -20 => RpcErrorCode::DatabaseError,
+            -22 => RpcErrorCode::DeserializationError,
+            -25 => RpcErrorCode::VerifyError,
+            -26 => RpcErrorCode::VerifyRejected,
+            -27 => RpcErrorCode::VerifyAlreadyInChain,
+            -28 => RpcErrorCode::InWarmup,
+
+            -9 => RpcErrorCode::ClientNotConnected,
+            -10 => RpcErrorCode::ClientInInitialDownload,
+            -23 => RpcErrorCode::ClientNodeAlreadyAdded,
+            -24 => RpcErrorCode::ClientNodeNotAdded,
+
+            -4 => RpcErrorCode::WalletError,
+            -6 => RpcErrorCode::WalletInsufficientFunds,
+            -11 => RpcErrorCode::WalletInvalidAccountName,
+            -12 => RpcErrorCode::WalletKeypoolRanOut,
+            -13 => RpcErrorCode::WalletUnlockNeeded,
+            -14 => RpcErrorCode::WalletPassphraseIncorrect,
+            -15 => RpcErrorCode::WalletWrongEncState,
+            -16 => RpcErrorCode::WalletEncryptionFailed,
+            -17 => RpcErrorCode::WalletAlreadyUnlocked,
+
+            code => RpcErrorCode::Unknown(code),
+        }
+    }
+}
+
+impl RpcError {
+    /// The known error `code` stands for.
+    pub fn error_code(&self) -> RpcErrorCode {
+        RpcErrorCode::from(self.code)
+    }
+
+    /// A transaction spends outputs that are spent or that komodod does not know of.
+    pub fn is_missing_inputs(&self) -> bool {
+        self.error_code() == RpcErrorCode::VerifyError && self.message.contains("Missing inputs")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct RpcResponse<R> {
-    pub id: String,
+    /// `None` for an error that komodod could not relate to a request, such as a parse error.
+    #[serde(default)]
+    pub id: Option<String>,
     pub result: Option<R>,
     pub error: Option<RpcError>,
 }
 
 impl<R> RpcResponse<R> {
-    pub fn into_result(self) -> StdResult<R, RpcError> {
+    /// A response without a result and without an error is a `ClientError::EmptyResponse`.
+    pub fn into_result(self) -> StdResult<R, ApiError> {
         match self {
             RpcResponse {
                 result: Some(result),
@@ -23,16 +173,41 @@ impl<R> RpcResponse<R> {
                 ..
             } => Ok(result),
             RpcResponse {
-                result: None,
                 error: Some(rpc_error),
                 ..
-            } => Err(rpc_error),
-            _ => Err(RpcError { code: 777, message: "Empty response".to_string()})
+            } => Err(ApiError::RPC(rpc_error)),
+            _ => Err(ApiError::Client(ClientError::EmptyResponse))
         }
     }
 
-    pub fn id(&self) -> &str {
-        &self.id
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl RpcResponse<Value> {
+    /// Deserializes the result into `R`. komodod answers RPCs that have nothing to return
+    /// with a `null` result, which deserializes into `()` or `None`.
+    pub fn into_typed_result<R: DeserializeOwned>(self) -> StdResult<R, ApiError> {
+        match self.error {
+            Some(rpc_error) => Err(ApiError::RPC(rpc_error)),
+            None => serde_json::from_value(self.result.unwrap_or(Value::Null))
+                .map_err(|err| ApiError::Client(ClientError::Json(err))),
+        }
+    }
+}
+
+impl RpcResponse<Box<RawValue>> {
+    /// Deserializes the result into `R` from its JSON text, which keeps amounts exact, see `Amount`.
+    /// A `null` result deserializes into `()` or `None`.
+    pub fn into_typed_result<R: DeserializeOwned>(self) -> StdResult<R, ApiError> {
+        let result = match (self.error, self.result) {
+            (Some(rpc_error), _) => return Err(ApiError::RPC(rpc_error)),
+            (None, Some(result)) => serde_json::from_str(result.get()),
+            (None, None) => serde_json::from_value(Value::Null),
+        };
+
+        result.map_err(|err| ApiError::Client(ClientError::Json(err)))
     }
 }
 
@@ -0,0 +1,139 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use error::ApiError;
+
+/// The wait before a retry is at most this many seconds, a little over 136 years, so that it fits in a `Duration`.
+const MAX_INTERVAL_SECS: f64 = u32::MAX as f64;
+
+/// The methods that only read, which can be sent again after komodod may have executed them.
+const READ_ONLY_METHODS: &[&str] = &[
+    "coinsupply", "decoderawtransaction", "decodescript", "getaddednodeinfo", "getaddressbalance",
+    "getaddressdeltas", "getaddressmempool", "getaddresstxids", "getaddressutxos", "getbalance", "getbestblockhash",
+    "getblock", "getblockchaininfo", "getblockcount", "getblockhash", "getblockheader", "getblocksubsidy",
+    "getchaintips", "getconnectioncount", "getdeprecationinfo", "getdifficulty", "getinfo", "getlocalsolps",
+    "getmempoolinfo", "getmininginfo", "getnettotals", "getnetworkhashps", "getnetworkinfo", "getnetworksolps",
+    "getpeerinfo", "getrawmempool", "getrawtransaction", "getreceivedbyaddress", "getsnapshot", "gettransaction",
+    "gettxout", "gettxoutsetinfo", "getunconfirmedbalance", "getwalletinfo", "listaddressgroupings", "listbanned",
+    "listlockunspent", "listreceivedbyaddress", "listsinceblock", "listtransactions", "listunspent", "validateaddress",
+    "z_getbalance", "z_getoperationstatus", "z_gettotalbalance", "z_listaddresses", "z_listoperationids",
+    "z_listreceivedbyaddress", "z_validateaddress",
+    "channelsinfo", "channelslist", "diceinfo", "dicelist", "faucetinfo", "minerids", "mytokenorders", "notaries",
+    "oraclesinfo", "oracleslist", "oraclessamples", "rewardsinfo", "rewardslist", "tokenbalance", "tokeninfo",
+    "tokenlist", "tokenorders",
+];
+
+/// `method` does not change anything, so it can be retried after an error for which
+/// `ApiError::is_retryable_if_read_only` is true.
+pub(crate) fn is_read_only(method: &str) -> bool {
+    READ_ONLY_METHODS.contains(&method)
+}
+
+/// How a `Client` retries requests that failed.
+///
+/// Any request is retried when `ApiError::is_retryable` is true for its error: komodod refused the connection,
+/// so it never got the request, or it is still starting up. Read-only requests, such as `getinfo` or
+/// `listunspent`, are also retried when `ApiError::is_retryable_if_read_only` is: they timed out, the connection
+/// failed after they were sent, or komodod answered with a HTTP 5xx status. Other requests, such as
+/// `sendtoaddress`, may have been executed then and are not sent again, and neither are batches that contain one.
+///
+/// The wait before each retry grows by `multiplier`, up to `max_interval`, and is made longer or shorter by
+/// a random part of up to `jitter` times itself, so that clients that failed at the same time do not retry
+/// at the same time. No retry is made once `max_elapsed_time` has passed since the first attempt.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use komodo_rpc_client::RetryPolicy;
+/// let policy = RetryPolicy {
+///     max_elapsed_time: Duration::from_secs(300),
+///     jitter: 0.0,
+///     ..RetryPolicy::default()
+/// };
+///
+/// assert_eq!(policy.interval(0), Duration::from_millis(500));
+/// assert_eq!(policy.interval(2), Duration::from_secs(2));
+/// assert_eq!(policy.interval(10), Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// The wait before the first retry.
+    pub initial_interval: Duration,
+    pub multiplier: f64,
+    pub max_interval: Duration,
+    pub max_elapsed_time: Duration,
+    /// Between 0 and 1.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    /// Retries for up to 2 minutes, starting after half a second.
+    fn default() -> Self {
+        RetryPolicy {
+            initial_interval: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_interval: Duration::from_secs(10),
+            max_elapsed_time: Duration::from_secs(120),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Does not retry. This is what a `Client` does unless it is given a policy.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_elapsed_time: Duration::from_secs(0),
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// The wait before retry `attempt`, counted from 0.
+    pub fn interval(&self, attempt: u32) -> Duration {
+        let interval = self.initial_interval.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let interval = interval.min(self.max_interval.as_secs_f64().min(MAX_INTERVAL_SECS));
+        let jitter = self.jitter.clamp(0.0, 1.0) * (2.0 * random() - 1.0);
+
+        let interval = interval * (1.0 + jitter);
+        // from a NaN jitter
+        if interval.is_nan() {
+            return Duration::from_secs(0);
+        }
+
+        Duration::from_secs_f64(interval.clamp(0.0, 2.0 * MAX_INTERVAL_SECS))
+    }
+
+    /// Calls `f` until it succeeds, fails with an error that is not retryable, or the next retry would
+    /// start after `max_elapsed_time`. `read_only` is whether what `f` sends only reads, see `is_read_only`.
+    pub(crate) fn retry<R, F>(&self, read_only: bool, mut f: F) -> Result<R, ApiError>
+        where
+            F: FnMut() -> Result<R, ApiError>,
+    {
+        let start = Instant::now();
+        let mut attempt = 0;
+
+        loop {
+            match f() {
+                Err(err) => {
+                    let interval = self.interval(attempt);
+                    let retryable = err.is_retryable() || read_only && err.is_retryable_if_read_only();
+                    if !retryable || start.elapsed() + interval > self.max_elapsed_time {
+                        return Err(err);
+                    }
+
+                    thread::sleep(interval);
+                    attempt += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A number in `[0, 1)`. `RandomState` is seeded randomly for every thread and differs for every call.
+fn random() -> f64 {
+    let hasher = RandomState::new().build_hasher();
+
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
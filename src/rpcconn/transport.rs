@@ -0,0 +1,23 @@
+use serde_json::{self, Value};
+
+use error::ApiError;
+use rpcconn::ClientError;
+
+/// Sends a JSON-RPC body to a komodod instance and returns the JSON body of its response.
+///
+/// The body is either a single request object or, for a batch, an array of request objects.
+/// `RpcClient` implements this over HTTP; `MockTransport` replays canned responses so a `Client`
+/// can be used without a running komodod.
+pub trait Transport {
+    fn send(&self, body: &Value) -> Result<Value, ApiError>;
+
+    /// Sends the JSON text `body` and returns the JSON text of the response. A `Client` sends its requests
+    /// through this, since text keeps amounts exact where a `Value` holds them as an `f64`.
+    ///
+    /// Defaults to `send`. `RpcClient` sends the text as it is.
+    fn send_text(&self, body: &str) -> Result<String, ApiError> {
+        let body: Value = serde_json::from_str(body).map_err(ClientError::Json)?;
+
+        Ok(self.send(&body)?.to_string())
+    }
+}
@@ -0,0 +1,408 @@
+use bitcoin::util::base58;
+use bitcoin::util::hash::Hash160;
+use secp256k1::{Message, Secp256k1};
+use secp256k1::key::{PublicKey, SecretKey};
+use std::convert::TryFrom;
+use std_hex;
+
+use tx::fee::{FeePolicy, InputKind, OutputKind, TransactionSize};
+use tx::select::Selection;
+use tx::sighash::{signature_hash, SIGHASH_ALL};
+use tx::transaction::*;
+use types::arguments::address::{Address, AddressKind};
+use types::{AddressUtxo, AddressUtxos, PrivateKey, SignedRawTransaction, Unspent};
+use Amount;
+use ApiError;
+use Txid;
+
+/// How many blocks after the block it is meant for a transaction of komodod expires, its default `-txexpirydelta`.
+pub const DEFAULT_TX_EXPIRY_DELTA: u32 = 20;
+
+/// The version byte of a KMD private key in WIF.
+const WIF_VERSION: u8 = 188;
+
+const OP_DUP: u8 = 0x76;
+const OP_HASH160: u8 = 0xa9;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_CHECKSIG: u8 = 0xac;
+
+/// Builds and signs a transparent Sapling (v4) transaction, without a daemon.
+///
+/// Inputs are P2PKH or P2PK outputs of the key that signs. The fee is whatever the inputs hold more than
+/// the outputs, so add an output for the change.
+///
+/// ```
+/// # use komodo_rpc_client::{Amount, PrivateKey, TransactionBuilder, Txid};
+/// # use komodo_rpc_client::arguments::address::Address;
+/// let key = PrivateKey::from_string("UuN1MBSLoHvCxznDjPQw2naC2KEJ3byRCcf6nfJv7JD8UaMYABYp".to_string()).unwrap();
+/// // the P2PKH script of the key's address, RXvbuoZCZ1GpTNTfroFABvFUf6ce4s1J4L
+/// let script = "76a914f8660d5f2b19c8e28981290efa1143c9021d0ced88ac";
+/// let txid = Txid::from_hex("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a").unwrap();
+/// let to = Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap();
+///
+/// let signed = TransactionBuilder::new()
+///     .add_input(txid, 0, script, Amount::ONE_COIN)
+///     .add_output(&to, Amount::ONE_COIN - Amount::from_sat(10_000))
+///     .set_expiry_height(1_500_000)
+///     .sign(&key)
+///     .unwrap();
+///
+/// assert!(signed.complete);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TransactionBuilder {
+    inputs: Vec<Input>,
+    outputs: Vec<(Address, Amount)>,
+    lock_time: Option<u32>,
+    expiry_height: u32,
+    rewards: Amount,
+}
+
+#[derive(Debug, Clone)]
+struct Input {
+    txid: Txid,
+    vout: u32,
+    script_pubkey: String,
+    value: Amount,
+}
+
+impl TransactionBuilder {
+    pub fn new() -> Self {
+        TransactionBuilder::default()
+    }
+
+    /// `script_pubkey` is the hex of the script of the output being spent.
+    pub fn add_input(&mut self, txid: Txid, vout: u32, script_pubkey: &str, value: Amount) -> &mut Self {
+        self.inputs.push(Input {
+            txid,
+            vout,
+            script_pubkey: script_pubkey.to_string(),
+            value,
+        });
+
+        self
+    }
+
+    /// Fails for a UTXO of more than `i64::MAX` satoshis, which no `Amount` holds.
+    pub fn add_utxo(&mut self, utxo: &AddressUtxo) -> Result<&mut Self, ApiError> {
+        let satoshis = i64::try_from(utxo.satoshis).map_err(|_| ApiError::Other(format!(
+            "UTXO {}:{} holds {} satoshis, more than an amount can hold",
+            utxo.txid, utxo.output_index, utxo.satoshis
+        )))?;
+        self.inputs.push(Input {
+            txid: utxo.txid,
+            vout: utxo.output_index,
+            script_pubkey: utxo.script.clone(),
+            value: Amount::from_sat(satoshis),
+        });
+
+        Ok(self)
+    }
+
+    /// Fails, without adding any of them, if one of the UTXOs does, see `add_utxo`.
+    pub fn add_utxos(&mut self, utxos: &AddressUtxos) -> Result<&mut Self, ApiError> {
+        let len = self.inputs.len();
+        for utxo in &utxos.0 {
+            if let Err(e) = self.add_utxo(utxo) {
+                self.inputs.truncate(len);
+                return Err(e);
+            }
+        }
+
+        Ok(self)
+    }
+
+    pub fn add_unspent(&mut self, unspent: &Unspent) -> &mut Self {
+        let txid = unspent.txid;
+
+        self.add_input(txid, unspent.vout, &unspent.script_pub_key, unspent.amount)
+    }
+
+    /// Spends the UTXOs a `CoinSelector` selected. Their change still needs an output.
+    pub fn add_selection(&mut self, selection: &Selection) -> &mut Self {
+        for utxo in &selection.inputs {
+            self.inputs.push(Input {
+                txid: utxo.txid,
+                vout: utxo.vout,
+                script_pubkey: utxo.script_pub_key.clone(),
+                value: utxo.value,
+            });
+        }
+
+        self
+    }
+
+    /// Only transparent addresses can be paid to.
+    pub fn add_output(&mut self, address: &Address, amount: Amount) -> &mut Self {
+        self.outputs.push((address.clone(), amount));
+
+        self
+    }
+
+    /// Defaults to the current time - 777 seconds, which lets the inputs claim their KMD rewards.
+    pub fn set_lock_time(&mut self, lock_time: u32) -> &mut Self {
+        self.lock_time = Some(lock_time);
+
+        self
+    }
+
+    /// The KMD rewards the inputs claim, which the outputs can spend on top of the inputs. Defaults to 0.
+    ///
+    /// See `komodo_interest` for what an input may claim; komodod rejects a transaction that claims more.
+    pub fn set_rewards(&mut self, rewards: Amount) -> &mut Self {
+        self.rewards = rewards;
+
+        self
+    }
+
+    /// The last height the transaction can be mined at.
+    ///
+    /// Defaults to 0, which means the transaction never expires. This is not what komodod does: it lets its
+    /// transactions expire `DEFAULT_TX_EXPIRY_DELTA` blocks after the next block, see `expire_after`.
+    /// A transaction that never expires stays valid, and can be mined, until its inputs are spent otherwise.
+    pub fn set_expiry_height(&mut self, expiry_height: u32) -> &mut Self {
+        self.expiry_height = expiry_height;
+
+        self
+    }
+
+    /// Lets the transaction expire as one of komodod would: `DEFAULT_TX_EXPIRY_DELTA` blocks after
+    /// `next_height`, the height of the next block (the current block count + 1).
+    pub fn expire_after(&mut self, next_height: u32) -> &mut Self {
+        self.set_expiry_height(next_height.saturating_add(DEFAULT_TX_EXPIRY_DELTA))
+    }
+
+    /// The size of the transaction once it is signed, see `TransactionSize`. Fails for inputs that do not
+    /// spend a P2PKH or P2PK script, see `InputKind::from_script_pubkey`.
+    pub fn estimated_size(&self) -> Result<usize, ApiError> {
+        let mut size = TransactionSize::new();
+        for input in &self.inputs {
+            let kind = InputKind::from_script_pubkey(&input.script_pubkey).ok_or_else(|| ApiError::Other(format!(
+                "cannot estimate the size of the input spending {}:{} with script {}",
+                input.txid, input.vout, input.script_pubkey
+            )))?;
+            size.add_input(kind);
+        }
+        for (address, _) in &self.outputs {
+            size.add_output(output_kind(address)?);
+        }
+
+        Ok(size.size())
+    }
+
+    /// Pays what the inputs and rewards hold more than the outputs and the fee `policy` asks for to `address`.
+    /// Change below the `dust_threshold` of `policy` goes to the fee.
+    pub fn add_change(&mut self, address: &Address, policy: &FeePolicy) -> Result<&mut Self, ApiError> {
+        let available = self.total_in()?.checked_sub(self.total_out()?)
+            .ok_or_else(|| ApiError::Other("inputs minus outputs overflowed".to_string()))?;
+
+        let size = self.estimated_size()?;
+        let fee_overflowed = || ApiError::Other(format!("fee of {:?} overflowed", policy));
+        let fee = policy.checked_fee(size).ok_or_else(fee_overflowed)?;
+        let fee_with_change = policy.checked_fee(size + output_kind(address)?.size()).ok_or_else(fee_overflowed)?;
+
+        let change = available.checked_sub(fee_with_change)
+            .ok_or_else(|| ApiError::Other(format!("inputs leave {} for a fee of {}", available, fee_with_change)))?;
+        if change >= policy.dust_threshold {
+            self.outputs.push((address.clone(), change));
+        } else if available < fee {
+            return Err(ApiError::Other(format!("inputs leave {} for a fee of {}", available, fee)));
+        }
+
+        Ok(self)
+    }
+
+    /// The transaction with empty `scriptSig`s.
+    pub fn build(&self) -> Result<KomodoTransaction, ApiError> {
+        if self.inputs.is_empty() {
+            return Err(ApiError::Other("transaction has no inputs".to_string()));
+        }
+        if self.outputs.is_empty() {
+            return Err(ApiError::Other("transaction has no outputs".to_string()));
+        }
+
+        let mut inputs = vec![];
+        for input in &self.inputs {
+            inputs.push(TxIn {
+                prevout: OutPoint {
+                    txid: input.txid,
+                    vout: input.vout,
+                },
+                script_sig: vec![],
+                sequence: SEQUENCE_LOCKTIME,
+            });
+        }
+
+        let mut outputs = vec![];
+        for (address, amount) in &self.outputs {
+            if amount.is_negative() {
+                return Err(ApiError::Other(format!("negative amount for output to {}", address)));
+            }
+            outputs.push(TxOut {
+                value: *amount,
+                script_pubkey: script_pubkey(address)?,
+            });
+        }
+
+        if self.rewards.is_negative() {
+            return Err(ApiError::Other(format!("negative rewards: {}", self.rewards)));
+        }
+        let total_in = self.total_in()?;
+        let total_out = self.total_out()?;
+        if total_out > total_in {
+            return Err(ApiError::Other(format!("outputs ({}) exceed inputs ({})", total_out, total_in)));
+        }
+
+        let lock_time = match self.lock_time {
+            Some(lock_time) => lock_time,
+            None => komodo_lock_time()?,
+        };
+
+        Ok(KomodoTransaction {
+            inputs,
+            outputs,
+            lock_time,
+            expiry_height: self.expiry_height,
+            ..KomodoTransaction::default()
+        })
+    }
+
+    /// The inputs plus the rewards.
+    fn total_in(&self) -> Result<Amount, ApiError> {
+        let mut total = self.rewards;
+        for input in &self.inputs {
+            total = total.checked_add(input.value)
+                .ok_or_else(|| ApiError::Other("sum of inputs overflowed".to_string()))?;
+        }
+
+        Ok(total)
+    }
+
+    fn total_out(&self) -> Result<Amount, ApiError> {
+        let mut total = Amount::ZERO;
+        for &(_, amount) in &self.outputs {
+            total = total.checked_add(amount)
+                .ok_or_else(|| ApiError::Other("sum of outputs overflowed".to_string()))?;
+        }
+
+        Ok(total)
+    }
+
+    /// Builds the transaction and signs every input with `key`, a WIF private key.
+    /// The result can be passed to `send_raw_transaction` as is.
+    pub fn sign(&self, key: &PrivateKey) -> Result<SignedRawTransaction, ApiError> {
+        let mut tx = self.build()?;
+
+        let secp = Secp256k1::new();
+        let (secret_key, compressed) = decode_wif(&secp, &key.0)?;
+        let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+        let public_key = if compressed {
+            public_key.serialize().to_vec()
+        } else {
+            public_key.serialize_uncompressed().to_vec()
+        };
+        let pubkey_hash = Hash160::from_data(&public_key);
+
+        let mut script_sigs = vec![];
+        for (i, input) in self.inputs.iter().enumerate() {
+            let script_code = std_hex::decode(&input.script_pubkey)
+                .map_err(|e| ApiError::Other(format!("invalid script of input {}: {}", i, e)))?;
+
+            let sighash = signature_hash(&tx, i, &script_code, input.value, SAPLING_BRANCH_ID);
+            let message = Message::from_slice(&sighash)
+                .map_err(|e| ApiError::Other(format!("invalid signature hash: {}", e)))?;
+            let mut signature = secp.sign(&message, &secret_key).serialize_der(&secp);
+            signature.push(SIGHASH_ALL as u8);
+
+            let mut script_sig = vec![];
+            if is_p2pkh(&script_code, &pubkey_hash[..]) {
+                push_data(&mut script_sig, &signature);
+                push_data(&mut script_sig, &public_key);
+            } else if is_p2pk(&script_code, &public_key) {
+                push_data(&mut script_sig, &signature);
+            } else {
+                return Err(ApiError::Other(format!("input {} can not be spent with this private key", i)));
+            }
+
+            script_sigs.push(script_sig);
+        }
+
+        for (input, script_sig) in tx.inputs.iter_mut().zip(script_sigs) {
+            input.script_sig = script_sig;
+        }
+
+        Ok(SignedRawTransaction {
+            hex: tx.to_hex(),
+            complete: true,
+            errors: None,
+        })
+    }
+}
+
+fn decode_wif(secp: &Secp256k1<::secp256k1::All>, wif: &str) -> Result<(SecretKey, bool), ApiError> {
+    let data = base58::from_check(wif)
+        .map_err(|e| ApiError::Other(format!("invalid private key: {}", e)))?;
+
+    let compressed = match data.len() {
+        33 => false,
+        34 if data[33] == 1 => true,
+        _ => return Err(ApiError::Other("invalid private key: incorrect length".to_string())),
+    };
+    if data[0] != WIF_VERSION {
+        return Err(ApiError::Other(format!("invalid private key: unknown version byte {}", data[0])));
+    }
+
+    let secret_key = SecretKey::from_slice(secp, &data[1..33])
+        .map_err(|e| ApiError::Other(format!("invalid private key: {}", e)))?;
+
+    Ok((secret_key, compressed))
+}
+
+fn script_pubkey(address: &Address) -> Result<Vec<u8>, ApiError> {
+    let hash = address.payload();
+
+    match address.kind() {
+        Some(AddressKind::PubkeyHash) => {
+            let mut script = vec![OP_DUP, OP_HASH160];
+            push_data(&mut script, hash);
+            script.extend_from_slice(&[OP_EQUALVERIFY, OP_CHECKSIG]);
+            Ok(script)
+        },
+        Some(AddressKind::ScriptHash) => {
+            let mut script = vec![OP_HASH160];
+            push_data(&mut script, hash);
+            script.push(OP_EQUAL);
+            Ok(script)
+        },
+        _ => Err(ApiError::Other(format!("can not pay to {}, only transparent addresses are supported", address))),
+    }
+}
+
+fn output_kind(address: &Address) -> Result<OutputKind, ApiError> {
+    match address.kind() {
+        Some(AddressKind::PubkeyHash) => Ok(OutputKind::P2PKH),
+        Some(AddressKind::ScriptHash) => Ok(OutputKind::P2SH),
+        _ => Err(ApiError::Other(format!("can not pay to {}, only transparent addresses are supported", address))),
+    }
+}
+
+fn is_p2pkh(script: &[u8], pubkey_hash: &[u8]) -> bool {
+    script.len() == 25
+        && script[..3] == [OP_DUP, OP_HASH160, 20]
+        && &script[3..23] == pubkey_hash
+        && script[23..] == [OP_EQUALVERIFY, OP_CHECKSIG]
+}
+
+fn is_p2pk(script: &[u8], public_key: &[u8]) -> bool {
+    script.len() == public_key.len() + 2
+        && script[0] as usize == public_key.len()
+        && &script[1..script.len() - 1] == public_key
+        && script[script.len() - 1] == OP_CHECKSIG
+}
+
+/// Pushes of up to 75 bytes, which covers hashes, public keys and signatures.
+fn push_data(script: &mut Vec<u8>, data: &[u8]) {
+    script.push(data.len() as u8);
+    script.extend_from_slice(data);
+}
@@ -0,0 +1,133 @@
+use ApiError;
+
+/// The largest length komodod accepts for a vector in a serialized transaction.
+const MAX_SIZE: u64 = 0x0200_0000;
+
+pub(crate) fn write_u32(buf: &mut Vec<u8>, n: u32) {
+    for i in 0..4 {
+        buf.push((n >> (8 * i)) as u8);
+    }
+}
+
+pub(crate) fn write_u64(buf: &mut Vec<u8>, n: u64) {
+    for i in 0..8 {
+        buf.push((n >> (8 * i)) as u8);
+    }
+}
+
+pub(crate) fn write_i64(buf: &mut Vec<u8>, n: i64) {
+    write_u64(buf, n as u64)
+}
+
+pub(crate) fn write_compact_size(buf: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0xFC => buf.push(n as u8),
+        0xFD..=0xFFFF => {
+            buf.push(0xFD);
+            buf.push(n as u8);
+            buf.push((n >> 8) as u8);
+        },
+        0x1_0000..=0xFFFF_FFFF => {
+            buf.push(0xFE);
+            write_u32(buf, n as u32);
+        },
+        _ => {
+            buf.push(0xFF);
+            write_u64(buf, n);
+        }
+    }
+}
+
+/// Writes `bytes` prefixed with their length, as scripts are serialized.
+pub(crate) fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_compact_size(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Reads the consensus encoding of a transaction.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
+    }
+
+    pub(crate) fn read_slice(&mut self, len: usize) -> Result<&'a [u8], ApiError> {
+        if self.data.len() - self.pos < len {
+            return Err(ApiError::Other(format!("invalid transaction: unexpected end of data at byte {}", self.pos)));
+        }
+
+        let slice = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+
+        Ok(slice)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, ApiError> {
+        Ok(self.read_slice(1)?[0])
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, ApiError> {
+        let bytes = self.read_slice(4)?;
+        let mut n = 0u32;
+        for (i, byte) in bytes.iter().enumerate() {
+            n |= u32::from(*byte) << (8 * i);
+        }
+
+        Ok(n)
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, ApiError> {
+        let bytes = self.read_slice(8)?;
+        let mut n = 0u64;
+        for (i, byte) in bytes.iter().enumerate() {
+            n |= u64::from(*byte) << (8 * i);
+        }
+
+        Ok(n)
+    }
+
+    pub(crate) fn read_i64(&mut self) -> Result<i64, ApiError> {
+        Ok(self.read_u64()? as i64)
+    }
+
+    /// Like komodod, rejects sizes that are not encoded in as few bytes as possible.
+    pub(crate) fn read_compact_size(&mut self) -> Result<u64, ApiError> {
+        let (n, min) = match self.read_u8()? {
+            0xFD => (u64::from(self.read_u8()?) | u64::from(self.read_u8()?) << 8, 0xFD),
+            0xFE => (u64::from(self.read_u32()?), 0x1_0000),
+            0xFF => (self.read_u64()?, 0x1_0000_0000),
+            n => (u64::from(n), 0),
+        };
+
+        if n < min {
+            return Err(ApiError::Other(format!("invalid transaction: non-canonical size at byte {}", self.pos)));
+        }
+        if n > MAX_SIZE {
+            return Err(ApiError::Other(format!("invalid transaction: size {} too large", n)));
+        }
+
+        Ok(n)
+    }
+
+    /// Reads bytes prefixed with their length, such as a script.
+    pub(crate) fn read_bytes(&mut self) -> Result<Vec<u8>, ApiError> {
+        let len = self.read_compact_size()? as usize;
+
+        Ok(self.read_slice(len)?.to_vec())
+    }
+
+    /// Fills `out`, for fields of a fixed size.
+    pub(crate) fn read_into(&mut self, out: &mut [u8]) -> Result<(), ApiError> {
+        out.copy_from_slice(self.read_slice(out.len())?);
+
+        Ok(())
+    }
+}
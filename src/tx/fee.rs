@@ -0,0 +1,310 @@
+use std::cmp;
+use std::convert::TryFrom;
+
+use types::NetworkInfo;
+use Amount;
+
+/// The fee komodod's wallet pays per transaction, and the minimum this crate pays.
+pub const DEFAULT_FEE: Amount = Amount::from_sat(10_000);
+/// komodod's default `-minrelaytxfee`, in satoshis per 1000 bytes.
+pub const DEFAULT_RELAY_FEE: Amount = Amount::from_sat(100);
+/// komodod's dust threshold of a P2PKH output at `DEFAULT_RELAY_FEE`, see `FeePolicy::dust_threshold`.
+pub const DEFAULT_DUST_THRESHOLD: Amount = Amount::from_sat(54);
+
+/// A DER signature of 71 bytes, the most common length, and its sighash type.
+const SIGNATURE_SIZE: usize = 72;
+const COMPRESSED_PUBKEY_SIZE: usize = 33;
+const UNCOMPRESSED_PUBKEY_SIZE: usize = 65;
+/// A Sapling spend description without its signature, plus the 64 byte `spendAuthSig`.
+const SHIELDED_SPEND_SIZE: usize = 384;
+const SHIELDED_OUTPUT_SIZE: usize = 948;
+const BINDING_SIG_SIZE: usize = 64;
+
+/// A Komodo CC fulfillment of one secp256k1 signature and an eval code, as `FinalizeCCTx` writes it:
+/// a threshold of an eval code and a secp256k1 fulfillment, followed by the sighash type.
+pub const CC_FULFILLMENT_SIZE: usize = 115;
+/// The condition of a 1-of-1 CC output with an eval code, followed by `OP_CHECKCRYPTOCONDITION`.
+pub const CC_CONDITION_SIZE: usize = 47;
+
+/// What an input spends, which determines the size of its `scriptSig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// Pay to public key hash, signed by a compressed or uncompressed key.
+    P2PKH { compressed: bool },
+    /// Pay to public key, such as coinbase outputs.
+    P2PK,
+    /// Pay to script hash of an `m`-of-`n` multisig script with compressed keys.
+    P2SHMultisig { required: usize, keys: usize },
+    /// A Komodo CC output, spent with a fulfillment of `fulfillment_size` bytes. `CC_FULFILLMENT_SIZE` is
+    /// the size of the usual single signature fulfillment.
+    CryptoCondition { fulfillment_size: usize },
+}
+
+/// What an output pays to, which determines the size of its `scriptPubKey`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputKind {
+    P2PKH,
+    P2SH,
+    /// Pay to a compressed public key.
+    P2PK,
+    /// A Komodo CC output with a condition of `condition_size` bytes, see `CC_CONDITION_SIZE`.
+    CryptoCondition { condition_size: usize },
+    /// An `OP_RETURN` output with `data_size` bytes of data.
+    OpReturn { data_size: usize },
+}
+
+impl InputKind {
+    /// The kind of input from the hex of the script of the output it spends, if that is a P2PKH or P2PK script.
+    /// A P2PKH input is assumed to be signed by a compressed key, as komodod's wallet does.
+    ///
+    /// Other scripts, such as P2SH and CC scripts, do not tell what it takes to spend them, so this returns `None`
+    /// and the caller has to know the kind.
+    pub fn from_script_pubkey(script_pubkey: &str) -> Option<InputKind> {
+        let script = script_pubkey.to_ascii_lowercase();
+        let is_hex = script.bytes().all(|byte| byte.is_ascii_hexdigit());
+        let p2pkh = script.len() == 50 && script.starts_with("76a914") && script.ends_with("88ac");
+        let p2pk = (script.len() == 70 && script.starts_with("21") || script.len() == 134 && script.starts_with("41"))
+            && script.ends_with("ac");
+
+        if !is_hex {
+            None
+        } else if p2pkh {
+            Some(InputKind::P2PKH { compressed: true })
+        } else if p2pk {
+            Some(InputKind::P2PK)
+        } else {
+            None
+        }
+    }
+
+    fn script_sig_size(self) -> usize {
+        match self {
+            InputKind::P2PKH { compressed } => {
+                let pubkey = if compressed { COMPRESSED_PUBKEY_SIZE } else { UNCOMPRESSED_PUBKEY_SIZE };
+
+                push_size(SIGNATURE_SIZE) + push_size(pubkey)
+            },
+            InputKind::P2PK => push_size(SIGNATURE_SIZE),
+            InputKind::P2SHMultisig { required, keys } => {
+                // OP_m, the keys, OP_n and OP_CHECKMULTISIG
+                let redeem_script = 1 + keys * push_size(COMPRESSED_PUBKEY_SIZE) + 1 + 1;
+
+                // OP_0 for the off-by-one of OP_CHECKMULTISIG, the signatures and the redeem script
+                1 + required * push_size(SIGNATURE_SIZE) + push_size(redeem_script)
+            },
+            InputKind::CryptoCondition { fulfillment_size } => push_size(fulfillment_size),
+        }
+    }
+
+    /// The serialized size: outpoint, `scriptSig` and sequence.
+    pub fn size(self) -> usize {
+        let script_sig = self.script_sig_size();
+
+        32 + 4 + compact_size(script_sig) + script_sig + 4
+    }
+}
+
+impl OutputKind {
+    fn script_pubkey_size(self) -> usize {
+        match self {
+            OutputKind::P2PKH => 25,
+            OutputKind::P2SH => 23,
+            OutputKind::P2PK => push_size(COMPRESSED_PUBKEY_SIZE) + 1,
+            OutputKind::CryptoCondition { condition_size } => push_size(condition_size) + 1,
+            OutputKind::OpReturn { data_size } => 1 + push_size(data_size),
+        }
+    }
+
+    /// The serialized size: value and `scriptPubKey`.
+    pub fn size(self) -> usize {
+        let script_pubkey = self.script_pubkey_size();
+
+        8 + compact_size(script_pubkey) + script_pubkey
+    }
+}
+
+/// Calculates the serialized size of a Sapling (v4) transaction from what it spends and pays to.
+///
+/// ```
+/// # use komodo_rpc_client::tx::{InputKind, OutputKind, TransactionSize};
+/// let size = TransactionSize::new()
+///     .add_input(InputKind::P2PKH { compressed: true })
+///     .add_output(OutputKind::P2PKH)
+///     .add_output(OutputKind::P2PKH)
+///     .size();
+///
+/// assert_eq!(size, 245);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TransactionSize {
+    inputs: Vec<InputKind>,
+    outputs: Vec<OutputKind>,
+    shielded_spends: usize,
+    shielded_outputs: usize,
+}
+
+impl TransactionSize {
+    pub fn new() -> Self {
+        TransactionSize::default()
+    }
+
+    pub fn add_input(&mut self, input: InputKind) -> &mut Self {
+        self.inputs.push(input);
+
+        self
+    }
+
+    pub fn add_output(&mut self, output: OutputKind) -> &mut Self {
+        self.outputs.push(output);
+
+        self
+    }
+
+    /// Sapling notes spent.
+    pub fn add_shielded_spends(&mut self, spends: usize) -> &mut Self {
+        self.shielded_spends += spends;
+
+        self
+    }
+
+    /// Sapling notes created.
+    pub fn add_shielded_outputs(&mut self, outputs: usize) -> &mut Self {
+        self.shielded_outputs += outputs;
+
+        self
+    }
+
+    /// The size in bytes.
+    pub fn size(&self) -> usize {
+        // header, version group id, locktime, expiry height and value balance
+        let mut size = 4 + 4 + 4 + 4 + 8;
+
+        size += compact_size(self.inputs.len());
+        size += self.inputs.iter().map(|input| input.size()).sum::<usize>();
+        size += compact_size(self.outputs.len());
+        size += self.outputs.iter().map(|output| output.size()).sum::<usize>();
+
+        size += compact_size(self.shielded_spends) + self.shielded_spends * SHIELDED_SPEND_SIZE;
+        size += compact_size(self.shielded_outputs) + self.shielded_outputs * SHIELDED_OUTPUT_SIZE;
+        // no joinsplits
+        size += compact_size(0);
+        if self.shielded_spends + self.shielded_outputs > 0 {
+            size += BINDING_SIG_SIZE;
+        }
+
+        size
+    }
+}
+
+/// The fee a transaction pays: `min_fee`, or what `relay_fee` asks for its size if that is more.
+///
+/// komodod does not relay transactions that pay less than its relay fee, which is in satoshis per 1000 bytes
+/// and is reported as `relayfee` by `getnetworkinfo`. Its wallet pays 10000 satoshis regardless of size.
+///
+/// ```
+/// # use komodo_rpc_client::tx::FeePolicy;
+/// # use komodo_rpc_client::Amount;
+/// let policy = FeePolicy::default();
+/// assert_eq!(policy.fee(245), Amount::from_sat(10_000));
+///
+/// // a relay fee of 1000 satoshis per 1000 bytes asks for more for a 20000 byte transaction
+/// let policy = FeePolicy { relay_fee: Amount::from_sat(1_000), ..FeePolicy::default() };
+/// assert_eq!(policy.fee(20_000), Amount::from_sat(20_000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeePolicy {
+    pub min_fee: Amount,
+    pub relay_fee: Amount,
+    /// Change below this is added to the fee instead, since komodod does not relay outputs this small.
+    /// Defaults to `DEFAULT_DUST_THRESHOLD`; `FeePolicy::dust_threshold` derives it from a relay fee.
+    pub dust_threshold: Amount,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        FeePolicy {
+            min_fee: DEFAULT_FEE,
+            relay_fee: DEFAULT_RELAY_FEE,
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+        }
+    }
+}
+
+/// The default fee with the relay fee of the node, and the dust threshold that follows from it.
+impl From<&NetworkInfo> for FeePolicy {
+    fn from(info: &NetworkInfo) -> Self {
+        FeePolicy {
+            relay_fee: info.relayfee,
+            dust_threshold: FeePolicy::dust_threshold(info.relayfee),
+            ..FeePolicy::default()
+        }
+    }
+}
+
+impl FeePolicy {
+    /// Pays `fee_rate` satoshis per 1000 bytes, without a minimum. Change below `DEFAULT_DUST_THRESHOLD`
+    /// is added to the fee.
+    pub fn from_rate(fee_rate: Amount) -> Self {
+        FeePolicy {
+            min_fee: Amount::ZERO,
+            relay_fee: fee_rate,
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+        }
+    }
+
+    /// `GetDustThreshold` of a P2PKH output: 3 times the relay fee of the output and the input that spends it.
+    ///
+    /// ```
+    /// # use komodo_rpc_client::tx::{FeePolicy, DEFAULT_DUST_THRESHOLD, DEFAULT_RELAY_FEE};
+    /// # use komodo_rpc_client::Amount;
+    /// assert_eq!(FeePolicy::dust_threshold(DEFAULT_RELAY_FEE), DEFAULT_DUST_THRESHOLD);
+    /// // bitcoind's 546 satoshis at its relay fee of 1000 satoshis per 1000 bytes
+    /// assert_eq!(FeePolicy::dust_threshold(Amount::from_sat(1_000)), Amount::from_sat(546));
+    /// ```
+    pub fn dust_threshold(relay_fee: Amount) -> Amount {
+        // a P2PKH output and the 148 byte input that spends it with an uncompressed key
+        let size = OutputKind::P2PKH.size() + 148;
+
+        self::relay_fee(relay_fee, size).and_then(|fee| fee.checked_mul(3)).expect("dust threshold overflowed")
+    }
+
+    /// The fee of a transaction of `size` bytes.
+    ///
+    /// Panics if the relay fee times the size overflows, like `Amount` arithmetic. Use `checked_fee` where
+    /// the relay fee is not known to be sane.
+    pub fn fee(&self, size: usize) -> Amount {
+        self.checked_fee(size).expect("fee overflowed")
+    }
+
+    pub fn checked_fee(&self, size: usize) -> Option<Amount> {
+        relay_fee(self.relay_fee, size).map(|fee| cmp::max(self.min_fee, fee))
+    }
+}
+
+/// `CFeeRate::GetFee`: the fee rate times the size, but not less than the rate itself if that is positive.
+fn relay_fee(fee_rate: Amount, size: usize) -> Option<Amount> {
+    let fee = fee_rate.as_sat().checked_mul(i64::try_from(size).ok()?)? / 1000;
+
+    if fee == 0 && size != 0 && fee_rate.is_positive() {
+        Some(fee_rate)
+    } else {
+        Some(Amount::from_sat(fee))
+    }
+}
+
+/// A push of `size` bytes: the opcode, and `OP_PUSHDATA1` or `OP_PUSHDATA2` for longer data.
+fn push_size(size: usize) -> usize {
+    match size {
+        0..=75 => 1 + size,
+        76..=255 => 2 + size,
+        _ => 3 + size,
+    }
+}
+
+fn compact_size(n: usize) -> usize {
+    match n {
+        0..=0xfc => 1,
+        0xfd..=0xffff => 3,
+        _ => 5,
+    }
+}
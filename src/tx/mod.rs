@@ -0,0 +1,16 @@
+//! Encoding, decoding, building and signing transactions without a daemon, and selecting the UTXOs they spend.
+//!
+//! Only sending the resulting transaction (`send_raw_transaction`) needs a node, which means a transaction
+//! can be signed on a machine that is not connected to one.
+
+mod builder;
+mod encode;
+mod fee;
+mod select;
+mod sighash;
+mod transaction;
+
+pub use self::builder::{TransactionBuilder, DEFAULT_TX_EXPIRY_DELTA};
+pub use self::fee::*;
+pub use self::select::{CoinSelector, Selection, SelectionStrategy, Utxo};
+pub use self::transaction::*;
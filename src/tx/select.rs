@@ -0,0 +1,429 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+
+use tx::fee::{FeePolicy, InputKind, OutputKind, TransactionSize};
+use types::{AddressUtxo, AddressUtxos, Unspent};
+use Amount;
+use ApiError;
+use Txid;
+
+/// The number of branches `BranchAndBound` explores before it gives up, as in bitcoind.
+const BNB_MAX_TRIES: u32 = 100_000;
+
+/// How `CoinSelector` picks the UTXOs to spend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionStrategy {
+    /// The largest UTXOs first, which spends as few inputs as possible.
+    LargestFirst,
+    /// Searches for a set of UTXOs that pays the target and fee without change. When there is none, this
+    /// falls back to `LargestFirst`.
+    BranchAndBound,
+    /// The oldest UTXOs first. On KMD these have accrued the most rewards, which spending them claims.
+    OldestFirst,
+    /// Only spends the UTXOs of a single address, so the transaction does not link addresses together.
+    /// Of the addresses that can pay on their own, the one that needs the fewest inputs is used.
+    AvoidMixingAddresses,
+}
+
+/// A UTXO that can be selected, from `getaddressutxos` or `listunspent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Utxo {
+    pub txid: Txid,
+    pub vout: u32,
+    pub address: String,
+    /// Hex of the script of the output.
+    pub script_pub_key: String,
+    pub value: Amount,
+    /// Known for UTXOs from `getaddressutxos`.
+    pub height: Option<u64>,
+    /// Known for UTXOs from `listunspent`.
+    pub confirmations: Option<u64>,
+}
+
+impl Utxo {
+    /// The kind of input that spends the UTXO, if its script is a P2PKH or P2PK script.
+    pub fn input_kind(&self) -> Option<InputKind> {
+        InputKind::from_script_pubkey(&self.script_pub_key)
+    }
+}
+
+/// Fails for a UTXO of more than `i64::MAX` satoshis, which no `Amount` holds.
+impl TryFrom<&AddressUtxo> for Utxo {
+    type Error = ApiError;
+
+    fn try_from(utxo: &AddressUtxo) -> Result<Self, Self::Error> {
+        let satoshis = i64::try_from(utxo.satoshis).map_err(|_| ApiError::Other(format!(
+            "UTXO {}:{} holds {} satoshis, more than an amount can hold",
+            utxo.txid, utxo.output_index, utxo.satoshis
+        )))?;
+
+        Ok(Utxo {
+            txid: utxo.txid,
+            vout: utxo.output_index,
+            address: utxo.address.clone(),
+            script_pub_key: utxo.script.clone(),
+            value: Amount::from_sat(satoshis),
+            height: Some(utxo.height),
+            confirmations: None,
+        })
+    }
+}
+
+impl From<&Unspent> for Utxo {
+    fn from(unspent: &Unspent) -> Self {
+        Utxo {
+            txid: unspent.txid,
+            vout: unspent.vout,
+            address: unspent.address.to_string(),
+            script_pub_key: unspent.script_pub_key.clone(),
+            value: unspent.amount,
+            height: None,
+            confirmations: Some(unspent.rawconfirmations),
+        }
+    }
+}
+
+/// The UTXOs `CoinSelector` picked, and what is left of them after the target and fee.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    pub inputs: Vec<Utxo>,
+    /// The fee the transaction pays. Change that would be dust is included in it.
+    pub fee: Amount,
+    /// The value of the change output, or 0 when the transaction needs none.
+    pub change: Amount,
+}
+
+impl Selection {
+    /// The sum of the selected UTXOs.
+    pub fn total(&self) -> Amount {
+        self.inputs.iter().map(|utxo| utxo.value).sum()
+    }
+}
+
+/// Selects which UTXOs to spend for a payment.
+///
+/// The fee is a rate in satoshis per 1000 bytes, like komodod's `paytxfee` and `relayfee`, or a `FeePolicy`.
+/// Sizes are calculated with `TransactionSize` for the scripts of the UTXOs and P2PKH outputs. UTXOs of other
+/// scripts than P2PKH and P2PK can only be selected when their `InputKind` is given with `input_kind`.
+///
+/// ```
+/// # use komodo_rpc_client::{Amount, CoinSelector, SelectionStrategy, Txid};
+/// # use komodo_rpc_client::tx::Utxo;
+/// let utxo = |txid: u8, coins: i64, height: u64| Utxo {
+///     txid: Txid::from_bytes([txid; 32]),
+///     vout: 0,
+///     address: "RXvbuoZCZ1GpTNTfroFABvFUf6ce4s1J4L".to_string(),
+///     script_pub_key: "76a914f8660d5f2b19c8e28981290efa1143c9021d0ced88ac".to_string(),
+///     value: Amount::from_coins(coins).unwrap(),
+///     height: Some(height),
+///     confirmations: None,
+/// };
+/// let utxos = vec![utxo(0xaa, 5, 1_500_000), utxo(0xbb, 20, 1_600_000), utxo(0xcc, 8, 1_400_000)];
+///
+/// let selection = CoinSelector::new(utxos)
+///     .strategy(SelectionStrategy::OldestFirst)
+///     .select(Amount::from_coins(10).unwrap(), Amount::from_sat(1_000))
+///     .unwrap();
+///
+/// // 8 and 5 KMD, the oldest two
+/// assert_eq!(selection.inputs.len(), 2);
+/// // 2 inputs and 2 outputs are 393 bytes
+/// assert_eq!(selection.fee, Amount::from_sat(393));
+/// assert_eq!(selection.change, Amount::from_sat(299_999_607));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CoinSelector {
+    utxos: Vec<Utxo>,
+    strategy: SelectionStrategy,
+    outputs: usize,
+    input_kinds: HashMap<String, InputKind>,
+}
+
+impl TryFrom<&AddressUtxos> for CoinSelector {
+    type Error = ApiError;
+
+    fn try_from(utxos: &AddressUtxos) -> Result<Self, Self::Error> {
+        Ok(CoinSelector::new(utxos.0.iter().map(Utxo::try_from).collect::<Result<_, _>>()?))
+    }
+}
+
+/// Only the spendable UTXOs of a `listunspent` are used.
+impl From<&[Unspent]> for CoinSelector {
+    fn from(unspents: &[Unspent]) -> Self {
+        CoinSelector::new(unspents.iter().filter(|unspent| unspent.spendable).map(Utxo::from).collect())
+    }
+}
+
+impl CoinSelector {
+    pub fn new(utxos: Vec<Utxo>) -> Self {
+        CoinSelector {
+            utxos,
+            strategy: SelectionStrategy::LargestFirst,
+            outputs: 1,
+            input_kinds: HashMap::new(),
+        }
+    }
+
+    /// Defaults to `LargestFirst`.
+    pub fn strategy(&mut self, strategy: SelectionStrategy) -> &mut Self {
+        self.strategy = strategy;
+
+        self
+    }
+
+    /// The number of outputs the transaction pays to, not counting change. Defaults to 1.
+    pub fn outputs(&mut self, outputs: usize) -> &mut Self {
+        self.outputs = outputs;
+
+        self
+    }
+
+    /// UTXOs with the script `script_pub_key`, in hex, are spent by inputs of `kind`. This is needed for scripts
+    /// other than P2PKH and P2PK, such as P2SH and CC scripts.
+    pub fn input_kind(&mut self, script_pub_key: &str, kind: InputKind) -> &mut Self {
+        self.input_kinds.insert(script_pub_key.to_ascii_lowercase(), kind);
+
+        self
+    }
+
+    /// Selects UTXOs that pay `target` and a fee of `fee_rate` satoshis per 1000 bytes.
+    pub fn select(&self, target: Amount, fee_rate: Amount) -> Result<Selection, ApiError> {
+        self.select_with_policy(target, &FeePolicy::from_rate(fee_rate))
+    }
+
+    /// Selects UTXOs that pay `target` and the fee `policy` asks for, such as komodod's 10000 satoshis.
+    pub fn select_with_policy(&self, target: Amount, policy: &FeePolicy) -> Result<Selection, ApiError> {
+        if !target.is_positive() {
+            return Err(ApiError::Other(format!("invalid target: {}", target)));
+        }
+        if policy.min_fee.is_negative() || policy.relay_fee.is_negative() || policy.dust_threshold.is_negative() {
+            return Err(ApiError::Other(format!("invalid fee policy: {:?}", policy)));
+        }
+        if let Some(utxo) = self.utxos.iter().find(|utxo| self.kind_of(utxo).is_none()) {
+            return Err(ApiError::Other(format!(
+                "unknown input kind of {}:{} with script {}", utxo.txid, utxo.vout, utxo.script_pub_key
+            )));
+        }
+
+        let available = checked_sum(self.utxos.iter().map(|utxo| utxo.value))?;
+
+        let all: Vec<&Utxo> = self.utxos.iter().collect();
+        let selection = match self.strategy {
+            SelectionStrategy::LargestFirst => self.accumulate(largest_first(all), target, policy)?,
+            SelectionStrategy::OldestFirst => self.accumulate(oldest_first(all), target, policy)?,
+            SelectionStrategy::BranchAndBound => match self.branch_and_bound(&all, target, policy)? {
+                Some(selection) => Some(selection),
+                None => self.accumulate(largest_first(all), target, policy)?,
+            },
+            SelectionStrategy::AvoidMixingAddresses => self.single_address(all, target, policy)?,
+        };
+
+        selection.ok_or_else(|| {
+            match self.strategy {
+                SelectionStrategy::AvoidMixingAddresses => ApiError::Other(format!(
+                    "no single address can pay {} and its fee, {} available in total", target, available
+                )),
+                _ => ApiError::Other(format!("insufficient funds: {} available, {} and its fee needed", available, target)),
+            }
+        })
+    }
+
+    fn kind_of(&self, utxo: &Utxo) -> Option<InputKind> {
+        self.input_kinds.get(&utxo.script_pub_key.to_ascii_lowercase()).cloned().or_else(|| utxo.input_kind())
+    }
+
+    /// The input kind of a UTXO `select_with_policy` has checked.
+    fn checked_kind_of(&self, utxo: &Utxo) -> InputKind {
+        self.kind_of(utxo).expect("the input kinds of all UTXOs are checked before selecting")
+    }
+
+    /// The fee of a transaction that spends `inputs` and pays the outputs, plus a change output if `change`.
+    fn fee(&self, inputs: &[Utxo], change: bool, policy: &FeePolicy) -> Result<Amount, ApiError> {
+        let mut size = TransactionSize::new();
+        for utxo in inputs {
+            size.add_input(self.checked_kind_of(utxo));
+        }
+        let outputs = if change { self.outputs + 1 } else { self.outputs };
+        for _ in 0..outputs {
+            size.add_output(OutputKind::P2PKH);
+        }
+
+        policy.checked_fee(size.size()).ok_or_else(overflow)
+    }
+
+    /// Spends `utxos` in order until they pay the target and fee, with change if that is not dust.
+    fn accumulate(&self, utxos: Vec<&Utxo>, target: Amount, policy: &FeePolicy) -> Result<Option<Selection>, ApiError> {
+        let mut inputs = vec![];
+        let mut total = Amount::ZERO;
+
+        for utxo in utxos {
+            inputs.push(utxo.clone());
+            total = total.checked_add(utxo.value).ok_or_else(overflow)?;
+            let excess = total.checked_sub(target).ok_or_else(overflow)?;
+
+            let fee = self.fee(&inputs, true, policy)?;
+            let change = excess.checked_sub(fee).ok_or_else(overflow)?;
+            if change >= policy.dust_threshold {
+                return Ok(Some(Selection {
+                    fee,
+                    change,
+                    inputs,
+                }));
+            }
+            if excess >= self.fee(&inputs, false, policy)? {
+                return Ok(Some(Selection {
+                    fee: excess,
+                    change: Amount::ZERO,
+                    inputs,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Searches depth first for UTXOs whose value after their own fee pays the target and the fee of the rest
+    /// of the transaction exactly, or with less excess than a change output would cost. The excess goes to the fee.
+    fn branch_and_bound(&self, utxos: &[&Utxo], target: Amount, policy: &FeePolicy) -> Result<Option<Selection>, ApiError> {
+        let rate_fee = |size: usize| policy.relay_fee.as_sat().checked_mul(size as i64).map(|fee| fee / 1000).ok_or_else(overflow);
+
+        let mut candidates: Vec<(&Utxo, i64)> = vec![];
+        for utxo in utxos {
+            let effective_value = utxo.value.as_sat().checked_sub(rate_fee(self.checked_kind_of(utxo).size())?)
+                .ok_or_else(overflow)?;
+            if effective_value > 0 {
+                candidates.push((*utxo, effective_value));
+            }
+        }
+        candidates.sort_by_key(|&(_, effective_value)| Reverse(effective_value));
+
+        let values: Vec<i64> = candidates.iter().map(|&(_, effective_value)| effective_value).collect();
+        let search_target = target.checked_add(self.fee(&[], false, policy)?).ok_or_else(overflow)?.as_sat();
+        // what a change output costs now and when it is spent later
+        let cost_of_change = rate_fee(OutputKind::P2PKH.size() + InputKind::P2PKH { compressed: true }.size())?;
+        let mut search = BranchAndBound {
+            values: &values,
+            target: search_target,
+            // a bound past `i64::MAX` bounds nothing
+            max_total: search_target.saturating_add(cost_of_change),
+            tries: BNB_MAX_TRIES,
+            selected: vec![],
+            best: None,
+        };
+        // the values are positive, so no sum of some of them overflows if this does not
+        let remaining = checked_sum(values.iter().map(|&value| Amount::from_sat(value)))?.as_sat();
+        search.explore(0, 0, remaining);
+
+        let best = match search.best {
+            Some((_, best)) => best,
+            None => return Ok(None),
+        };
+        let inputs: Vec<Utxo> = best.iter().map(|&i| candidates[i].0.clone()).collect();
+        let excess = checked_sum(inputs.iter().map(|utxo| utxo.value))?.checked_sub(target).ok_or_else(overflow)?;
+
+        // the effective values round the fee of each input down
+        if excess < self.fee(&inputs, false, policy)? {
+            return Ok(None);
+        }
+
+        Ok(Some(Selection {
+            fee: excess,
+            change: Amount::ZERO,
+            inputs,
+        }))
+    }
+
+    /// Selects largest first from the UTXOs of each address on its own, and keeps the selection with the fewest
+    /// inputs, then the least change.
+    fn single_address(&self, utxos: Vec<&Utxo>, target: Amount, policy: &FeePolicy) -> Result<Option<Selection>, ApiError> {
+        let mut by_address: BTreeMap<&str, Vec<&Utxo>> = BTreeMap::new();
+        for utxo in utxos {
+            by_address.entry(&utxo.address).or_default().push(utxo);
+        }
+
+        let mut selections = vec![];
+        for utxos in by_address.into_values() {
+            selections.extend(self.accumulate(largest_first(utxos), target, policy)?);
+        }
+
+        Ok(selections.into_iter().min_by(|a, b| a.inputs.len().cmp(&b.inputs.len()).then(a.change.cmp(&b.change))))
+    }
+}
+
+struct BranchAndBound<'a> {
+    /// Effective values, largest first.
+    values: &'a [i64],
+    target: i64,
+    /// The target plus the cost of change, past which a selection would rather pay change.
+    max_total: i64,
+    tries: u32,
+    selected: Vec<usize>,
+    /// The excess over the target and the indexes of the best selection so far.
+    best: Option<(i64, Vec<usize>)>,
+}
+
+impl<'a> BranchAndBound<'a> {
+    /// `remaining` is the sum of the values from `index` on.
+    fn explore(&mut self, index: usize, total: i64, remaining: i64) {
+        if self.tries == 0 || total > self.max_total || total + remaining < self.target {
+            return;
+        }
+        self.tries -= 1;
+
+        if total >= self.target {
+            let excess = total - self.target;
+            let better = match self.best {
+                Some((best, _)) => excess < best,
+                None => true,
+            };
+            if better {
+                self.best = Some((excess, self.selected.clone()));
+            }
+            if excess == 0 {
+                self.tries = 0;
+            }
+            return;
+        }
+        if index == self.values.len() {
+            return;
+        }
+
+        let value = self.values[index];
+        self.selected.push(index);
+        self.explore(index + 1, total + value, remaining - value);
+        self.selected.pop();
+        self.explore(index + 1, total, remaining - value);
+    }
+}
+
+fn checked_sum<I: Iterator<Item = Amount>>(amounts: I) -> Result<Amount, ApiError> {
+    let mut sum = Amount::ZERO;
+    for amount in amounts {
+        sum = sum.checked_add(amount).ok_or_else(overflow)?;
+    }
+
+    Ok(sum)
+}
+
+fn overflow() -> ApiError {
+    ApiError::Other("amounts of the UTXOs, target or fee overflowed".to_string())
+}
+
+fn largest_first(mut utxos: Vec<&Utxo>) -> Vec<&Utxo> {
+    utxos.sort_by_key(|utxo| Reverse(utxo.value));
+
+    utxos
+}
+
+/// Lowest height first for UTXOs from `getaddressutxos`, most confirmations first for those from `listunspent`.
+fn oldest_first(mut utxos: Vec<&Utxo>) -> Vec<&Utxo> {
+    utxos.sort_by(|a, b| match (a.height, b.height) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => b.confirmations.cmp(&a.confirmations),
+    });
+
+    utxos
+}
@@ -0,0 +1,94 @@
+use blake2b_simd::Params;
+
+use Amount;
+use tx::encode::*;
+use tx::transaction::*;
+
+pub(crate) const SIGHASH_ALL: u32 = 1;
+
+fn blake2b_256(personal: &[u8], data: &[u8]) -> Vec<u8> {
+    Params::new()
+        .hash_length(32)
+        .personal(personal)
+        .hash(data)
+        .as_bytes()
+        .to_vec()
+}
+
+/// The ZIP-243 signature hash of `input` of a Sapling (v4) transaction with `SIGHASH_ALL`, which is what
+/// komodod signs since Sapling.
+///
+/// `script_code` is the `scriptPubKey` of the output being spent and `value` its amount.
+pub(crate) fn signature_hash(
+    tx: &KomodoTransaction,
+    input: usize,
+    script_code: &[u8],
+    value: Amount,
+    branch_id: u32,
+) -> Vec<u8> {
+    let mut prevouts = vec![];
+    let mut sequences = vec![];
+    for txin in &tx.inputs {
+        write_outpoint(&mut prevouts, &txin.prevout);
+        write_u32(&mut sequences, txin.sequence);
+    }
+
+    let mut outputs = vec![];
+    for txout in &tx.outputs {
+        write_txout(&mut outputs, txout);
+    }
+
+    // the hashes of the shielded parts are all zeros when a transaction has none of them
+    let mut hash_joinsplits = vec![0u8; 32];
+    if !tx.joinsplits.is_empty() {
+        let mut joinsplits = vec![];
+        for joinsplit in &tx.joinsplits {
+            write_joinsplit(&mut joinsplits, joinsplit);
+        }
+        joinsplits.extend_from_slice(&tx.joinsplit_pubkey);
+        hash_joinsplits = blake2b_256(b"ZcashJSplitsHash", &joinsplits);
+    }
+
+    let mut hash_spends = vec![0u8; 32];
+    if !tx.shielded_spends.is_empty() {
+        let mut spends = vec![];
+        for spend in &tx.shielded_spends {
+            write_spend_without_sig(&mut spends, spend);
+        }
+        hash_spends = blake2b_256(b"ZcashSSpendsHash", &spends);
+    }
+
+    let mut hash_shielded_outputs = vec![0u8; 32];
+    if !tx.shielded_outputs.is_empty() {
+        let mut shielded_outputs = vec![];
+        for output in &tx.shielded_outputs {
+            write_shielded_output(&mut shielded_outputs, output);
+        }
+        hash_shielded_outputs = blake2b_256(b"ZcashSOutputHash", &shielded_outputs);
+    }
+
+    let mut preimage = vec![];
+    write_u32(&mut preimage, tx.header());
+    write_u32(&mut preimage, tx.version_group_id);
+    preimage.extend(blake2b_256(b"ZcashPrevoutHash", &prevouts));
+    preimage.extend(blake2b_256(b"ZcashSequencHash", &sequences));
+    preimage.extend(blake2b_256(b"ZcashOutputsHash", &outputs));
+    preimage.extend(hash_joinsplits);
+    preimage.extend(hash_spends);
+    preimage.extend(hash_shielded_outputs);
+    write_u32(&mut preimage, tx.lock_time);
+    write_u32(&mut preimage, tx.expiry_height);
+    write_i64(&mut preimage, tx.value_balance.as_sat());
+    write_u32(&mut preimage, SIGHASH_ALL);
+
+    let txin = &tx.inputs[input];
+    write_outpoint(&mut preimage, &txin.prevout);
+    write_bytes(&mut preimage, script_code);
+    write_i64(&mut preimage, value.as_sat());
+    write_u32(&mut preimage, txin.sequence);
+
+    let mut personal = b"ZcashSigHash".to_vec();
+    write_u32(&mut personal, branch_id);
+
+    blake2b_256(&personal, &preimage)
+}
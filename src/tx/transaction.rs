@@ -0,0 +1,465 @@
+use std::time::SystemTime;
+use std_hex;
+
+use tx::encode::*;
+use Amount;
+use ApiError;
+use Txid;
+
+/// Set in the header of every Overwinter (v3) and Sapling (v4) transaction.
+pub const OVERWINTERED_FLAG: u32 = 1 << 31;
+pub const OVERWINTER_TX_VERSION: u32 = 3;
+pub const OVERWINTER_VERSION_GROUP_ID: u32 = 0x03C4_8270;
+pub const SAPLING_TX_VERSION: u32 = 4;
+pub const SAPLING_VERSION_GROUP_ID: u32 = 0x892F_2085;
+/// The consensus branch id of Sapling, which KMD and all its assetchains run.
+pub const SAPLING_BRANCH_ID: u32 = 0x76B8_09BB;
+
+/// The `nSequence` komodod gives its inputs, so that `nLockTime` is enforced.
+pub const SEQUENCE_LOCKTIME: u32 = 0xFFFF_FFFE;
+
+/// komodod sets the locktime this many seconds in the past, to make an input earn KMD rewards.
+const LOCKTIME_OFFSET: u64 = 777;
+
+/// The locktime komodod gives a KMD transaction: the current time - 777 seconds.
+pub fn komodo_lock_time() -> Result<u32, ApiError> {
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(|e| ApiError::Other(format!("system time is before the unix epoch: {}", e)))?;
+
+    Ok((now.as_secs() - LOCKTIME_OFFSET) as u32)
+}
+
+/// A reference to an output of a previous transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutPoint {
+    pub txid: Txid,
+    pub vout: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    pub prevout: OutPoint,
+    pub script_sig: Vec<u8>,
+    pub sequence: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    pub value: Amount,
+    pub script_pubkey: Vec<u8>,
+}
+
+/// A Sapling spend, in v4 transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendDescription {
+    pub cv: [u8; 32],
+    pub anchor: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub rk: [u8; 32],
+    pub zkproof: [u8; 192],
+    pub spend_auth_sig: [u8; 64],
+}
+
+/// A Sapling output, in v4 transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputDescription {
+    pub cv: [u8; 32],
+    pub cmu: [u8; 32],
+    pub ephemeral_key: [u8; 32],
+    pub enc_ciphertext: [u8; 580],
+    pub out_ciphertext: [u8; 80],
+    pub zkproof: [u8; 192],
+}
+
+/// The proof of a `JoinSplit`: PHGR13 before Sapling, Groth16 in v4 transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinSplitProof {
+    PHGR13([u8; 296]),
+    Groth16([u8; 192]),
+}
+
+/// A Sprout joinsplit, in v2 and later transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinSplit {
+    pub vpub_old: Amount,
+    pub vpub_new: Amount,
+    pub anchor: [u8; 32],
+    pub nullifiers: [[u8; 32]; 2],
+    pub commitments: [[u8; 32]; 2],
+    pub ephemeral_key: [u8; 32],
+    pub random_seed: [u8; 32],
+    pub macs: [[u8; 32]; 2],
+    pub proof: JoinSplitProof,
+    pub ciphertexts: [[u8; 601]; 2],
+}
+
+/// A Komodo transaction of any version: v1 and v2 (Sprout), v3 (Overwinter) or v4 (Sapling).
+///
+/// Fields that do not exist in a version are ignored when it is serialized. `joinsplit_pubkey` and
+/// `joinsplit_sig` are only serialized when there are joinsplits, `binding_sig` only when there are
+/// Sapling spends or outputs.
+///
+/// ```
+/// # use komodo_rpc_client::KomodoTransaction;
+/// let hex = "0400008085202f890001e8030000000000000000000000000000000000000000000000000000";
+/// let mut tx = KomodoTransaction::from_hex(hex).unwrap();
+/// assert_eq!(tx.version, 4);
+///
+/// tx.expiry_height = 3_296_000;
+/// assert_eq!(tx.to_hex(), "0400008085202f890001e8030000000000000000000000004b32000000000000000000000000");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KomodoTransaction {
+    pub version: u32,
+    pub overwintered: bool,
+    /// 0 unless the transaction is overwintered.
+    pub version_group_id: u32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+    /// The block height after which the transaction can no longer be mined, 0 to never expire.
+    pub expiry_height: u32,
+    /// The net value of the Sapling spends minus outputs.
+    pub value_balance: Amount,
+    pub shielded_spends: Vec<SpendDescription>,
+    pub shielded_outputs: Vec<OutputDescription>,
+    pub joinsplits: Vec<JoinSplit>,
+    pub joinsplit_pubkey: [u8; 32],
+    pub joinsplit_sig: [u8; 64],
+    pub binding_sig: [u8; 64],
+}
+
+/// An empty Sapling (v4) transaction.
+impl Default for KomodoTransaction {
+    fn default() -> Self {
+        KomodoTransaction {
+            version: SAPLING_TX_VERSION,
+            overwintered: true,
+            version_group_id: SAPLING_VERSION_GROUP_ID,
+            inputs: vec![],
+            outputs: vec![],
+            lock_time: 0,
+            expiry_height: 0,
+            value_balance: Amount::ZERO,
+            shielded_spends: vec![],
+            shielded_outputs: vec![],
+            joinsplits: vec![],
+            joinsplit_pubkey: [0; 32],
+            joinsplit_sig: [0; 64],
+            binding_sig: [0; 64],
+        }
+    }
+}
+
+impl KomodoTransaction {
+    /// The first 4 bytes of the transaction: the version, with the overwintered flag.
+    pub fn header(&self) -> u32 {
+        if self.overwintered {
+            self.version | OVERWINTERED_FLAG
+        } else {
+            self.version
+        }
+    }
+
+    pub fn is_sapling(&self) -> bool {
+        self.overwintered && self.version == SAPLING_TX_VERSION && self.version_group_id == SAPLING_VERSION_GROUP_ID
+    }
+
+    /// The consensus encoding of the transaction.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = vec![];
+
+        write_u32(&mut buf, self.header());
+        if self.overwintered {
+            write_u32(&mut buf, self.version_group_id);
+        }
+
+        write_compact_size(&mut buf, self.inputs.len() as u64);
+        for input in &self.inputs {
+            write_outpoint(&mut buf, &input.prevout);
+            write_bytes(&mut buf, &input.script_sig);
+            write_u32(&mut buf, input.sequence);
+        }
+
+        write_compact_size(&mut buf, self.outputs.len() as u64);
+        for output in &self.outputs {
+            write_txout(&mut buf, output);
+        }
+
+        write_u32(&mut buf, self.lock_time);
+        if self.overwintered {
+            write_u32(&mut buf, self.expiry_height);
+        }
+
+        if self.is_sapling() {
+            write_i64(&mut buf, self.value_balance.as_sat());
+            write_compact_size(&mut buf, self.shielded_spends.len() as u64);
+            for spend in &self.shielded_spends {
+                write_spend(&mut buf, spend);
+            }
+            write_compact_size(&mut buf, self.shielded_outputs.len() as u64);
+            for output in &self.shielded_outputs {
+                write_shielded_output(&mut buf, output);
+            }
+        }
+
+        if self.version >= 2 {
+            write_compact_size(&mut buf, self.joinsplits.len() as u64);
+            for joinsplit in &self.joinsplits {
+                write_joinsplit(&mut buf, joinsplit);
+            }
+            if !self.joinsplits.is_empty() {
+                buf.extend_from_slice(&self.joinsplit_pubkey);
+                buf.extend_from_slice(&self.joinsplit_sig);
+            }
+        }
+
+        if self.is_sapling() && !(self.shielded_spends.is_empty() && self.shielded_outputs.is_empty()) {
+            buf.extend_from_slice(&self.binding_sig);
+        }
+
+        buf
+    }
+
+    /// The serialized transaction as hex, as `send_raw_transaction` takes it.
+    pub fn to_hex(&self) -> String {
+        std_hex::encode(self.serialize())
+    }
+
+    pub fn txid(&self) -> Txid {
+        Txid::hash(&self.serialize())
+    }
+
+    /// Decodes a transaction from its consensus encoding. All of `data` must be the transaction.
+    pub fn deserialize(data: &[u8]) -> Result<KomodoTransaction, ApiError> {
+        let mut reader = Reader::new(data);
+        let mut tx = KomodoTransaction::default();
+
+        let header = reader.read_u32()?;
+        tx.overwintered = header & OVERWINTERED_FLAG != 0;
+        tx.version = header & !OVERWINTERED_FLAG;
+        tx.version_group_id = 0;
+
+        if tx.overwintered {
+            tx.version_group_id = reader.read_u32()?;
+
+            match (tx.version, tx.version_group_id) {
+                (OVERWINTER_TX_VERSION, OVERWINTER_VERSION_GROUP_ID) | (SAPLING_TX_VERSION, SAPLING_VERSION_GROUP_ID) => {},
+                (version, group) => return Err(ApiError::Other(
+                    format!("invalid transaction: unknown version {} with version group id {:#010x}", version, group)
+                )),
+            }
+        } else if tx.version < 1 {
+            return Err(ApiError::Other(format!("invalid transaction: unknown version {}", tx.version)));
+        }
+
+        for _ in 0..reader.read_compact_size()? {
+            let mut txid = [0u8; 32];
+            reader.read_into(&mut txid)?;
+
+            tx.inputs.push(TxIn {
+                prevout: OutPoint {
+                    txid: Txid::from_bytes(txid),
+                    vout: reader.read_u32()?,
+                },
+                script_sig: reader.read_bytes()?,
+                sequence: reader.read_u32()?,
+            });
+        }
+
+        for _ in 0..reader.read_compact_size()? {
+            tx.outputs.push(TxOut {
+                value: Amount::from_sat(reader.read_i64()?),
+                script_pubkey: reader.read_bytes()?,
+            });
+        }
+
+        tx.lock_time = reader.read_u32()?;
+        if tx.overwintered {
+            tx.expiry_height = reader.read_u32()?;
+        }
+
+        if tx.is_sapling() {
+            tx.value_balance = Amount::from_sat(reader.read_i64()?);
+            for _ in 0..reader.read_compact_size()? {
+                tx.shielded_spends.push(read_spend(&mut reader)?);
+            }
+            for _ in 0..reader.read_compact_size()? {
+                tx.shielded_outputs.push(read_shielded_output(&mut reader)?);
+            }
+        }
+
+        if tx.version >= 2 {
+            for _ in 0..reader.read_compact_size()? {
+                let joinsplit = read_joinsplit(&mut reader, tx.is_sapling())?;
+                tx.joinsplits.push(joinsplit);
+            }
+            if !tx.joinsplits.is_empty() {
+                reader.read_into(&mut tx.joinsplit_pubkey)?;
+                reader.read_into(&mut tx.joinsplit_sig)?;
+            }
+        }
+
+        if tx.is_sapling() && !(tx.shielded_spends.is_empty() && tx.shielded_outputs.is_empty()) {
+            reader.read_into(&mut tx.binding_sig)?;
+        }
+
+        if !reader.is_empty() {
+            return Err(ApiError::Other("invalid transaction: data left after the transaction".to_string()));
+        }
+
+        Ok(tx)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<KomodoTransaction, ApiError> {
+        let data = std_hex::decode(hex)
+            .map_err(|e| ApiError::Other(format!("invalid transaction: {}", e)))?;
+
+        KomodoTransaction::deserialize(&data)
+    }
+}
+
+pub(crate) fn write_outpoint(buf: &mut Vec<u8>, outpoint: &OutPoint) {
+    buf.extend_from_slice(outpoint.txid.as_bytes());
+    write_u32(buf, outpoint.vout);
+}
+
+pub(crate) fn write_txout(buf: &mut Vec<u8>, output: &TxOut) {
+    write_i64(buf, output.value.as_sat());
+    write_bytes(buf, &output.script_pubkey);
+}
+
+/// A spend without its `spend_auth_sig`, as it is hashed for signatures.
+pub(crate) fn write_spend_without_sig(buf: &mut Vec<u8>, spend: &SpendDescription) {
+    buf.extend_from_slice(&spend.cv);
+    buf.extend_from_slice(&spend.anchor);
+    buf.extend_from_slice(&spend.nullifier);
+    buf.extend_from_slice(&spend.rk);
+    buf.extend_from_slice(&spend.zkproof);
+}
+
+fn write_spend(buf: &mut Vec<u8>, spend: &SpendDescription) {
+    write_spend_without_sig(buf, spend);
+    buf.extend_from_slice(&spend.spend_auth_sig);
+}
+
+fn read_spend(reader: &mut Reader) -> Result<SpendDescription, ApiError> {
+    let mut spend = SpendDescription {
+        cv: [0; 32],
+        anchor: [0; 32],
+        nullifier: [0; 32],
+        rk: [0; 32],
+        zkproof: [0; 192],
+        spend_auth_sig: [0; 64],
+    };
+
+    reader.read_into(&mut spend.cv)?;
+    reader.read_into(&mut spend.anchor)?;
+    reader.read_into(&mut spend.nullifier)?;
+    reader.read_into(&mut spend.rk)?;
+    reader.read_into(&mut spend.zkproof)?;
+    reader.read_into(&mut spend.spend_auth_sig)?;
+
+    Ok(spend)
+}
+
+pub(crate) fn write_shielded_output(buf: &mut Vec<u8>, output: &OutputDescription) {
+    buf.extend_from_slice(&output.cv);
+    buf.extend_from_slice(&output.cmu);
+    buf.extend_from_slice(&output.ephemeral_key);
+    buf.extend_from_slice(&output.enc_ciphertext);
+    buf.extend_from_slice(&output.out_ciphertext);
+    buf.extend_from_slice(&output.zkproof);
+}
+
+fn read_shielded_output(reader: &mut Reader) -> Result<OutputDescription, ApiError> {
+    let mut output = OutputDescription {
+        cv: [0; 32],
+        cmu: [0; 32],
+        ephemeral_key: [0; 32],
+        enc_ciphertext: [0; 580],
+        out_ciphertext: [0; 80],
+        zkproof: [0; 192],
+    };
+
+    reader.read_into(&mut output.cv)?;
+    reader.read_into(&mut output.cmu)?;
+    reader.read_into(&mut output.ephemeral_key)?;
+    reader.read_into(&mut output.enc_ciphertext)?;
+    reader.read_into(&mut output.out_ciphertext)?;
+    reader.read_into(&mut output.zkproof)?;
+
+    Ok(output)
+}
+
+pub(crate) fn write_joinsplit(buf: &mut Vec<u8>, joinsplit: &JoinSplit) {
+    write_i64(buf, joinsplit.vpub_old.as_sat());
+    write_i64(buf, joinsplit.vpub_new.as_sat());
+    buf.extend_from_slice(&joinsplit.anchor);
+    for nullifier in &joinsplit.nullifiers {
+        buf.extend_from_slice(nullifier);
+    }
+    for commitment in &joinsplit.commitments {
+        buf.extend_from_slice(commitment);
+    }
+    buf.extend_from_slice(&joinsplit.ephemeral_key);
+    buf.extend_from_slice(&joinsplit.random_seed);
+    for mac in &joinsplit.macs {
+        buf.extend_from_slice(mac);
+    }
+    match joinsplit.proof {
+        JoinSplitProof::PHGR13(ref proof) => buf.extend_from_slice(proof),
+        JoinSplitProof::Groth16(ref proof) => buf.extend_from_slice(proof),
+    }
+    for ciphertext in &joinsplit.ciphertexts {
+        buf.extend_from_slice(ciphertext);
+    }
+}
+
+/// Joinsplits in Sapling transactions have a Groth16 proof, in earlier transactions a PHGR13 proof.
+fn read_joinsplit(reader: &mut Reader, groth16: bool) -> Result<JoinSplit, ApiError> {
+    let vpub_old = Amount::from_sat(reader.read_i64()?);
+    let vpub_new = Amount::from_sat(reader.read_i64()?);
+
+    let mut joinsplit = JoinSplit {
+        vpub_old,
+        vpub_new,
+        anchor: [0; 32],
+        nullifiers: [[0; 32]; 2],
+        commitments: [[0; 32]; 2],
+        ephemeral_key: [0; 32],
+        random_seed: [0; 32],
+        macs: [[0; 32]; 2],
+        proof: JoinSplitProof::Groth16([0; 192]),
+        ciphertexts: [[0; 601]; 2],
+    };
+
+    reader.read_into(&mut joinsplit.anchor)?;
+    for nullifier in &mut joinsplit.nullifiers {
+        reader.read_into(nullifier)?;
+    }
+    for commitment in &mut joinsplit.commitments {
+        reader.read_into(commitment)?;
+    }
+    reader.read_into(&mut joinsplit.ephemeral_key)?;
+    reader.read_into(&mut joinsplit.random_seed)?;
+    for mac in &mut joinsplit.macs {
+        reader.read_into(mac)?;
+    }
+
+    joinsplit.proof = if groth16 {
+        let mut proof = [0; 192];
+        reader.read_into(&mut proof)?;
+        JoinSplitProof::Groth16(proof)
+    } else {
+        let mut proof = [0; 296];
+        reader.read_into(&mut proof)?;
+        JoinSplitProof::PHGR13(proof)
+    };
+
+    for ciphertext in &mut joinsplit.ciphertexts {
+        reader.read_into(ciphertext)?;
+    }
+
+    Ok(joinsplit)
+}
@@ -1,7 +1,8 @@
-use serde::de::{self, Deserialize, Deserializer};
-use std::str::FromStr;
-use std::fmt::Display;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use std::fmt;
 use types::arguments::address::Address;
+use Amount;
+use Txid;
 
 #[derive(Debug, Deserialize)]
 pub struct AddressBalance {
@@ -16,7 +17,7 @@ pub struct AddressDeltas(pub Vec<AddressDelta>);
 #[derive(Debug, Deserialize)]
 pub struct AddressDelta {
     pub satoshis: i64,
-    pub txid: String,
+    pub txid: Txid,
     pub index: u32,
     pub blockindex: u64,
     pub height: u64,
@@ -29,16 +30,16 @@ pub struct AddressMempool(pub Vec<AddressMempoolDelta>);
 #[derive(Debug, Deserialize)]
 pub struct AddressMempoolDelta {
     pub address: String,
-    pub txid: String,
+    pub txid: Txid,
     pub index: u32,
     pub satoshis: i64,
     pub timestamp: u64,
-    pub prevtxid: String,
-    pub prevout: u32,
+    pub prevtxid: Option<Txid>, // only present for spending deltas
+    pub prevout: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct AddressTxIDs(pub Vec<String>); // todo: a vec with txids, needs work.
+pub struct AddressTxIDs(pub Vec<Txid>);
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AddressUtxos(pub Vec<AddressUtxo>);
@@ -46,7 +47,7 @@ pub struct AddressUtxos(pub Vec<AddressUtxo>);
 #[derive(Debug, Deserialize, Clone)]
 pub struct AddressUtxo {
     pub address: String,
-    pub txid: String,
+    pub txid: Txid,
     #[serde(rename = "outputIndex")]
     pub output_index: u32,
     pub script: String,
@@ -58,6 +59,7 @@ pub struct AddressUtxo {
 pub struct Snapshot {
     pub start_time: u64,
     pub addresses: Vec<SnapshotAddress>,
+    // `total`, `average`, `cc_utxo_value` and `total_includeCCvouts` are calculated as doubles by komodod
     pub total: f64,
     pub average: f64,
     pub utxos: u64,
@@ -66,7 +68,7 @@ pub struct Snapshot {
     pub end_time: u64,
     pub ignored_addresses: u32,
     pub skipped_cc_utxos: u32,
-    pub cc_utxo_value: u32,
+    pub cc_utxo_value: f64,
     #[serde(rename = "total_includeCCvouts")]
     pub total_include_ccvouts: f64,
 
@@ -75,47 +77,55 @@ pub struct Snapshot {
 #[derive(Debug, Deserialize)]
 pub struct SnapshotAddress {
     pub addr: String,
-    #[serde(deserialize_with = "from_str")]
-    pub amount: f64
-}
-
-fn from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
-    where T: FromStr,
-          T::Err: Display,
-          D: Deserializer<'de>
-{
-    let s = String::deserialize(deserializer)?;
-    T::from_str(&s).map_err(de::Error::custom)
+    pub amount: Amount, // komodod returns a string
 }
 
+/// Groups of addresses whose common ownership has been made public by being used together
+/// as inputs or as the resulting change.
 #[derive(Debug, Deserialize)]
-pub struct AddressGroupings(Vec<Vec<Vec<AddressGrouping>>>);
+pub struct AddressGroupings(pub Vec<Vec<AddressGrouping>>);
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug)]
 pub struct AddressGrouping {
     pub address: Address,
-    pub amount: f64,
+    pub amount: Amount,
     pub account: Option<String>,
 }
 
+// komodod returns a grouping as an array: [address, amount] or [address, amount, account]
+impl<'de> Deserialize<'de> for AddressGrouping {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        struct AddressGroupingVisitor;
+
+        impl<'de> Visitor<'de> for AddressGroupingVisitor {
+            type Value = AddressGrouping;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array of address, amount and an optional account")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<AddressGrouping, A::Error> where
+                A: SeqAccess<'de> {
+                let address = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let amount = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let account = seq.next_element()?;
+
+                Ok(AddressGrouping {
+                    address,
+                    amount,
+                    account,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(AddressGroupingVisitor)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Multisig {
     pub address: Address,
     #[serde(rename = "redeemScript")]
     pub redeem_script: String
 }
-
-//impl<'de> Deserialize<'de> for AddressGrouping {
-//    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
-//        D: Deserializer<'de> {
-//        let s: &str = Deserialize::deserialize(deserializer)?;
-//        dbg!(&s);
-//
-//        Ok(AddressGrouping {
-//            address: Address::from("RKakNo1Vz86xiCLB5vq5UBe4P7sB9Gw6pv").unwrap(),
-//            amount: 10.0,
-//            account: None,
-//        })
-//
-//    }
-//}
\ No newline at end of file
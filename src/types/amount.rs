@@ -0,0 +1,230 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{self, Serialize, Serializer};
+use serde_json::{self, value::RawValue};
+
+use ApiError;
+
+/// The number of satoshis in one coin.
+pub const COIN: i64 = 100_000_000;
+
+/// An amount of coins, stored as a number of satoshis.
+///
+/// komodod writes amounts as decimal numbers with 8 decimals. `Amount` reads them from their decimal form
+/// rather than multiplying a floating point number, so no satoshi gets lost to rounding.
+///
+/// ```
+/// # use komodo_rpc_client::Amount;
+/// let amount: Amount = "13.78296712".parse().unwrap();
+///
+/// assert_eq!(amount.as_sat(), 1_378_296_712);
+/// assert_eq!(amount.to_string(), "13.78296712");
+/// assert_eq!(amount.checked_add(Amount::from_sat(88)).unwrap().to_string(), "13.78296800");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+    pub const ONE_COIN: Amount = Amount(COIN);
+
+    pub const fn from_sat(satoshis: i64) -> Amount {
+        Amount(satoshis)
+    }
+
+    pub fn as_sat(self) -> i64 {
+        self.0
+    }
+
+    /// An amount of whole coins. `None` if the amount does not fit in an `i64` of satoshis.
+    pub fn from_coins(coins: i64) -> Option<Amount> {
+        coins.checked_mul(COIN).map(Amount)
+    }
+
+    pub fn checked_add(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_add(rhs.0).map(Amount)
+    }
+
+    pub fn checked_sub(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_sub(rhs.0).map(Amount)
+    }
+
+    pub fn checked_mul(self, rhs: i64) -> Option<Amount> {
+        self.0.checked_mul(rhs).map(Amount)
+    }
+
+    /// Integer division, rounding towards zero.
+    pub fn checked_div(self, rhs: i64) -> Option<Amount> {
+        self.0.checked_div(rhs).map(Amount)
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+}
+
+/// Panics on overflow, like integer arithmetic in debug builds. Use `checked_add` where overflow is possible.
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, rhs: Amount) -> Amount {
+        self.checked_add(rhs).expect("Amount addition overflowed")
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, rhs: Amount) {
+        *self = *self + rhs
+    }
+}
+
+/// Panics on overflow, like integer arithmetic in debug builds. Use `checked_sub` where overflow is possible.
+impl Sub for Amount {
+    type Output = Amount;
+
+    fn sub(self, rhs: Amount) -> Amount {
+        self.checked_sub(rhs).expect("Amount subtraction overflowed")
+    }
+}
+
+impl SubAssign for Amount {
+    fn sub_assign(&mut self, rhs: Amount) {
+        *self = *self - rhs
+    }
+}
+
+impl Neg for Amount {
+    type Output = Amount;
+
+    fn neg(self) -> Amount {
+        Amount(self.0.checked_neg().expect("Amount negation overflowed"))
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Amount> for Amount {
+    fn sum<I: Iterator<Item = &'a Amount>>(iter: I) -> Amount {
+        iter.cloned().sum()
+    }
+}
+
+/// Formats like komodod does: a decimal number with 8 decimals.
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let satoshis = self.0.unsigned_abs();
+
+        write!(f, "{}{}.{:08}", sign, satoshis / COIN as u64, satoshis % COIN as u64)
+    }
+}
+
+/// Parses a decimal number of coins, such as `13.78296712`, `-0.0001` or `1e-4`.
+/// More than 8 decimals is an error, as such an amount can not be expressed in satoshis.
+impl FromStr for Amount {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ApiError::Other(format!("invalid amount: {:?}", s));
+
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (mantissa, exponent) = match unsigned.find(&['e', 'E'][..]) {
+            Some(i) => (&unsigned[..i], unsigned[i + 1..].parse::<i32>().map_err(|_| invalid())?),
+            None => (unsigned, 0),
+        };
+
+        let (integer, fraction) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+            None => (mantissa, ""),
+        };
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut digits: Vec<u8> = Vec::with_capacity(integer.len() + fraction.len());
+        for c in integer.bytes().chain(fraction.bytes()) {
+            if !c.is_ascii_digit() {
+                return Err(invalid());
+            }
+            digits.push(c - b'0');
+        }
+
+        // the value is `digits * 10^-decimals`, which in satoshis is `digits * 10^(8 - decimals)`
+        let decimals = fraction.len() as i64 - exponent as i64;
+        let mut shift = 8 - decimals;
+
+        while shift < 0 {
+            match digits.pop() {
+                Some(0) => shift += 1,
+                Some(_) => return Err(ApiError::Other(format!("amount has more than 8 decimals: {}", s))),
+                None => break,
+            }
+        }
+
+        let overflow = || ApiError::Other(format!("amount out of range: {}", s));
+        let mut satoshis: i64 = 0;
+
+        for digit in digits {
+            satoshis = satoshis.checked_mul(10)
+                .and_then(|sat| sat.checked_add(digit as i64))
+                .ok_or_else(overflow)?;
+        }
+
+        for _ in 0..shift.max(0) {
+            if satoshis == 0 {
+                break;
+            }
+            satoshis = satoshis.checked_mul(10).ok_or_else(overflow)?;
+        }
+
+        Ok(Amount(if negative { -satoshis } else { satoshis }))
+    }
+}
+
+/// Serializes as a JSON number with 8 decimals, written as it is rather than through an `f64`, which cannot
+/// hold every satoshi above about 67 million coins.
+///
+/// The number is a serde_json `RawValue`. Serialized into a `Value`, it is read back as an `f64`.
+impl Serialize for Amount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+        S: Serializer {
+        RawValue::from_string(self.to_string()).map_err(ser::Error::custom)?.serialize(serializer)
+    }
+}
+
+/// Deserializes from a JSON number or, as some RPCs return amounts that way, from a string.
+///
+/// The number is parsed from its text as komodod wrote it, which serde_json hands out as a `RawValue`,
+/// rather than from an `f64`. Deserialized from a `Value`, the text is that of the `f64` it holds.
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        let text = raw.get().trim();
+
+        if text.starts_with('"') {
+            let text: String = serde_json::from_str(text).map_err(de::Error::custom)?;
+
+            Amount::from_str(&text).map_err(de::Error::custom)
+        } else {
+            Amount::from_str(text).map_err(de::Error::custom)
+        }
+    }
+}
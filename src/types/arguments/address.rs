@@ -34,33 +34,84 @@
 //    }
 //}
 
+use Amount;
 use ApiError;
+use bech32::{Bech32, FromBase32, ToBase32};
+use bitcoin::util::base58;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde::de::Error;
 use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
-/// Address is either Transparent (address starts with `R`) or Shielded (all sapling, starts with `zs`)
-#[derive(Debug, Clone, Hash, Eq)]
+/// The length of a hash160, the payload of a transparent address.
+const HASH160_LEN: usize = 20;
+/// The length of a raw Sapling payment address: an 11 byte diversifier followed by the 32 byte `pk_d`.
+const SAPLING_PAYLOAD_LEN: usize = 43;
+
+/// The kind of an `Address`, which determines what its payload is.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum AddressKind {
+    /// A transparent P2PKH address, starts with `R` on KMD.
+    PubkeyHash,
+    /// A transparent P2SH address, starts with `b` on KMD.
+    ScriptHash,
+    /// A shielded Sapling address, starts with `zs`.
+    Sapling,
+}
+
+/// The version bytes and prefixes a chain encodes its addresses with.
+///
+/// KMD and assetchains all use `AddressParams::komodo()`, unless the chain was started with custom prefixes.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AddressParams {
+    pub pubkey_hash: u8,
+    pub script_hash: u8,
+    pub sapling_hrp: String,
+}
+
+impl AddressParams {
+    pub fn komodo() -> AddressParams {
+        AddressParams {
+            pubkey_hash: 60,
+            script_hash: 85,
+            sapling_hrp: "zs".to_string(),
+        }
+    }
+}
+
+impl Default for AddressParams {
+    fn default() -> Self {
+        AddressParams::komodo()
+    }
+}
+
+/// Address is either Transparent (base58check, starts with `R` or `b`) or Shielded (all sapling, bech32, starts with `zs`)
+///
+/// Addresses are validated when they are created with `from` or `from_with_params`: the checksum, the length
+/// of the payload and the version byte or human readable part must all be correct. Addresses in responses are
+/// not, as the client does not know the prefixes of the chain; check them with `validate`.
+///
+/// ```
+/// # use komodo_rpc_client::arguments::address::{Address, AddressKind};
+/// let address = Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap();
+///
+/// assert_eq!(address.kind(), Some(AddressKind::PubkeyHash));
+/// assert_eq!(address.payload().len(), 20);
+/// assert!(Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWr").is_err());
+/// ```
+#[derive(Debug, Clone, Eq)]
 pub struct Address {
     pub(crate) addr: String,
     pub(crate) addr_type: AddrType,
+    kind: Option<AddressKind>,
+    payload: Vec<u8>,
 }
 
 impl TryFrom<&String> for Address {
     type Error = ApiError;
 
     fn try_from(addr_str: &String) -> Result<Self, Self::Error> {
-        match addr_str.len() {
-            78 => Ok(Address {
-                addr: addr_str.to_string(),
-                addr_type: AddrType::Shielded,
-            }),
-            34 => Ok(Address {
-                addr: addr_str.to_string(),
-                addr_type: AddrType::Transparent,
-            }),
-            _ => Err(ApiError::Other(format!("Address has incorrect length")))
-        }
+        Address::from(addr_str)
     }
 }
 
@@ -70,21 +121,121 @@ impl PartialEq for Address {
     }
 }
 
-//impl Eq for Address { }
+impl Hash for Address {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr.hash(state)
+    }
+}
 
 impl Address {
-    // todo check if address is correctly encoded
+    /// Parses and validates a KMD address.
     pub fn from(addr_str: &str) -> Result<Address, ApiError> {
-        match addr_str.len() {
-            78 => Ok(Address {
-                    addr: addr_str.to_string(),
-                    addr_type: AddrType::Shielded,
-                }),
-            34 => Ok(Address {
-                    addr: addr_str.to_string(),
-                    addr_type: AddrType::Transparent,
-                }),
-            _ => Err(ApiError::Other(format!("Address has incorrect length")))
+        Address::from_with_params(addr_str, &AddressParams::komodo())
+    }
+
+    /// Parses and validates an address of a chain that uses other version bytes or prefixes than KMD.
+    pub fn from_with_params(addr_str: &str, params: &AddressParams) -> Result<Address, ApiError> {
+        if addr_str.starts_with(&format!("{}1", params.sapling_hrp)) {
+            return Address::from_sapling(addr_str, params);
+        }
+
+        let decoded = base58::from_check(addr_str)
+            .map_err(|e| ApiError::Other(format!("invalid address {}: {}", addr_str, e)))?;
+
+        if decoded.len() != 1 + HASH160_LEN {
+            return Err(ApiError::Other(format!("invalid address {}: payload has incorrect length", addr_str)));
+        }
+
+        let kind = match decoded[0] {
+            v if v == params.pubkey_hash => AddressKind::PubkeyHash,
+            v if v == params.script_hash => AddressKind::ScriptHash,
+            v => return Err(ApiError::Other(format!("invalid address {}: unknown version byte {}", addr_str, v))),
+        };
+
+        Ok(Address {
+            addr: addr_str.to_string(),
+            addr_type: AddrType::Transparent,
+            kind: Some(kind),
+            payload: decoded[1..].to_vec(),
+        })
+    }
+
+    fn from_sapling(addr_str: &str, params: &AddressParams) -> Result<Address, ApiError> {
+        let invalid = |e: String| ApiError::Other(format!("invalid address {}: {}", addr_str, e));
+
+        let bech32: Bech32 = addr_str.parse().map_err(|e: ::bech32::Error| invalid(e.to_string()))?;
+        if bech32.hrp() != params.sapling_hrp {
+            return Err(invalid(format!("unknown prefix {}", bech32.hrp())));
+        }
+
+        let payload = Vec::<u8>::from_base32(bech32.data()).map_err(|e| invalid(e.to_string()))?;
+        if payload.len() != SAPLING_PAYLOAD_LEN {
+            return Err(invalid("payload has incorrect length".to_string()));
+        }
+
+        Ok(Address {
+            addr: addr_str.to_string(),
+            addr_type: AddrType::Shielded,
+            kind: Some(AddressKind::Sapling),
+            payload,
+        })
+    }
+
+    /// Validates this address against the prefixes of a chain, such as one read from a response.
+    /// Returns the address with its kind and payload as the chain defines them.
+    pub fn validate(&self, params: &AddressParams) -> Result<Address, ApiError> {
+        Address::from_with_params(&self.addr, params)
+    }
+
+    /// Reads an address without knowing the prefixes of its chain. The kind and payload are only known
+    /// when it is a valid KMD address; the address is kept as it is either way.
+    fn unchecked(addr_str: &str) -> Address {
+        if let Ok(address) = Address::from(addr_str) {
+            return address;
+        }
+
+        let addr_type = match addr_str.parse::<Bech32>() {
+            Ok(_) => AddrType::Shielded,
+            Err(_) => AddrType::Transparent,
+        };
+
+        Address {
+            addr: addr_str.to_string(),
+            addr_type,
+            kind: None,
+            payload: vec![],
+        }
+    }
+
+    /// The P2PKH address of a hash160 of a public key.
+    pub fn from_pubkey_hash(hash: &[u8; 20], params: &AddressParams) -> Address {
+        Address::transparent(params.pubkey_hash, AddressKind::PubkeyHash, hash)
+    }
+
+    /// The P2SH address of a hash160 of a redeem script.
+    pub fn from_script_hash(hash: &[u8; 20], params: &AddressParams) -> Address {
+        Address::transparent(params.script_hash, AddressKind::ScriptHash, hash)
+    }
+
+    /// The Sapling address of a raw payment address: the diversifier followed by `pk_d`.
+    /// Fails when the `sapling_hrp` of `params` is not a valid bech32 human readable part.
+    pub fn from_sapling_payload(payload: &[u8; 43], params: &AddressParams) -> Result<Address, ApiError> {
+        let bech32 = Bech32::new(params.sapling_hrp.clone(), payload.to_base32())
+            .map_err(|e| ApiError::Other(format!("invalid human readable part {:?}: {}", params.sapling_hrp, e)))?;
+
+        // parsing it back rejects what `Bech32::new` does not check: the characters and the length
+        Address::from_sapling(&bech32.to_string(), params)
+    }
+
+    fn transparent(version: u8, kind: AddressKind, hash: &[u8; 20]) -> Address {
+        let mut data = vec![version];
+        data.extend_from_slice(hash);
+
+        Address {
+            addr: base58::check_encode_slice(&data),
+            addr_type: AddrType::Transparent,
+            kind: Some(kind),
+            payload: hash.to_vec(),
         }
     }
 
@@ -92,23 +243,45 @@ impl Address {
     pub fn any() -> Address {
         Address {
             addr: "*".to_string(),
-            addr_type: AddrType::Transparent
+            addr_type: AddrType::Transparent,
+            kind: None,
+            payload: vec![],
         }
     }
 
-    pub fn to_string(&self) -> String {
-        self.addr.clone()
+    /// The kind of address, `None` for the wildcard of `Address::any()` and for a deserialized address
+    /// that is not a KMD address, until it is checked with `validate`.
+    pub fn kind(&self) -> Option<AddressKind> {
+        self.kind
+    }
+
+    /// The hash160 of a transparent address, or the raw payment address (diversifier and `pk_d`) of a Sapling address.
+    /// Empty when `kind` is `None`.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    pub fn is_shielded(&self) -> bool {
+        self.addr_type == AddrType::Shielded
     }
 }
 
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.addr)
+    }
+}
+
+/// Deserializes any address, so responses of chains with custom prefixes can be read. The address is
+/// only validated when it is a KMD address; use `Address::validate` with the prefixes of the chain.
 impl<'de> Deserialize<'de> for Address {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
     {
-        let s: &str = Deserialize::deserialize(deserializer)?;
-        Address::from(s)
-            .map_err(D::Error::custom)
+        let s: String = Deserialize::deserialize(deserializer)?;
+
+        Ok(Address::unchecked(&s))
     }
 }
 
@@ -167,17 +340,17 @@ impl FromAddress {
 }
 
 // for use in `z_sendmany` RPC
-pub struct Amounts(pub(crate) Vec<Amount>);
+pub struct Amounts(pub(crate) Vec<SendAmount>);
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct Amount {
+pub struct SendAmount {
     pub address: Address,
-    pub amount: f64,
+    pub amount: Amount,
     pub memo: Option<String>,
 }
 
-impl From<Vec<Amount>> for Amounts {
-    fn from(v: Vec<Amount>) -> Self {
+impl From<Vec<SendAmount>> for Amounts {
+    fn from(v: Vec<SendAmount>) -> Self {
         let mut result = vec![];
         for amount in v {
             result.push(amount.clone())
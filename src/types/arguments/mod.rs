@@ -1,9 +1,11 @@
 pub mod address;
 mod address_list;
 mod keys;
+mod oracles;
 pub mod shielded;
 mod transaction;
 
 pub use self::{address_list::*};
 pub use self::{transaction::*};
-pub use TransactionId;
\ No newline at end of file
+pub use self::{oracles::*};
+pub use Txid;
\ No newline at end of file
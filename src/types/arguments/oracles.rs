@@ -0,0 +1,177 @@
+use std_hex;
+use ::{ApiError, BlockHash, Txid};
+
+/// The characters an oracle format is made of, see `OracleFormat::encode`.
+const FORMAT_CHARS: &str = "sSdDcCtTiIlLh";
+
+/// The format of an oracle as given to `oraclescreate`: one character per value in its data.
+///
+/// | char | value                 | encoded as                               |
+/// |------|-----------------------|------------------------------------------|
+/// | `s`  | string, < 256 bytes   | length in 1 byte, UTF-8 bytes            |
+/// | `S`  | string, < 65536 bytes | length in 2 bytes little endian, bytes   |
+/// | `d`  | bytes, < 256          | length in 1 byte, bytes                  |
+/// | `D`  | bytes, < 65536        | length in 2 bytes little endian, bytes   |
+/// | `c`  `C` | `i8` `u8`         | 1 byte                                   |
+/// | `t`  `T` | `i16` `u16`       | 2 bytes little endian                    |
+/// | `i`  `I` | `i32` `u32`       | 4 bytes little endian                    |
+/// | `l`  `L` | `i64` `u64`       | 8 bytes little endian                    |
+/// | `h`  | hash                  | 32 bytes, as a uint256 in komodod        |
+///
+/// Formats combine these, such as `Ihh` for a block height, its hash and merkle root.
+///
+/// ```
+/// # use komodo_rpc_client::arguments::{OracleFormat, OracleValue};
+/// let format = OracleFormat::new("sL").unwrap();
+/// let data = format.encode(&["KMD".into(), 3_141_592u64.into()]).unwrap();
+///
+/// assert_eq!(data.0, "034b4d44d8ef2f0000000000");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleFormat(String);
+
+/// A value of the data of an oracle, see `OracleFormat`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OracleValue {
+    String(String),
+    Bytes(Vec<u8>),
+    Int(i64),
+    UInt(u64),
+    /// In the byte order of `BlockHash::as_bytes`, so komodod displays it as the hex it was parsed from.
+    Hash([u8; 32]),
+}
+
+/// Hex encoded oracle data, as `oraclesdata` expects it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OracleData(pub String);
+
+impl OracleFormat {
+    pub fn new(format: &str) -> Result<Self, ApiError> {
+        if format.is_empty() {
+            return Err(ApiError::Other(String::from("oracle format is empty")));
+        }
+        if let Some(c) = format.chars().find(|c| !FORMAT_CHARS.contains(*c)) {
+            return Err(ApiError::Other(format!("invalid character {} in oracle format {}", c, format)));
+        }
+
+        Ok(OracleFormat(format.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Encodes `values`, one for every character of the format.
+    pub fn encode(&self, values: &[OracleValue]) -> Result<OracleData, ApiError> {
+        if values.len() != self.0.len() {
+            return Err(ApiError::Other(format!(
+                "oracle format {} takes {} values, not {}", self.0, self.0.len(), values.len()
+            )));
+        }
+
+        let mut data = Vec::new();
+        for (c, value) in self.0.chars().zip(values) {
+            encode_value(c, value, &mut data)?;
+        }
+
+        Ok(OracleData(std_hex::encode(data)))
+    }
+}
+
+fn encode_value(c: char, value: &OracleValue, data: &mut Vec<u8>) -> Result<(), ApiError> {
+    match (c, value) {
+        ('s', OracleValue::String(s)) => encode_bytes(c, s.as_bytes(), 1, data),
+        ('S', OracleValue::String(s)) => encode_bytes(c, s.as_bytes(), 2, data),
+        ('d', OracleValue::Bytes(bytes)) => encode_bytes(c, bytes, 1, data),
+        ('D', OracleValue::Bytes(bytes)) => encode_bytes(c, bytes, 2, data),
+        ('h', OracleValue::Hash(hash)) => {
+            data.extend_from_slice(hash);
+            Ok(())
+        },
+        ('c', _) | ('C', _) | ('t', _) | ('T', _) | ('i', _) | ('I', _) | ('l', _) | ('L', _) => encode_integer(c, value, data),
+        _ => Err(ApiError::Other(format!("{:?} does not fit oracle format {}", value, c))),
+    }
+}
+
+fn encode_bytes(c: char, bytes: &[u8], length_size: usize, data: &mut Vec<u8>) -> Result<(), ApiError> {
+    if bytes.len() >= 1 << (8 * length_size) {
+        return Err(ApiError::Other(format!("{} bytes are too long for oracle format {}", bytes.len(), c)));
+    }
+
+    data.extend_from_slice(&(bytes.len() as u16).to_le_bytes()[..length_size]);
+    data.extend_from_slice(bytes);
+
+    Ok(())
+}
+
+fn encode_integer(c: char, value: &OracleValue, data: &mut Vec<u8>) -> Result<(), ApiError> {
+    let n = match *value {
+        OracleValue::Int(n) => n as i128,
+        OracleValue::UInt(n) => n as i128,
+        _ => return Err(ApiError::Other(format!("{:?} does not fit oracle format {}", value, c))),
+    };
+
+    let (size, min, max) = match c {
+        'c' => (1, i8::MIN as i128, i8::MAX as i128),
+        'C' => (1, 0, u8::MAX as i128),
+        't' => (2, i16::MIN as i128, i16::MAX as i128),
+        'T' => (2, 0, u16::MAX as i128),
+        'i' => (4, i32::MIN as i128, i32::MAX as i128),
+        'I' => (4, 0, u32::MAX as i128),
+        'l' => (8, i64::MIN as i128, i64::MAX as i128),
+        _ => (8, 0, u64::MAX as i128),
+    };
+    if n < min || n > max {
+        return Err(ApiError::Other(format!("{} is out of range for oracle format {}", n, c)));
+    }
+
+    // two's complement, so the low bytes of a negative number are those of its smaller type
+    data.extend_from_slice(&(n as u64).to_le_bytes()[..size]);
+
+    Ok(())
+}
+
+impl<'a> From<&'a str> for OracleValue {
+    fn from(s: &'a str) -> Self {
+        OracleValue::String(s.to_string())
+    }
+}
+
+impl From<String> for OracleValue {
+    fn from(s: String) -> Self {
+        OracleValue::String(s)
+    }
+}
+
+impl From<Vec<u8>> for OracleValue {
+    fn from(bytes: Vec<u8>) -> Self {
+        OracleValue::Bytes(bytes)
+    }
+}
+
+impl From<BlockHash> for OracleValue {
+    fn from(hash: BlockHash) -> Self {
+        OracleValue::Hash(*hash.as_bytes())
+    }
+}
+
+impl From<Txid> for OracleValue {
+    fn from(hash: Txid) -> Self {
+        OracleValue::Hash(*hash.as_bytes())
+    }
+}
+
+macro_rules! oracle_value_from {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for OracleValue {
+                fn from(n: $t) -> Self {
+                    OracleValue::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+oracle_value_from!(Int, i64, i8, i16, i32, i64);
+oracle_value_from!(UInt, u64, u8, u16, u32, u64);
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use types::address::AddressUtxos;
-use bitcoin::util::hash::Sha256dHash;
-use ::{ApiError, TransactionId};
+use tx::Selection;
+use ::{Amount, ApiError, Txid};
 use std::iter::FromIterator;
 use types::arguments::address::Address;
 
@@ -13,9 +13,9 @@ impl CreateRawTransactionInputs {
         CreateRawTransactionInputs(Vec::new())
     }
 
-    pub fn add(&mut self, txid: &str, vout: u32) {
+    pub fn add(&mut self, txid: Txid, vout: u32) {
         let input = Input {
-            txid: txid.to_owned(),
+            txid,
             vout
         };
 
@@ -28,7 +28,7 @@ impl From<&AddressUtxos> for CreateRawTransactionInputs {
         let mut set = vec![];
         for utxo in &utxo_set.0 {
             set.push(Input {
-                txid: utxo.txid.clone(),
+                txid: utxo.txid,
                 vout: utxo.output_index
             })
         }
@@ -37,49 +37,63 @@ impl From<&AddressUtxos> for CreateRawTransactionInputs {
     }
 }
 
+impl From<&Selection> for CreateRawTransactionInputs {
+    fn from(selection: &Selection) -> Self {
+        let mut set = vec![];
+        for utxo in &selection.inputs {
+            set.push(Input {
+                txid: utxo.txid,
+                vout: utxo.vout
+            })
+        }
+
+        CreateRawTransactionInputs(set)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct Input {
-    txid: String,
+    txid: Txid,
     vout: u32
 }
 
 
 
 #[derive(Debug, Deserialize, Serialize)]
-pub struct CreateRawTransactionOutputs(HashMap<Address, f64>);
+pub struct CreateRawTransactionOutputs(HashMap<Address, Amount>);
 
 impl CreateRawTransactionOutputs {
     pub fn new() -> Self {
         CreateRawTransactionOutputs(HashMap::new())
     }
 
-    pub fn add(&mut self, address: &Address, amount: f64) {
+    pub fn add(&mut self, address: &Address, amount: Amount) {
         self.0.insert(address.to_owned(), amount);
     }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-pub struct SendManyAmounts(HashMap<String, f64>);
+pub struct SendManyAmounts(HashMap<String, Amount>);
 
 impl SendManyAmounts {
     pub fn new() -> Self {
         SendManyAmounts(HashMap::new())
     }
 
-    pub fn add(&mut self, address: &str, amount: f64) {
+    pub fn add(&mut self, address: &str, amount: Amount) {
         self.0.insert(address.to_owned(), amount);
     }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct P2SHInput {
-    pub txid: TransactionId,
+    pub txid: Txid,
     pub vout: u32,
     #[serde(rename = "scriptPubKey")]
     pub script_pub_key: String,
     #[serde(rename = "redeemScript")]
     pub redeem_script: Option<String>, // is hex hash
-    pub amount: f64,
+    pub amount: Amount,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -137,11 +151,10 @@ impl From<&AddressUtxos> for P2SHInputSetBuilder {
         let mut set = vec![];
         for utxo in &utxo_set.0 {
             set.push(P2SHInput {
-                // todo the unwrap here is ugly, but needed:
-                txid: TransactionId::from(Sha256dHash::from_hex(&utxo.txid).unwrap()),
+                txid: utxo.txid,
                 vout: utxo.output_index,
                 script_pub_key: utxo.script.clone(),
-                amount: (utxo.satoshis as f64 / 100_000_000.0),
+                amount: Amount::from_sat(utxo.satoshis as i64),
                 redeem_script: None,
             })
         }
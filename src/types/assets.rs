@@ -0,0 +1,192 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use Amount;
+use Txid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    /// Coins offered for tokens, made with `tokenbid`.
+    Bid,
+    /// Tokens offered for coins, made with `tokenask`.
+    Ask,
+}
+
+/// An open order of the assets DEX, as listed by `tokenorders` and `mytokenorders`.
+///
+/// komodod lists the amounts of bids and asks in different fields and units. Here `tokens` is always the
+/// number of tokens and `coins` the coins they are traded for. A partly filled order holds what is left.
+///
+/// For an order whose price is not positive komodod leaves out what it requires and its price, so the
+/// `tokens` of such a bid, the `coins` of such an ask and the `price` are `None`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TokenOrder {
+    pub side: OrderSide,
+    /// `b` or `s` for a new bid or ask, `B` or `S` for what is left of one after a fill.
+    pub funcid: String,
+    pub txid: Txid,
+    pub vout: u32,
+    pub tokenid: Option<Txid>,
+    /// The CC address of the pubkey that placed the order.
+    pub origaddress: Option<String>,
+    pub tokens: Option<u64>,
+    pub coins: Option<Amount>,
+    /// The price of a single token.
+    pub price: Option<Amount>,
+}
+
+/// A `TokenOrder` as komodod prints it. Most numbers are strings.
+#[derive(Deserialize)]
+struct RawTokenOrder {
+    funcid: String,
+    txid: Txid,
+    vout: u32,
+    amount: Value,
+    tokenid: Option<Txid>,
+    origaddress: Option<String>,
+    totalrequired: Option<Value>,
+    price: Option<Amount>,
+}
+
+fn tokens<E: de::Error>(value: &Value) -> Result<u64, E> {
+    let tokens = match *value {
+        Value::Number(ref number) => number.as_u64(),
+        Value::String(ref string) => string.parse().ok(),
+        _ => None,
+    };
+
+    tokens.ok_or_else(|| E::custom(format!("invalid number of tokens: {}", value)))
+}
+
+fn coins<E: de::Error>(value: &Value) -> Result<Amount, E> {
+    serde_json::from_value(value.clone()).map_err(E::custom)
+}
+
+impl<'de> Deserialize<'de> for TokenOrder {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        let raw = RawTokenOrder::deserialize(deserializer)?;
+
+        // bids hold coins and require tokens, asks hold tokens and require coins
+        let required = raw.totalrequired.as_ref();
+        let (side, tokens, coins) = match raw.funcid.as_str() {
+            "b" | "B" => (OrderSide::Bid, required.map(tokens).transpose()?, Some(coins(&raw.amount)?)),
+            "s" | "S" => (OrderSide::Ask, Some(tokens(&raw.amount)?), required.map(coins).transpose()?),
+            other => return Err(de::Error::custom(format!("unknown order funcid {}", other))),
+        };
+
+        Ok(TokenOrder {
+            side,
+            funcid: raw.funcid,
+            txid: raw.txid,
+            vout: raw.vout,
+            tokenid: raw.tokenid,
+            origaddress: raw.origaddress,
+            tokens,
+            coins,
+            price: raw.price,
+        })
+    }
+}
+
+/// The open orders of a single token, best price first.
+#[derive(Debug, Default, Clone)]
+pub struct TokenOrders {
+    /// Highest price first.
+    pub bids: Vec<TokenOrder>,
+    /// Lowest price first.
+    pub asks: Vec<TokenOrder>,
+}
+
+impl TokenOrders {
+    pub fn best_bid(&self) -> Option<&TokenOrder> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&TokenOrder> {
+        self.asks.first()
+    }
+
+    /// The lowest ask minus the highest bid, `None` unless there are both.
+    pub fn spread(&self) -> Option<Amount> {
+        let bid = self.best_bid()?.price?;
+        let ask = self.best_ask()?.price?;
+
+        ask.checked_sub(bid)
+    }
+}
+
+/// The open orders of `tokenorders` grouped by token id.
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate komodo_rpc_client;
+/// # use komodo_rpc_client::{Amount, OrderBook, TokenOrder};
+/// # fn main() {
+/// let tokenid = "1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864";
+/// let orders: Vec<TokenOrder> = serde_json::from_value(json!([
+///     {"funcid": "b", "txid": "9b4c1a6a1a0e7d7a4d4b6b3e0f5d9f2c8e6a5b3c1d0e9f8a7b6c5d4e3f2a1b0c", "vout": 0,
+///      "amount": "1.00000000", "bidamount": "1.00000000", "tokenid": tokenid, "totalrequired": 10, "price": "0.10000000"},
+///     {"funcid": "s", "txid": "4e5d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d", "vout": 0,
+///      "amount": "10", "askamount": "10", "tokenid": tokenid, "totalrequired": "1.20000000", "price": "0.12000000"}
+/// ])).unwrap();
+///
+/// let book = OrderBook::from(orders);
+/// let token = book.get(&tokenid.parse().unwrap()).unwrap();
+/// assert_eq!(token.spread(), Some("0.02".parse::<Amount>().unwrap()));
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct OrderBook {
+    tokens: HashMap<Txid, TokenOrders>,
+}
+
+impl OrderBook {
+    /// The orders of `tokenid`, `None` if it has no open orders.
+    pub fn get(&self, tokenid: &Txid) -> Option<&TokenOrders> {
+        self.tokens.get(tokenid)
+    }
+
+    /// The tokens with open orders, in no particular order.
+    pub fn tokenids(&self) -> impl Iterator<Item = &Txid> {
+        self.tokens.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+/// Orders without a token id or a price are left out.
+impl From<Vec<TokenOrder>> for OrderBook {
+    fn from(orders: Vec<TokenOrder>) -> Self {
+        let mut tokens: HashMap<Txid, TokenOrders> = HashMap::new();
+
+        for order in orders {
+            let tokenid = match order.tokenid {
+                Some(tokenid) if order.price.is_some() => tokenid,
+                _ => continue,
+            };
+
+            let book = tokens.entry(tokenid).or_default();
+            match order.side {
+                OrderSide::Bid => book.bids.push(order),
+                OrderSide::Ask => book.asks.push(order),
+            }
+        }
+
+        for book in tokens.values_mut() {
+            book.bids.sort_by_key(|order| Reverse(order.price));
+            book.asks.sort_by_key(|order| order.price);
+        }
+
+        OrderBook {
+            tokens,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
+use Amount;
 use BlockHash;
-use TransactionId;
+use Txid;
 
 #[derive(Debug, Deserialize)]
 pub struct Block {
@@ -12,12 +13,12 @@ pub struct Block {
     pub version: u32,
     pub merkleroot: String,
     pub segid: i32,
-    pub tx: Vec<TransactionId>,
+    pub tx: Vec<Txid>,
     pub time: u64,
     pub nonce: String,
     pub solution: String,
     pub bits: String,
-    pub difficulty: u64,
+    pub difficulty: f64,
     pub chainwork: String,
     pub anchor: String,
     #[serde(rename="valuePools")]
@@ -108,22 +109,22 @@ pub struct ValuePool {
     pub id: String,
     pub monitored: bool,
     #[serde(rename="chainValue")]
-    pub chain_value: f32,
+    pub chain_value: Amount,
     #[serde(rename="chainValueZat")]
     pub chain_value_zat: u64,
     #[serde(rename="valueDelta")]
-    pub value_delta: Option<f32>, // applies only to `getblock`
+    pub value_delta: Option<Amount>, // applies only to `getblock`
     #[serde(rename="valueDeltaZat")]
-    pub value_delta_zat: Option<u64>,
+    pub value_delta_zat: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct ChainTips(Vec<ChainTip>);
+pub struct ChainTips(pub Vec<ChainTip>);
 
 #[derive(Debug, Deserialize)]
 pub struct ChainTip {
     pub height: u64,
-    pub hash: String,
+    pub hash: BlockHash,
     pub branchlen: u32,
     pub status: ChainTipStatus
 }
@@ -146,9 +147,9 @@ pub struct Coinsupply {
     pub result: String,
     pub coin: String,
     pub height: u32,
-    pub supply: f64,
-    pub zfunds: f64,
-    pub total: f64,
+    pub supply: Amount,
+    pub zfunds: Amount,
+    pub total: Amount,
 }
 
 #[derive(Debug, Deserialize)]
@@ -159,20 +160,20 @@ pub struct MempoolInfo {
 }
 
 #[derive(Debug, Deserialize)]
-pub struct RawMempool(Vec<String>);
+pub struct RawMempool(pub Vec<Txid>);
 
 #[derive(Debug, Deserialize)]
-pub struct RawMempoolVerbose(HashMap<String, RawMempoolTransactionInfo>);
+pub struct RawMempoolVerbose(pub HashMap<Txid, RawMempoolTransactionInfo>);
 
 #[derive(Debug, Deserialize)]
 pub struct RawMempoolTransactionInfo {
     pub size: u32,
-    pub fee: f32,
+    pub fee: Amount,
     pub time: u32,
     pub height: u32,
     pub startingpriority: f64,
     pub currentpriority: f64,
-    pub depends: Vec<String>, // this either returns an empty array or an array with txids
+    pub depends: Vec<Txid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -180,7 +181,7 @@ pub struct TxOut {
     pub bestblock: BlockHash,
     pub confirmations: u32,
     pub rawconfirmations: u32,
-    pub value: f64,
+    pub value: Amount,
     #[serde(rename="scriptPubKey")]
     pub script_pubkey: ScriptPubKey,
     pub version: u32,
@@ -192,9 +193,10 @@ pub struct ScriptPubKey {
     pub asm: String,
     pub hex: String,
     #[serde(rename="reqSigs")]
-    pub req_sigs: u32,
+    pub req_sigs: Option<u32>, // nulldata and nonstandard scripts have no signers
     #[serde(rename="type")]
     pub script_type: String,
+    #[serde(default)]
     pub addresses: Vec<String>
 }
 
@@ -0,0 +1,133 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use ApiError;
+use ClientError;
+use SignedRawTransaction;
+
+/// The result of a CC RPC. These RPCs do not fail with a RPC error, but answer with
+/// `{"result": "error", "error": "..."}`, which becomes an `ApiError::CC`.
+pub(crate) fn cc_result<R: DeserializeOwned>(response: Value) -> Result<R, ApiError> {
+    if response["result"] == "error" {
+        let message = match response["error"] {
+            Value::String(ref message) => message.clone(),
+            Value::Null => String::from("unknown error"),
+            ref other => other.to_string(),
+        };
+
+        return Err(ApiError::CC(message));
+    }
+
+    serde_json::from_value(response).map_err(|err| ApiError::Client(ClientError::Json(err)))
+}
+
+/// What went wrong in a CC RPC, recognized from the message of an `ApiError::CC`, see `ApiError::cc_error_kind`.
+///
+/// CC modules report errors as free text. Only the messages listed for each module below are recognized, and
+/// only as a whole; every other message is `Other`. The message itself stays in the `ApiError::CC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CCErrorKind {
+    /// The wallet, or the funds of a contract such as a channel, cannot pay for the transaction.
+    InsufficientFunds,
+    /// A funding plan or token was not found.
+    NotFound,
+    /// It is too early, such as for unlocking rewards that do not yet pay more than the fee.
+    NotReady,
+    /// A parameter such as an amount, odds or name was rejected.
+    InvalidParameter,
+    Other,
+}
+
+/// The messages of the CC RPCs of a module, and their kinds. `{}` stands for a value komodod formats into the
+/// message, which is not empty.
+type Messages = &'static [(&'static str, CCErrorKind)];
+
+/// The messages of `AddNormalinputs` and `AddCCinputs` failing, which most modules report.
+const COMMON_MESSAGES: Messages = &[
+    ("error adding normal inputs", CCErrorKind::InsufficientFunds),
+    ("error adding CC inputs", CCErrorKind::InsufficientFunds),
+];
+
+const DICE_MESSAGES: Messages = &[
+    ("amount and odds must be positive", CCErrorKind::InvalidParameter),
+    ("bet must be positive", CCErrorKind::InvalidParameter),
+    ("odds must be between 2 and 9999", CCErrorKind::InvalidParameter),
+    ("Dice plan {} illegal bet {}: minbet {} maxbet {} or odds {} vs max.{}", CCErrorKind::InvalidParameter),
+    ("Dice plan {} already exists", CCErrorKind::InvalidParameter),
+    ("Diceinit error in bet, is your transaction confirmed?", CCErrorKind::NotFound),
+    ("Diceinit error in finish, is your transaction confirmed?", CCErrorKind::NotFound),
+    ("couldnt create dice bet transaction. make sure your address has funds", CCErrorKind::InsufficientFunds),
+];
+
+const REWARDS_MESSAGES: Messages = &[
+    ("Rewards plan {} doesnt exist", CCErrorKind::NotFound),
+    ("Rewards plan {} already exists", CCErrorKind::InvalidParameter),
+    ("reward {} is <= the transaction fee", CCErrorKind::NotReady),
+];
+
+const TOKENS_MESSAGES: Messages = &[
+    ("cant find tokenid", CCErrorKind::NotFound),
+    ("Token name must not be empty", CCErrorKind::InvalidParameter),
+    ("Token name must be up to 32 characters", CCErrorKind::InvalidParameter),
+    ("Token description must be <= 4096 characters", CCErrorKind::InvalidParameter),
+    ("Token supply must be positive", CCErrorKind::InvalidParameter),
+    ("amount must be positive", CCErrorKind::InvalidParameter),
+];
+
+const MESSAGES: &[Messages] = &[COMMON_MESSAGES, DICE_MESSAGES, REWARDS_MESSAGES, TOKENS_MESSAGES];
+
+impl CCErrorKind {
+    pub fn from_message(message: &str) -> Self {
+        // komodod ends some messages with a newline
+        let message = message.trim_end();
+
+        MESSAGES.iter()
+            .flat_map(|messages| messages.iter())
+            .find(|&&(pattern, _)| matches_message(pattern, message))
+            .map_or(CCErrorKind::Other, |&(_, kind)| kind)
+    }
+}
+
+/// `message` is `pattern` with a value that is not empty in place of each `{}`.
+fn matches_message(pattern: &str, message: &str) -> bool {
+    let mut parts = pattern.split("{}");
+    let mut rest = match parts.next().and_then(|first| message.strip_prefix(first)) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+
+    for (i, part) in parts.iter().enumerate() {
+        // the value is at least one character
+        let value = match rest.chars().next() {
+            Some(c) => c.len_utf8(),
+            None => return false,
+        };
+        if i == parts.len() - 1 {
+            return rest[value..].ends_with(part);
+        }
+        match rest[value..].find(part) {
+            Some(at) => rest = &rest[value + at + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.is_empty()
+}
+
+/// A transaction made by a CC RPC such as `tokencreate`. komodod has signed it with the key of its
+/// `-pubkey`, but not sent it.
+#[derive(Deserialize, Debug)]
+pub(crate) struct CCTransaction {
+    hex: String,
+}
+
+impl From<CCTransaction> for SignedRawTransaction {
+    fn from(tx: CCTransaction) -> Self {
+        SignedRawTransaction {
+            hex: tx.hex,
+            complete: true,
+            errors: None,
+        }
+    }
+}
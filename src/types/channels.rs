@@ -0,0 +1,197 @@
+use crypto::digest::Digest;
+use crypto::sha2::Sha256;
+use serde::de::{self, Deserialize, Deserializer};
+use serde_json::Value;
+use ChannelSecret;
+use Txid;
+
+/// The hash chain of a payment channel.
+///
+/// The payer starts from a random `seed` and hashes it `numpayments` times with SHA-256. `channelsopen` commits
+/// to the last hash, the `hashchain`. Every payment reveals the link before the one revealed last: after `paid`
+/// payments that is the seed hashed `numpayments - paid` times, and hashing it `paid` times gives the hashchain.
+/// So the payee, or anyone, can check a secret against the hashchain without being able to make the next one.
+///
+/// komodod derives the seed of the channels it opens itself and finds the secrets of its payments, so it only
+/// needs a secret in `channels_payment` for a channel opened elsewhere.
+///
+/// ```
+/// # use komodo_rpc_client::{ChannelSecret, HashChain};
+/// let chain = HashChain::new(ChannelSecret::from_bytes([7; 32]), 10);
+///
+/// let secret = chain.secret(3).unwrap();
+/// assert!(HashChain::verify(&chain.hashchain(), &secret, 3));
+/// assert!(!HashChain::verify(&chain.hashchain(), &secret, 4));
+/// assert!(chain.secret(11).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashChain {
+    seed: ChannelSecret,
+    numpayments: u32,
+}
+
+impl HashChain {
+    pub fn new(seed: ChannelSecret, numpayments: u32) -> Self {
+        HashChain {
+            seed,
+            numpayments,
+        }
+    }
+
+    pub fn numpayments(&self) -> u32 {
+        self.numpayments
+    }
+
+    /// The end of the chain, which the channel is opened with.
+    pub fn hashchain(&self) -> ChannelSecret {
+        hash_times(&self.seed, self.numpayments)
+    }
+
+    /// The secret that proves `paid` payments in total, `None` if the channel has fewer payments.
+    pub fn secret(&self, paid: u32) -> Option<ChannelSecret> {
+        if paid > self.numpayments {
+            return None;
+        }
+
+        Some(hash_times(&self.seed, self.numpayments - paid))
+    }
+
+    /// Whether `secret` proves `paid` payments of the channel opened with `hashchain`.
+    pub fn verify(hashchain: &ChannelSecret, secret: &ChannelSecret, paid: u32) -> bool {
+        hash_times(secret, paid) == *hashchain
+    }
+}
+
+fn hash_times(secret: &ChannelSecret, times: u32) -> ChannelSecret {
+    let mut bytes = *secret.as_bytes();
+    let mut sha256 = Sha256::new();
+
+    for _ in 0..times {
+        sha256.reset();
+        sha256.input(&bytes);
+        sha256.result(&mut bytes);
+    }
+
+    ChannelSecret::from_bytes(bytes)
+}
+
+/// A number that komodod prints as a number or as a string.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(ref number) => number.as_u64(),
+        Value::String(ref string) => string.parse().ok(),
+        _ => None,
+    }.ok_or_else(|| de::Error::custom("expected a whole number"))
+}
+
+fn optional_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    number(deserializer).map(Some)
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ChannelInfo {
+    #[serde(rename = "Channel CC address")]
+    pub cc_address: String,
+    #[serde(rename = "Destination address")]
+    pub destination: String,
+    #[serde(rename = "Number of payments")]
+    pub numpayments: u32,
+    /// The amount of a single payment, in satoshis or, for a channel of tokens, in tokens.
+    #[serde(rename = "Denomination (satoshi)", alias = "Denomination (token)", deserialize_with = "number")]
+    pub denomination: u64,
+    #[serde(rename = "Token id")]
+    pub tokenid: Option<Txid>,
+    /// Oldest first, starting with the `Open` transaction.
+    #[serde(rename = "Transactions")]
+    pub transactions: Vec<ChannelTransaction>,
+}
+
+impl ChannelInfo {
+    /// The last payment, `None` if nothing has been paid yet.
+    pub fn last_payment(&self) -> Option<&ChannelTransaction> {
+        self.transactions.iter().rev().find(|tx| tx.kind == ChannelTxKind::Payment)
+    }
+
+    /// The payments that can still be made, unless the channel is closed.
+    pub fn payments_left(&self) -> u32 {
+        match self.last_payment() {
+            Some(payment) => payment.payments_left.unwrap_or(0),
+            None => self.numpayments,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.transactions.iter().any(|tx| tx.kind == ChannelTxKind::Close || tx.kind == ChannelTxKind::Refund)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelTxKind {
+    Open,
+    Payment,
+    Close,
+    Refund,
+}
+
+/// A transaction of a channel. komodod names the field with its txid after its kind.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ChannelTransaction {
+    pub kind: ChannelTxKind,
+    pub txid: Txid,
+    /// Payments: how many payments this one is worth.
+    pub payments: Option<u32>,
+    /// Payments and refunds, in satoshis or tokens.
+    pub amount: Option<u64>,
+    /// Payments: the address of the payee.
+    pub destination: Option<String>,
+    /// Payments: the secret that proves all payments up to and including this one, see `HashChain`.
+    pub secret: Option<ChannelSecret>,
+    pub payments_left: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RawChannelTransaction {
+    #[serde(rename = "Open")]
+    open: Option<Txid>,
+    #[serde(rename = "Payment")]
+    payment: Option<Txid>,
+    #[serde(rename = "Close")]
+    close: Option<Txid>,
+    #[serde(rename = "Refund")]
+    refund: Option<Txid>,
+    #[serde(rename = "Number of payments")]
+    payments: Option<u32>,
+    #[serde(rename = "Amount", default, deserialize_with = "optional_number")]
+    amount: Option<u64>,
+    #[serde(rename = "Destination")]
+    destination: Option<String>,
+    #[serde(rename = "Revealed secret")]
+    secret: Option<ChannelSecret>,
+    #[serde(rename = "Payments left")]
+    payments_left: Option<u32>,
+}
+
+impl<'de> Deserialize<'de> for ChannelTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        let raw = RawChannelTransaction::deserialize(deserializer)?;
+
+        let (kind, txid) = match (raw.open, raw.payment, raw.close, raw.refund) {
+            (Some(txid), None, None, None) => (ChannelTxKind::Open, txid),
+            (None, Some(txid), None, None) => (ChannelTxKind::Payment, txid),
+            (None, None, Some(txid), None) => (ChannelTxKind::Close, txid),
+            (None, None, None, Some(txid)) => (ChannelTxKind::Refund, txid),
+            _ => return Err(de::Error::custom("expected one of Open, Payment, Close or Refund")),
+        };
+
+        Ok(ChannelTransaction {
+            kind,
+            txid,
+            payments: raw.payments,
+            amount: raw.amount,
+            destination: raw.destination,
+            secret: raw.secret,
+            payments_left: raw.payments_left,
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use serde::de::{self, Deserialize, Deserializer};
+use Amount;
+use Txid;
+
+/// A dice house, made with `dicefund`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DiceInfo {
+    pub fundingtxid: Txid,
+    pub name: String,
+    /// The name, packed into a number.
+    pub sbits: i64,
+    pub minbet: Amount,
+    pub maxbet: Amount,
+    pub maxodds: u32,
+    /// After this many blocks without `dicefinish`, the bettor can claim the bet as won.
+    pub timeoutblocks: u32,
+    /// The coins the house can pay out.
+    pub funding: Amount,
+}
+
+/// The answer of `dicestatus`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub enum DiceStatus {
+    /// The bet won this amount.
+    Win(Amount),
+    Loss,
+    /// The house has not finished the bet yet.
+    Pending,
+    /// Asked without a bet: the number of pending bets that were finished.
+    Finalized(u64),
+    /// Asked without a bet: there was nothing to finish.
+    NoPendingBets,
+}
+
+#[derive(Deserialize)]
+struct RawDiceStatus {
+    status: String,
+    won: Option<Amount>,
+    n: Option<u64>,
+}
+
+impl<'de> Deserialize<'de> for DiceStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        let raw = RawDiceStatus::deserialize(deserializer)?;
+
+        let status = match raw.status.as_str() {
+            "win" => DiceStatus::Win(raw.won.ok_or_else(|| de::Error::missing_field("won"))?),
+            "loss" => DiceStatus::Loss,
+            "bet still pending" => DiceStatus::Pending,
+            "finalized" => DiceStatus::Finalized(raw.n.unwrap_or(0)),
+            "no pending bets" => DiceStatus::NoPendingBets,
+            other => return Err(de::Error::custom(format!("unknown dice status {}", other))),
+        };
+
+        Ok(status)
+    }
+}
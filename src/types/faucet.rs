@@ -0,0 +1,8 @@
+use Amount;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FaucetInfo {
+    pub name: String,
+    /// The coins left in the faucet.
+    pub funding: Amount,
+}
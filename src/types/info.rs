@@ -1,3 +1,7 @@
+use Amount;
+use BlockHash;
+use Txid;
+
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct Info {
     pub version: u32,
@@ -7,16 +11,16 @@ pub struct Info {
     pub notarized: u32,
     #[serde(rename = "prevMoMheight")]
     pub prev_mom_height: u32,
-    pub notarizedhash: String,
-    pub notarizedtxid: String,
+    pub notarizedhash: BlockHash,
+    pub notarizedtxid: Txid,
     pub notarizedtxid_height: String,
     pub notarized_confirms: u32,
     pub walletversion: u32,
-    pub balance: f32,
-    pub interest: Option<f32>,
+    pub balance: Amount,
+    pub interest: Option<Amount>,
     pub blocks: u32,
     pub longestchain: u32,
-    pub timeoffset: u32,
+    pub timeoffset: i64,
     pub tiptime: u32,
     pub connections: u32,
     pub proxy: String,
@@ -24,11 +28,11 @@ pub struct Info {
     pub testnet: bool,
     pub keypoololdest: u32,
     pub keypoolsize: u32,
-    pub paytxfee: f32,
-    pub relayfee: f32,
+    pub paytxfee: Amount,
+    pub relayfee: Amount,
     pub errors: String,
     pub name: String,
-    pub sapling: u64,
+    pub sapling: i64,
     pub p2pport: u32,
     pub rpcport: u32,
 }
@@ -36,22 +40,22 @@ pub struct Info {
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct WalletInfo {
     pub walletversion: u32,
-    pub balance: f64,
-    pub unconfirmed_balance: f64,
-    pub immature_balance: f64,
+    pub balance: Amount,
+    pub unconfirmed_balance: Amount,
+    pub immature_balance: Amount,
     pub txcount: u32,
     pub keypoololdest: u64,
     pub keypoolsize: u32,
-    pub paytxfee: f32,
+    pub paytxfee: Amount,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct TxOutSetInfo {
     pub height: u32,
-    pub bestblock: String,
+    pub bestblock: BlockHash,
     pub transactions: u32,
     pub txouts: u32,
-    pub bytes_serialized: u32,
+    pub bytes_serialized: u64,
     pub hash_serialized: String,
-    pub total_amount: f64,
+    pub total_amount: Amount,
 }
\ No newline at end of file
@@ -1,17 +1,21 @@
+use Amount;
+use BlockHash;
+use Txid;
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct BlockSubsidy {
-    pub miner: f64,
+    pub miner: Amount,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct BlockTemplate {
     pub capabilities: Vec<String>,
     pub version: u16,
-    pub previousblockhash: String, // todo hash
-    pub finalsaplingroothash: String, // todo hash
+    pub previousblockhash: BlockHash,
+    pub finalsaplingroothash: String, // a Sapling note commitment tree root, not a double SHA-256
     pub transactions: Vec<IncludedTransaction>,
     pub coinbasetxn: CoinbaseTransaction,
-    pub longpollid: String, // todo hash
+    pub longpollid: String, // the tip's hash followed by a counter
     pub target: String,
     pub mintime: u64,
     pub mutable: Vec<String>,
@@ -26,7 +30,7 @@ pub struct BlockTemplate {
 #[derive(Deserialize, Serialize, Debug)]
 pub struct CoinbaseTransaction {
     pub data: String,
-    pub hash: String,
+    pub hash: Txid,
     pub depends: Vec<u64>,
     pub fee: i64,
     pub sigops: u64,
@@ -37,7 +41,7 @@ pub struct CoinbaseTransaction {
 #[derive(Deserialize, Serialize, Debug)]
 pub struct IncludedTransaction {
     pub data: String,
-    pub hash: String,
+    pub hash: Txid,
     pub depends: Vec<u64>,
     pub fee: u64,
     pub sigops: u64,
@@ -65,7 +69,7 @@ pub struct MiningInfo {
     pub chain: String,
     pub staking: bool,
     pub generate: bool,
-    pub numthreads: u8
+    pub numthreads: i32 // -1 if mining is off
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -1,3 +1,4 @@
+mod amount;
 mod transaction;
 mod info;
 mod blockchain;
@@ -7,10 +8,22 @@ mod script;
 mod mining;
 mod network;
 mod shielded;
+mod cc;
+mod tokens;
+mod assets;
+mod oracles;
+mod channels;
+mod faucet;
+mod rewards;
+mod dice;
 
 pub mod arguments;
 
+pub use self::cc::CCErrorKind;
+pub(crate) use self::cc::{cc_result, CCTransaction};
+
 pub use self::{
+    amount::*,
     info::*,
     transaction::*,
     blockchain::*,
@@ -20,4 +33,11 @@ pub use self::{
     mining::*,
     network::*,
     shielded::*,
+    tokens::*,
+    assets::*,
+    oracles::*,
+    channels::*,
+    faucet::*,
+    rewards::*,
+    dice::*,
 };
@@ -1,3 +1,5 @@
+use Amount;
+
 #[derive(Deserialize, Serialize, Debug)]
 pub enum AddNodeCommand {
     #[serde(rename = "add")]
@@ -44,7 +46,7 @@ pub struct NetworkInfo {
     pub timeoffset: i32,
     pub connections: u32,
     pub networks: Vec<NetworkVersion>,
-    pub relayfee: f64,
+    pub relayfee: Amount,
     pub localaddresses: Vec<Option<LocalAddress>>,
     pub warnings: String
 }
@@ -69,7 +71,7 @@ pub struct LocalAddress {
 pub struct Peer {
     pub id: u32,
     pub addr: String,
-    pub addrlocal: String,
+    pub addrlocal: Option<String>, // not known for every peer
     pub services: String,
     pub lastsend: u64,
     pub lastrecv: u64,
@@ -81,10 +83,10 @@ pub struct Peer {
     pub version: u32,
     pub subver: String,
     pub inbound: bool,
-    pub startingheight: u64,
+    pub startingheight: i64, // -1 if unknown
     pub banscore: u8,
-    pub synced_headers: u64,
-    pub synced_blocks: u64,
+    pub synced_headers: i64,
+    pub synced_blocks: i64,
     pub inflight: Vec<Option<u64>>,
     pub whitelisted: bool
 }
@@ -0,0 +1,82 @@
+use serde::de::{Deserialize, Deserializer};
+use Amount;
+use Txid;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OracleInfo {
+    pub txid: Txid,
+    pub name: String,
+    pub description: String,
+    /// See `OracleFormat`.
+    pub format: String,
+    pub marker: String,
+    pub registered: Vec<OraclePublisher>,
+}
+
+/// A publisher registered with `oraclesregister`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OraclePublisher {
+    pub publisher: String,
+    /// The address that holds the baton, the UTXO each data transaction of the publisher spends.
+    pub baton: String,
+    pub batontxid: Txid,
+    pub lifetime: Amount,
+    /// What subscribers paid and is left for data fees.
+    pub funds: Amount,
+    /// Paid by the subscribers for each data transaction.
+    pub datafee: Amount,
+}
+
+/// The data of an oracle, decoded by komodod with the format of the oracle.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OracleSample {
+    /// The data transaction. Absent in the answer of `oraclessample`, which is asked for it.
+    pub txid: Option<Txid>,
+    /// A value for every character of the format.
+    pub data: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SampleData {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// komodod versions differ: a sample is an array of values, or an object with its txid and values.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawOracleSample {
+    Data(Vec<String>),
+    Object {
+        txid: Option<Txid>,
+        data: SampleData,
+    },
+}
+
+impl<'de> Deserialize<'de> for OracleSample {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        let sample = match RawOracleSample::deserialize(deserializer)? {
+            RawOracleSample::Data(data) => OracleSample {
+                txid: None,
+                data,
+            },
+            RawOracleSample::Object { txid, data } => OracleSample {
+                txid,
+                data: match data {
+                    SampleData::One(value) => vec![value],
+                    SampleData::Many(values) => values,
+                },
+            },
+        };
+
+        Ok(sample)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OracleSamples {
+    /// Newest first.
+    pub samples: Vec<OracleSample>,
+}
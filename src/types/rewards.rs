@@ -0,0 +1,32 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde_json::Value;
+use Amount;
+use Txid;
+
+/// A rewards plan of the Rewards CC module, made with `rewardscreatefunding`. Not to be confused with the
+/// KMD rewards of `komodo_interest`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RewardsInfo {
+    pub fundingtxid: Txid,
+    pub name: String,
+    /// The name, packed into a number.
+    pub sbits: i64,
+    /// The yearly rate in percent.
+    #[serde(rename = "APR", deserialize_with = "percentage")]
+    pub apr: f64,
+    pub minseconds: u64,
+    pub maxseconds: u64,
+    pub mindeposit: Amount,
+    /// The coins left to pay rewards from.
+    pub funding: Amount,
+    pub locked: Option<Amount>,
+}
+
+/// komodod prints the APR as a string with 8 decimals.
+fn percentage<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(ref number) => number.as_f64(),
+        Value::String(ref string) => string.parse().ok(),
+        _ => None,
+    }.ok_or_else(|| de::Error::custom("expected a percentage"))
+}
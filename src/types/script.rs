@@ -7,6 +7,7 @@ pub struct DecodedScript {
     pub req_sigs: Option<u16>,
     #[serde(rename = "type")]
     pub script_type: ScriptType,
+    #[serde(default)]
     pub addresses: Vec<Option<Address>>,
     pub p2sh: String,
 }
@@ -1,32 +1,17 @@
 use std::collections::HashMap;
 use arguments::address::Address;
-use serde::de::{self, Deserialize, Deserializer};
-use std::str::FromStr;
-use std::fmt::Display;
-use TransactionId;
+use Amount;
+use Txid;
 
 #[derive(Deserialize, Debug)]
-pub struct Balance(pub f64);
+pub struct Balance(pub Amount);
 
 #[derive(Debug, Deserialize)]
-pub struct TotalBalance {
-    #[serde(deserialize_with = "from_str")]
-    pub transparent: f64,
-    #[serde(deserialize_with = "from_str")]
-    pub interest: f64,
-    #[serde(deserialize_with = "from_str")]
-    pub private: f64,
-    #[serde(deserialize_with = "from_str")]
-    pub total: f64,
-}
-
-fn from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
-    where T: FromStr,
-          T::Err: Display,
-          D: Deserializer<'de>
-{
-    let s = String::deserialize(deserializer)?;
-    T::from_str(&s).map_err(de::Error::custom)
+pub struct TotalBalance { // komodod returns strings
+    pub transparent: Amount,
+    pub interest: Amount,
+    pub private: Amount,
+    pub total: Amount,
 }
 
 #[derive(Deserialize, Debug)]
@@ -37,33 +22,40 @@ pub struct Operation {
     pub id: String,
     pub status: String, // failed, cancelled or success
     pub creation_time: u64,
-    pub result: HashMap<String, String>,
-    pub execution_secs: f64,
+    pub result: Option<HashMap<String, String>>, // only present on success
+    pub error: Option<OperationError>, // only present on failure
+    pub execution_secs: Option<f64>,
     pub method: String,
     pub params: OperationParams,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct OperationParams {
-    pub fromaddress: Address,
-    pub amounts: Vec<Amount>,
-    pub minconf: u32,
-    pub fee: f64
+    pub fromaddress: Option<Address>,
+    pub amounts: Option<Vec<OperationAmount>>,
+    pub minconf: Option<u32>,
+    pub fee: Option<Amount>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OperationError {
+    pub code: i32,
+    pub message: String,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct Amount {
+pub struct OperationAmount {
     pub address: Address,
-    pub amount: f64
+    pub amount: Amount
 }
 
 #[derive(Deserialize, Debug)]
-pub struct ReceivedByAddress(pub Vec<Received>);
+pub struct ZReceivedByAddress(pub Vec<Received>);
 
 #[derive(Deserialize, Debug)]
 pub struct Received {
-    pub txid: TransactionId,
-    pub amount: f64,
+    pub txid: Txid,
+    pub amount: Amount,
     pub memo: String,
     pub outindex: u32,
     pub rawconfirmations: u32,
@@ -76,19 +68,19 @@ pub struct MergeResult {
     #[serde(rename = "remainingUTXOs")]
     pub remaining_utxos: u32,
     #[serde(rename = "remainingTransparentValue")]
-    pub remaining_transparent_value: f64,
+    pub remaining_transparent_value: Amount,
     #[serde(rename = "remainingNotes")]
     pub remaining_notes: u32,
     #[serde(rename = "remainingShieldedValue")]
-    pub remaining_shielded_value: f64,
+    pub remaining_shielded_value: Amount,
     #[serde(rename = "mergingUTXOs")]
     pub merging_utxos: u32,
     #[serde(rename = "mergingTransparentValue")]
-    pub merging_transparent_value: f64,
+    pub merging_transparent_value: Amount,
     #[serde(rename = "mergingNotes")]
     pub merging_notes: u32,
     #[serde(rename = "mergingShieldedValue")]
-    pub merging_shielded_value: f64,
+    pub merging_shielded_value: Amount,
     pub opid: String,
 }
 
@@ -97,10 +89,10 @@ pub struct ShieldResult {
     #[serde(rename = "remainingUTXOs")]
     pub remaining_utxos: u32,
     #[serde(rename = "remainingValue")]
-    pub remaining_value: u32,
+    pub remaining_value: Amount,
     #[serde(rename = "shieldingUTXOs")]
     pub shielding_utxos: u32,
     #[serde(rename = "shieldingValue")]
-    pub shielding_value: u32,
+    pub shielding_value: Amount,
     pub opid: String
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use Txid;
+
+/// The token balance of a pubkey, in tokens. A token is one satoshi of the supply it was created with.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TokenBalance {
+    /// The CC address of the pubkey, which starts with a C and does not validate as an `Address`.
+    #[serde(rename = "CCaddress")]
+    pub cc_address: String,
+    pub tokenid: Txid,
+    pub balance: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TokenInfo {
+    pub tokenid: Txid,
+    /// The pubkey that created the token.
+    pub owner: String,
+    pub name: String,
+    /// The number of tokens created.
+    pub supply: u64,
+    pub description: String,
+    /// The hex encoded non-fungible data, if any.
+    pub data: Option<String>,
+}
@@ -1,43 +1,30 @@
+use Amount;
+use ApiError;
 use BlockHash;
-use TransactionId;
+use Txid;
 use ScriptPubKey;
 
-use std::time::SystemTime;
+use tx::{komodo_lock_time, KomodoTransaction};
 use types::arguments::address::Address;
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SerializedRawTransaction(pub String);
 
 impl SerializedRawTransaction {
-
     /// This method sets the locktime of a transaction.
     /// A SerializedRawTransaction upon creation does not have a set locktime.
-    /// To earn KMD rewards, it must be set to the current time - 777 seconds in little endian hex.
-    /// The first 8 chars of the last 38 chars of the hex string is the place to set the locktime.
+    /// To earn KMD rewards, it must be set to the current time - 777 seconds.
     /// NOTE: this only applies to KMD, not its assetchains.
-    pub fn set_locktime(&mut self) {
-        let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap(); // todo return result
-        let hex_time = format!("{:x}", current_time.as_secs() - 120);
-
-        let mut rev_hexified_time = String::new();
-
-        // hex_time must be little endian
-        for i in (0..8).rev().step_by(2) {
-            rev_hexified_time.push_str(hex_time.get(i-1..i+1).unwrap());
-        }
-
-        let hex = self.0.clone();
-
-        let payload = &hex[0..hex.len() - 38];
-        let expiry = &hex[hex.len() - 30..hex.len() - 24];
+    pub fn set_locktime(&mut self) -> Result<(), ApiError> {
+        let mut tx = self.decode()?;
+        tx.lock_time = komodo_lock_time()?;
+        self.0 = tx.to_hex();
 
-        let mut result = String::new();
-        result.push_str(payload);
-        result.push_str(rev_hexified_time.as_str());
-        result.push_str(expiry);
-        result.push_str("000000000000000000000000");
+        Ok(())
+    }
 
-        self.0 = result;
+    pub fn decode(&self) -> Result<KomodoTransaction, ApiError> {
+        KomodoTransaction::from_hex(&self.0)
     }
 
     pub fn from_hex(hex: String) -> Self {
@@ -47,16 +34,16 @@ impl SerializedRawTransaction {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Transaction {
-    pub amount: f64,
+    pub amount: Amount,
     pub confirmations: u32,
     pub blockhash: Option<BlockHash>,
     /// Unix Timestamp
     pub blockindex: Option<u64>,
     /// Unix Timestamp
     pub blocktime: Option<u64>,
-    pub walletconflicts: Vec<TransactionId>,
+    pub walletconflicts: Vec<Txid>,
     pub expiryheight: u32,
-    pub txid: TransactionId,
+    pub txid: Txid,
     /// Unix Timestamp
     pub time: u64,
     /// Unix Timestamp
@@ -78,8 +65,8 @@ pub struct Detail {
     pub address: String,
     // send|receive|immature|generate|orphan TODO: Create enum if needed
     pub category: String,
-    pub amount: f64,
-    pub fee: Option<f64>,
+    pub amount: Amount,
+    pub fee: Option<Amount>,
     pub vout: u32,
     #[serde(rename = "involvesWatchonly")]
     pub involves_watchonly: Option<bool>,
@@ -91,7 +78,7 @@ pub struct Detail {
 pub struct RawTransaction {
     pub hex: Option<String>,
     pub overwintered: Option<bool>,
-    pub txid: String,
+    pub txid: Txid,
     pub size: Option<u32>,
     pub versiongroupid: Option<String>,
     pub version: u32,
@@ -108,10 +95,11 @@ pub struct RawTransaction {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Vin {
-    pub txid: TransactionId,
-    pub vout: u32,
+    pub coinbase: Option<String>, // only present in coinbase inputs, which have no txid, vout and scriptSig
+    pub txid: Option<Txid>,
+    pub vout: Option<u32>,
     #[serde(rename = "scriptSig")]
-    pub script_sig: ScriptSig,
+    pub script_sig: Option<ScriptSig>,
     pub sequence: u64,
 }
 
@@ -123,8 +111,8 @@ pub struct ScriptSig {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Vout {
-    pub value: f64,
-    pub interest: Option<f64>,
+    pub value: Amount,
+    pub interest: Option<Amount>,
     pub n: u32,
     #[serde(rename = "scriptPubKey")]
     pub script_pubkey: ScriptPubKey,
@@ -132,8 +120,8 @@ pub struct Vout {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct VJoinsplit {
-    pub vpub_old: f64,
-    pub vpub_new: f64,
+    pub vpub_old: Amount,
+    pub vpub_new: Amount,
     pub anchor: String,
     pub nullifiers: Vec<String>,
     pub commitments: Vec<String>,
@@ -161,7 +149,7 @@ impl SignedRawTransaction {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SignRawTransactionError {
-    pub txid: String,
+    pub txid: Txid,
     pub vout: u16,
     #[serde(rename = "scriptSig")]
     pub script_sig: String,
@@ -171,13 +159,13 @@ pub struct SignRawTransactionError {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct TransactionOutputDetail {
-    pub txid: TransactionId,
+    pub txid: Txid,
     pub vout: u32,
     #[serde(rename = "scriptPubKey")]
     pub script_pub_key: String,
     #[serde(rename = "redeemScript")]
     pub redeem_script: Option<String>, // is hex hash
-    pub amount: f64,
+    pub amount: Amount,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -198,13 +186,13 @@ pub enum SigHashType {
 
 #[derive(Deserialize, Debug)]
 pub struct Unspent {
-    pub txid: TransactionId,
-    pub vout: u8,
+    pub txid: Txid,
+    pub vout: u32,
     pub generated: bool,
     pub address: Address,
     pub account: Option<String>, //does not exist for an utxo in a change address
-    pub amount: f64,
-    pub interest: Option<f64>,
+    pub amount: Amount,
+    pub interest: Option<Amount>,
     #[serde(rename = "scriptPubKey")]
     pub script_pub_key: String,
     pub rawconfirmations: u64,
@@ -214,24 +202,24 @@ pub struct Unspent {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct LockedUnspent {
-    pub txid: TransactionId,
-    pub vout: u8
+    pub txid: Txid,
+    pub vout: u32
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ReceivedByAddress {
     pub address: Address,
     pub account: Option<String>, //accounts are deprecated
-    pub amount: f64,
+    pub amount: Amount,
     pub rawconfirmations: u64,
     pub confirmations: u64,
-    pub txids: Vec<TransactionId>,
+    pub txids: Vec<Txid>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct TxListSinceBlock {
     pub transactions: Vec<ListTransaction>,
-    pub lastblock: String,
+    pub lastblock: BlockHash,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -239,17 +227,17 @@ pub struct ListTransaction {
     pub account: String,
     pub address: Address,
     pub category: TxCategory,
-    pub amount: f64,
+    pub amount: Amount,
     pub vout: u32,
-    pub fee: Option<f64>,
+    pub fee: Option<Amount>,
     pub rawconfirmations: u64,
     pub confirmations: u64,
-    pub blockhash: String,
-    pub blockindex: u64,
-    pub blocktime: u64,
+    pub blockhash: Option<BlockHash>, // unconfirmed transactions are not in a block yet
+    pub blockindex: Option<u64>,
+    pub blocktime: Option<u64>,
     pub expiryheight: u64,
-    pub txid: TransactionId,
-    pub walletconflicts: Vec<Option<String>>,
+    pub txid: Txid,
+    pub walletconflicts: Vec<Txid>,
     pub time: u64,
     pub timereceived: u64,
     pub vjoinsplit: Vec<Option<VJoinsplit>>,
@@ -265,14 +253,20 @@ pub enum TxCategory {
     #[serde(rename = "receive")]
     Receive,
     #[serde(rename = "move")]
-    Move
+    Move,
+    #[serde(rename = "generate")]
+    Generate,
+    #[serde(rename = "immature")]
+    Immature,
+    #[serde(rename = "orphan")]
+    Orphan,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
-pub struct ListTransactions(Vec<ListTransaction>);
+pub struct ListTransactions(pub Vec<ListTransaction>);
 
 #[derive(Deserialize, Serialize, Debug)]
-pub struct ResentWalletTransactions(Vec<TransactionId>);
+pub struct ResentWalletTransactions(pub Vec<Txid>);
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SetPubkey {
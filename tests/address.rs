@@ -0,0 +1,130 @@
+extern crate komodo_rpc_client;
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::address::{Address, AddressKind, AddressParams};
+
+const P2PKH: &str = "REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4";
+const P2SH: &str = "bUy5shS9vfE27TzhnLAcqWZYRT5Wfcecb6";
+const SAPLING: &str = "zs1kfhjdt2fkj5hmtlgul27kegrm3rh6kuy5dazsg6nzn45s3zdd55wdr5hwxgj59nklmz3y2w77pp";
+
+#[test]
+fn parses_transparent_addresses() {
+    let address = Address::from(P2PKH).unwrap();
+    assert_eq!(address.kind(), Some(AddressKind::PubkeyHash));
+    assert_eq!(address.payload().len(), 20);
+    assert!(!address.is_shielded());
+    assert_eq!(address.to_string(), P2PKH);
+
+    let address = Address::from(P2SH).unwrap();
+    assert_eq!(address.kind(), Some(AddressKind::ScriptHash));
+    assert_eq!(address.payload().len(), 20);
+}
+
+#[test]
+fn parses_sapling_addresses() {
+    let address = Address::from(SAPLING).unwrap();
+    assert_eq!(address.kind(), Some(AddressKind::Sapling));
+    assert_eq!(address.payload().len(), 43);
+    assert!(address.is_shielded());
+}
+
+#[test]
+fn rejects_invalid_addresses() {
+    // a typo breaks the checksum
+    assert!(Address::from("REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP5").is_err());
+    assert!(Address::from("zs1kfhjdt2fkj5hmtlgul27kegrm3rh6kuy5dazsg6nzn45s3zdd55wdr5hwxgj59nklmz3y2w77pq").is_err());
+    // a valid bitcoin address has the wrong version byte
+    assert!(Address::from("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2").is_err());
+    // the Sapling testnet prefix
+    assert!(Address::from("ztestsapling1kfhjdt2fkj5hmtlgul27kegrm3rh6kuy5dazsg6nzn45s3zdd55wdr5hwxgj59nklmz3y2w77pp").is_err());
+    assert!(Address::from("").is_err());
+    assert!(Address::from("R").is_err());
+}
+
+#[test]
+fn roundtrips_payloads() {
+    let params = AddressParams::komodo();
+
+    let address = Address::from(P2PKH).unwrap();
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(address.payload());
+    assert_eq!(Address::from_pubkey_hash(&hash, &params), address);
+
+    let address = Address::from(P2SH).unwrap();
+    hash.copy_from_slice(address.payload());
+    assert_eq!(Address::from_script_hash(&hash, &params), address);
+
+    let address = Address::from(SAPLING).unwrap();
+    let mut payload = [0u8; 43];
+    payload.copy_from_slice(address.payload());
+    assert_eq!(Address::from_sapling_payload(&payload, &params).unwrap(), address);
+
+    // human readable parts that are empty, in upper case, or make the address longer than bech32 allows
+    for hrp in &["", "ZS", "z s", "zs-but-far-too-long"] {
+        let params = AddressParams { sapling_hrp: hrp.to_string(), ..AddressParams::komodo() };
+        assert!(Address::from_sapling_payload(&payload, &params).is_err());
+    }
+    let params = AddressParams { sapling_hrp: "ztestsapling".to_string(), ..AddressParams::komodo() };
+    assert!(Address::from_sapling_payload(&payload, &params).unwrap().to_string().starts_with("ztestsapling1"));
+}
+
+#[test]
+fn uses_custom_version_bytes() {
+    let params = AddressParams {
+        pubkey_hash: 0,
+        script_hash: 5,
+        sapling_hrp: "zs".to_string(),
+    };
+
+    let kmd = Address::from(P2PKH).unwrap();
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(kmd.payload());
+
+    let custom = Address::from_pubkey_hash(&hash, &params);
+    assert!(custom.to_string().starts_with('1'));
+    assert_eq!(Address::from_with_params(&custom.to_string(), &params).unwrap(), custom);
+    assert!(Address::from(&custom.to_string()).is_err());
+    assert!(Address::from_with_params(P2PKH, &params).is_err());
+}
+
+#[test]
+fn deserializes_addresses_of_any_chain() {
+    let address: Address = serde_json::from_str(&format!("\"{}\"", P2PKH)).unwrap();
+    assert_eq!(address.kind(), Some(AddressKind::PubkeyHash));
+    assert_eq!(serde_json::to_string(&address).unwrap(), format!("\"{}\"", P2PKH));
+
+    // an address of a chain with version byte 0, as an assetchain with custom prefixes has
+    let params = AddressParams {
+        pubkey_hash: 0,
+        script_hash: 5,
+        sapling_hrp: "zs".to_string(),
+    };
+    let address: Address = serde_json::from_str("\"1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2\"").unwrap();
+    assert_eq!(address.kind(), None);
+    assert!(address.payload().is_empty());
+    assert_eq!(address.validate(&params).unwrap().kind(), Some(AddressKind::PubkeyHash));
+    assert!(address.validate(&AddressParams::komodo()).is_err());
+
+    let typo: Address = serde_json::from_str("\"REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP5\"").unwrap();
+    assert_eq!(typo.kind(), None);
+    assert!(typo.validate(&AddressParams::komodo()).is_err());
+}
+
+#[test]
+fn hashes_like_it_compares() {
+    use std::collections::HashSet;
+
+    let checked = Address::from(P2PKH).unwrap();
+    let unchecked: Address = serde_json::from_str(&format!("\"{}\"", P2PKH)).unwrap();
+
+    let set: HashSet<Address> = vec![checked, unchecked].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn wildcard_has_no_kind() {
+    let any = Address::any();
+    assert_eq!(any.kind(), None);
+    assert!(any.payload().is_empty());
+    assert_eq!(any.to_string(), "*");
+}
@@ -0,0 +1,143 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::address::Address;
+use komodo_rpc_client::{Amount, ApiError, Client, KomodoRpcApi, MockTransport, Transport};
+use serde_json::Value;
+
+/// A komodod that answers every request with the JSON text `result`, as it is.
+struct TextNode(&'static str);
+
+impl Transport for TextNode {
+    fn send(&self, _: &Value) -> Result<Value, ApiError> {
+        unreachable!("a client sends text")
+    }
+
+    fn send_text(&self, body: &str) -> Result<String, ApiError> {
+        let request: Value = serde_json::from_str(body).unwrap();
+
+        Ok(format!(r#"{{"result": {}, "error": null, "id": {}}}"#, self.0, request["id"]))
+    }
+}
+
+#[test]
+fn parses_decimal_coins() {
+    assert_eq!("13.78296712".parse::<Amount>().unwrap().as_sat(), 1_378_296_712);
+    assert_eq!("1".parse::<Amount>().unwrap().as_sat(), 100_000_000);
+    assert_eq!("0.1".parse::<Amount>().unwrap().as_sat(), 10_000_000);
+    assert_eq!(".5".parse::<Amount>().unwrap().as_sat(), 50_000_000);
+    assert_eq!("-0.0001".parse::<Amount>().unwrap().as_sat(), -10_000);
+    assert_eq!("1e-06".parse::<Amount>().unwrap().as_sat(), 100);
+    assert_eq!("2.5E2".parse::<Amount>().unwrap().as_sat(), 25_000_000_000);
+    assert_eq!("0.100000000000".parse::<Amount>().unwrap().as_sat(), 10_000_000);
+}
+
+#[test]
+fn rejects_invalid_amounts() {
+    assert!("".parse::<Amount>().is_err());
+    assert!("-".parse::<Amount>().is_err());
+    assert!(".".parse::<Amount>().is_err());
+    assert!("1.2.3".parse::<Amount>().is_err());
+    assert!("+1".parse::<Amount>().is_err());
+    assert!("1 KMD".parse::<Amount>().is_err());
+    // a fraction of a satoshi
+    assert!("0.000000001".parse::<Amount>().is_err());
+    assert!("1e-9".parse::<Amount>().is_err());
+    // does not fit in an i64 of satoshis
+    assert!("92233720368.54775808".parse::<Amount>().is_err());
+}
+
+#[test]
+fn displays_eight_decimals() {
+    assert_eq!(Amount::from_sat(1_378_296_712).to_string(), "13.78296712");
+    assert_eq!(Amount::from_sat(100).to_string(), "0.00000100");
+    assert_eq!(Amount::from_sat(-10_000).to_string(), "-0.00010000");
+    assert_eq!(Amount::ZERO.to_string(), "0.00000000");
+    assert_eq!(Amount::from_sat(i64::MIN).to_string(), "-92233720368.54775808");
+}
+
+#[test]
+fn checked_arithmetic() {
+    let one = Amount::ONE_COIN;
+
+    assert_eq!(one.checked_add(Amount::from_sat(1)), Some(Amount::from_sat(100_000_001)));
+    assert_eq!(one.checked_sub(Amount::from_sat(1)), Some(Amount::from_sat(99_999_999)));
+    assert_eq!(one.checked_mul(3), Some(Amount::from_sat(300_000_000)));
+    assert_eq!(one.checked_div(3), Some(Amount::from_sat(33_333_333)));
+    assert_eq!(one.checked_div(0), None);
+    assert_eq!(Amount::from_sat(i64::MAX).checked_add(Amount::from_sat(1)), None);
+    assert_eq!(Amount::from_coins(21_000_000), Some(Amount::from_sat(2_100_000_000_000_000)));
+    assert_eq!(Amount::from_coins(i64::MAX), None);
+
+    let total: Amount = [one, one, Amount::from_sat(5)].iter().sum();
+    assert_eq!(total, Amount::from_sat(200_000_005));
+}
+
+#[test]
+fn deserializes_exactly() {
+    // 13.78296712 * 100000000 as an f64 is 1378296711.9999998
+    let amount: Amount = serde_json::from_str("13.78296712").unwrap();
+    assert_eq!(amount.as_sat(), 1_378_296_712);
+
+    let amount: Amount = serde_json::from_str("21000000.00000001").unwrap();
+    assert_eq!(amount.as_sat(), 2_100_000_000_000_001);
+
+    let amount: Amount = serde_json::from_str("250").unwrap();
+    assert_eq!(amount, Amount::from_coins(250).unwrap());
+
+    // an f64 cannot hold every satoshi above about 67 million coins
+    let amount: Amount = serde_json::from_str("113927283.70941327").unwrap();
+    assert_eq!(amount.as_sat(), 11_392_728_370_941_327);
+    assert!(serde_json::from_str::<Amount>("113927283.709413251").is_err());
+
+    let amount: Amount = serde_json::from_str("0.00000000").unwrap();
+    assert_eq!(amount, Amount::ZERO);
+
+    // `z_gettotalbalance` returns its amounts as strings
+    let amount: Amount = serde_json::from_str("\"15.01746712\"").unwrap();
+    assert_eq!(amount.as_sat(), 1_501_746_712);
+
+    assert!(serde_json::from_str::<Amount>("true").is_err());
+}
+
+#[test]
+fn serializes_exactly() {
+    let amount = Amount::from_sat(2_100_000_000_000_001);
+
+    assert_eq!(serde_json::to_string(&amount).unwrap(), "21000000.00000001");
+    assert_eq!(serde_json::to_string(&Amount::from_sat(10_000)).unwrap(), "0.00010000");
+    assert_eq!(serde_json::to_string(&Amount::from_sat(11_392_728_370_941_327)).unwrap(), "113927283.70941327");
+
+    let value = serde_json::to_value(amount).unwrap();
+    assert_eq!(serde_json::from_value::<Amount>(value).unwrap(), amount);
+}
+
+#[test]
+fn sends_amounts_exactly() {
+    let mut transport = MockTransport::new();
+    transport.add_method_response("sendtoaddress", json!("3e812f40cd8e4ca3a92972610409922dedf1c0dbc68394fcb1c8f188a42655e2"));
+
+    let client = Client::with_transport(transport);
+    let address = Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap();
+    client.send_to_address(address, Amount::from_sat(1_378_296_712), None, None, None).unwrap();
+
+    let requests = client.transport().requests();
+    assert_eq!(requests[0]["params"][1].to_string(), "13.78296712");
+}
+
+#[test]
+fn reads_the_total_supply_exactly() {
+    let client = Client::with_transport(TextNode(r#"{
+        "height": 1523011,
+        "bestblock": "3da2892d37823d9298e1d5011d7dcfaaf2d9d9a6d465e99be33af5be1d87c12b",
+        "transactions": 2373822,
+        "txouts": 3520612,
+        "bytes_serialized": 246473915,
+        "hash_serialized": "f94162d36adc88d1442df16ce5d13df131f05c603abda6230e52d888ed1b9415",
+        "total_amount": 113927283.70941327
+    }"#));
+
+    // as an f64 this is 113927283.70941328
+    assert_eq!(client.get_tx_out_set_info().unwrap().total_amount.as_sat(), 11_392_728_370_941_327);
+}
@@ -0,0 +1,111 @@
+extern crate komodo_rpc_client;
+extern crate serde_json;
+
+use komodo_rpc_client::{RpcBatch, RpcError, RpcRequest, RpcResponse};
+use serde_json::Value;
+use serde_json::value::{to_raw_value, RawValue};
+
+fn response(id: &str, result: Option<Value>, error: Option<RpcError>) -> RpcResponse<Box<RawValue>> {
+    RpcResponse {
+        id: Some(id.to_string()),
+        result: result.map(|result| to_raw_value(&result).unwrap()),
+        error,
+    }
+}
+
+#[test]
+fn requests_have_unique_ids() {
+    let first = RpcRequest::new1("getblockhash", 1);
+    let second = RpcRequest::new1("getblockhash", 1);
+
+    assert_ne!(first.id(), second.id());
+}
+
+#[test]
+fn rejects_duplicate_ids() {
+    let request = RpcRequest::new1("getblockhash", 1);
+
+    let mut batch = RpcBatch::new();
+    batch.add::<String, _>(&request).unwrap();
+    assert!(batch.add::<String, _>(&request).is_err());
+    assert_eq!(batch.len(), 1);
+
+    batch.add::<String, _>(&RpcRequest::new1("getblockhash", 1)).unwrap();
+    assert_eq!(batch.len(), 2);
+}
+
+#[test]
+fn serializes_as_json_array() {
+    let mut batch = RpcBatch::new();
+    batch.add::<String, _>(&RpcRequest::new1("getblockhash", 1)).unwrap();
+    batch.add::<u32, _>(&RpcRequest::new0("getblockcount")).unwrap();
+
+    let json = serde_json::to_value(batch.requests()).unwrap();
+
+    assert_eq!(json[0]["method"], "getblockhash");
+    assert_eq!(json[0]["params"], serde_json::json!([1]));
+    assert_eq!(json[1]["method"], "getblockcount");
+    assert_eq!(batch.len(), 2);
+}
+
+#[test]
+fn matches_responses_by_id() {
+    let first_request = RpcRequest::new1("getblockhash", 1);
+    let second_request = RpcRequest::new0("getblockcount");
+
+    let mut batch = RpcBatch::new();
+    let first = batch.add::<String, _>(&first_request).unwrap();
+    let second = batch.add::<u32, _>(&second_request).unwrap();
+
+    // komodod does not guarantee the order of the responses
+    let response = batch.match_responses(vec![
+        response(second_request.id(), Some(Value::from(1234567)), None),
+        response(first_request.id(), Some(Value::from("0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6")), None),
+    ]);
+
+    assert_eq!(response.get(&first).unwrap(), "0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6");
+    assert_eq!(response.get(&second).unwrap(), 1234567);
+}
+
+#[test]
+fn failed_entry_does_not_fail_batch() {
+    let first_request = RpcRequest::new1("getblockhash", 99999999);
+    let second_request = RpcRequest::new0("getblockcount");
+    let third_request = RpcRequest::new0("getconnectioncount");
+
+    let mut batch = RpcBatch::new();
+    let first = batch.add::<String, _>(&first_request).unwrap();
+    let second = batch.add::<u32, _>(&second_request).unwrap();
+    let third = batch.add::<u32, _>(&third_request).unwrap();
+
+    let response = batch.match_responses(vec![
+        response(first_request.id(), None, Some(RpcError { code: -8, message: String::from("Block height out of range") })),
+        response(second_request.id(), Some(Value::from(1234567)), None),
+    ]);
+
+    assert!(response.get(&first).is_err());
+    assert_eq!(response.get(&second).unwrap(), 1234567);
+    assert!(response.get(&third).is_err());
+}
+
+#[test]
+fn null_id_does_not_fail_batch() {
+    let first_request = RpcRequest::new1("getblockhash", 1);
+    let second_request = RpcRequest::new0("getblockcount");
+
+    let mut batch = RpcBatch::new();
+    let first = batch.add::<String, _>(&first_request).unwrap();
+    let second = batch.add::<u32, _>(&second_request).unwrap();
+
+    let responses: Vec<RpcResponse<Box<RawValue>>> = serde_json::from_value(serde_json::json!([
+        {"result": null, "error": {"code": -32700, "message": "Parse error"}, "id": null},
+        {"result": 1234567, "error": null, "id": second_request.id()},
+    ])).unwrap();
+    let response = batch.match_responses(responses);
+
+    assert!(response.get(&first).is_err());
+    assert_eq!(response.get(&second).unwrap(), 1234567);
+    assert_eq!(response.unmatched().len(), 1);
+    assert_eq!(response.unmatched()[0].id(), None);
+    assert_eq!(response.unmatched()[0].error.as_ref().unwrap().code, -32700);
+}
@@ -0,0 +1,153 @@
+extern crate komodo_rpc_client;
+
+use komodo_rpc_client::arguments::address::Address;
+use komodo_rpc_client::tx::{DEFAULT_TX_EXPIRY_DELTA, SAPLING_VERSION_GROUP_ID};
+use komodo_rpc_client::KomodoTransaction;
+use komodo_rpc_client::{AddressUtxo, AddressUtxos, Amount, PrivateKey, TransactionBuilder, Txid};
+
+const KEY: &str = "UuN1MBSLoHvCxznDjPQw2naC2KEJ3byRCcf6nfJv7JD8UaMYABYp";
+const KEY_UNCOMPRESSED: &str = "7KfHqyju4h5ofWrfgWYhnibFjSRmC1g4ft8Gp3nkJHb1ni2tf7D";
+/// P2PKH of the compressed public key of `KEY`, RXvbuoZCZ1GpTNTfroFABvFUf6ce4s1J4L
+const P2PKH_SCRIPT: &str = "76a914f8660d5f2b19c8e28981290efa1143c9021d0ced88ac";
+const P2PK_SCRIPT: &str = "2102b16ecfc0e61b75f89c55f47c28d019c22b2440919c3bcfe72bb45ab8fc7d9919ac";
+
+fn key(wif: &str) -> PrivateKey {
+    PrivateKey(wif.to_string())
+}
+
+fn txid(hex: &str) -> Txid {
+    Txid::from_hex(hex).unwrap()
+}
+
+fn builder() -> TransactionBuilder {
+    let mut builder = TransactionBuilder::new();
+    builder
+        .add_input(txid("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a"), 1, P2PKH_SCRIPT, Amount::ONE_COIN)
+        .add_input(txid("9f1d3a0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c77"), 0, P2PK_SCRIPT, Amount::from_sat(50_000_000))
+        .add_output(&Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap(), Amount::from_sat(120_000_000))
+        .add_output(&Address::from("bUy5shS9vfE27TzhnLAcqWZYRT5Wfcecb6").unwrap(), Amount::from_sat(29_990_000))
+        .set_lock_time(1_571_000_000)
+        .set_expiry_height(1_600_000);
+
+    builder
+}
+
+#[test]
+fn signs_p2pkh_and_p2pk_inputs() {
+    // signatures are deterministic (RFC 6979), so this is a regression value of this builder. It has not been
+    // checked against komodod or the official ZIP-243 test vectors, which `sighash` is not tested with yet
+    let signed = builder().sign(&key(KEY)).unwrap();
+
+    assert!(signed.complete);
+    let tx = KomodoTransaction::from_hex(&signed.hex).unwrap();
+    assert_eq!(tx.inputs.len(), 2);
+    assert_eq!(tx.inputs[0].script_sig.len(), 1 + 72 + 1 + 33);
+    assert_eq!(signed.hex, "0400008085202f89021a9c3dadb8f3a4d8c1d9d05dba0abd1f9e7efcfe06edd5fbb61cc4300cbae34a010000006b48304502210099937369b97340dc9b417497429891598bc53b911be3ff866120ba87ff50631c02203f6a28b0be1025934c32821471e4e3b9068f825111f9bace45e01d4c2f3f9d92012102b16ecfc0e61b75f89c55f47c28d019c22b2440919c3bcfe72bb45ab8fc7d9919feffffff779c3dadb8f3a4d8c1d9d05dba0abd1f9e7efcfe06edd5fbb61cc4300c3a1d9f0000000049483045022100be7cc081cc3d8012a4d2017f9906788f5e54cd25af0c85bd6deb6708fb321a3202202cbab5f89d6b81282f4a2406bd56565e2cd70e2fd24f76b139ce2358f0be902b01feffffff02000e2707000000001976a91449e0fe1eed1db113e3551adbbdfeeee900d6a49388ac709cc9010000000017a914b21ecde7a47f621fa7a97fd1676c66715796ada387c08ea35d006a18000000000000000000000000");
+}
+
+#[test]
+fn builds_sapling_v4_header() {
+    let tx = builder().build().unwrap();
+    let bytes = tx.serialize();
+
+    // version 4 with the overwintered flag, followed by the Sapling version group id
+    assert_eq!(&bytes[..4], &[0x04, 0x00, 0x00, 0x80]);
+    assert_eq!(&bytes[4..8], &[0x85, 0x20, 0x2f, 0x89]);
+    assert_eq!(SAPLING_VERSION_GROUP_ID, 0x892F_2085);
+
+    assert_eq!(tx.lock_time, 1_571_000_000);
+    assert_eq!(tx.expiry_height, 1_600_000);
+    assert!(tx.inputs.iter().all(|input| input.script_sig.is_empty() && input.sequence == 0xFFFF_FFFE));
+}
+
+#[test]
+fn expires_like_komodod() {
+    let tx = builder().expire_after(1_523_012).build().unwrap();
+    assert_eq!(tx.expiry_height, 1_523_012 + DEFAULT_TX_EXPIRY_DELTA);
+}
+
+#[test]
+fn defaults_to_komodo_lock_time() {
+    let mut builder = TransactionBuilder::new();
+    builder
+        .add_input(txid("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a"), 0, P2PKH_SCRIPT, Amount::ONE_COIN)
+        .add_output(&Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap(), Amount::from_sat(99_990_000));
+
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as u32;
+    let lock_time = builder.build().unwrap().lock_time;
+
+    assert!(lock_time <= now - 777 && lock_time >= now - 787);
+}
+
+#[test]
+fn rejects_foreign_inputs() {
+    // the uncompressed public key hashes to another address than the one of `P2PKH_SCRIPT`
+    assert!(builder().sign(&key(KEY_UNCOMPRESSED)).is_err());
+
+    let mut builder = TransactionBuilder::new();
+    builder
+        .add_input(txid("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a"), 0,
+                   "76a91449e0fe1eed1db113e3551adbbdfeeee900d6a49388ac", Amount::ONE_COIN)
+        .add_output(&Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap(), Amount::from_sat(99_990_000));
+    assert!(builder.sign(&key(KEY)).is_err());
+}
+
+#[test]
+fn signs_with_uncompressed_keys() {
+    let mut builder = TransactionBuilder::new();
+    builder
+        .add_input(txid("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a"), 0,
+                   "76a914cd4c084921010f65dff20377e9ff2d692b83a0e788ac", Amount::ONE_COIN)
+        .add_output(&Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap(), Amount::from_sat(99_990_000));
+
+    assert!(builder.sign(&key(KEY_UNCOMPRESSED)).unwrap().complete);
+}
+
+#[test]
+fn rejects_invalid_transactions() {
+    let to = Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap();
+    let input = txid("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a");
+
+    // no inputs, no outputs
+    assert!(TransactionBuilder::new().add_output(&to, Amount::ONE_COIN).build().is_err());
+    assert!(TransactionBuilder::new().add_input(input, 0, P2PKH_SCRIPT, Amount::ONE_COIN).build().is_err());
+
+    // outputs exceed inputs
+    assert!(TransactionBuilder::new()
+        .add_input(input, 0, P2PKH_SCRIPT, Amount::ONE_COIN)
+        .add_output(&to, Amount::from_sat(100_000_001))
+        .build()
+        .is_err());
+
+    // shielded outputs are not supported
+    let sapling = Address::from("zs1kfhjdt2fkj5hmtlgul27kegrm3rh6kuy5dazsg6nzn45s3zdd55wdr5hwxgj59nklmz3y2w77pp").unwrap();
+    assert!(TransactionBuilder::new()
+        .add_input(input, 0, P2PKH_SCRIPT, Amount::ONE_COIN)
+        .add_output(&sapling, Amount::from_sat(10_000))
+        .build()
+        .is_err());
+
+    // a bitcoin WIF
+    let bitcoin_key = key("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn");
+    assert!(builder().sign(&bitcoin_key).is_err());
+}
+
+#[test]
+fn rejects_utxos_an_amount_cannot_hold() {
+    let utxo = |satoshis| AddressUtxo {
+        address: "RXvbuoZCZ1GpTNTfroFABvFUf6ce4s1J4L".to_string(),
+        txid: txid("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a"),
+        output_index: 0,
+        script: P2PKH_SCRIPT.to_string(),
+        satoshis,
+        height: 1_500_000,
+    };
+
+    let mut builder = TransactionBuilder::new();
+    assert!(builder.add_utxo(&utxo(i64::MAX as u64 + 1)).is_err());
+    // none of the UTXOs is added if one is rejected
+    assert!(builder.add_utxos(&AddressUtxos(vec![utxo(100_000_000), utxo(u64::MAX)])).is_err());
+    builder.add_utxo(&utxo(i64::MAX as u64)).unwrap();
+    builder.add_output(&Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap(), Amount::ONE_COIN);
+    assert_eq!(builder.build().unwrap().inputs.len(), 1);
+}
@@ -0,0 +1,176 @@
+//! CC RPCs: the parameters they are sent with and how their `"result": "error"` answers are reported.
+
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::*;
+
+const TOKEN_ID: &str = "1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864";
+const PUBKEY: &str = "029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abf";
+
+fn token_id() -> Txid {
+    TOKEN_ID.parse().unwrap()
+}
+
+#[test]
+fn sends_amounts_as_strings() {
+    // komodod reads the parameters of CC RPCs with get_str()
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("tokencreate", json!(["GOLD", "0.50000000"]), json!({"result": "success", "hex": "01"}))
+        .add_response("tokencreate", json!(["GOLD", "0.50000000", "", "ff"]), json!({"result": "success", "hex": "02"}))
+        .add_response("tokentransfer", json!([TOKEN_ID, PUBKEY, "100"]), json!({"result": "success", "hex": "03"}));
+    let client = Client::with_transport(transport);
+
+    let amount = Amount::from_sat(50_000_000);
+    assert_eq!(client.token_create("GOLD", amount, None, None).unwrap().hex, "01");
+    assert_eq!(client.token_create("GOLD", amount, None, Some("ff")).unwrap().hex, "02");
+    assert_eq!(client.token_transfer(&token_id(), PUBKEY, 100).unwrap().hex, "03");
+}
+
+#[test]
+fn reports_cc_errors() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_method_response("tokentransfer", json!({"result": "error", "error": "could not create transfer token transaction"}))
+        .add_method_response("tokeninfo", json!({"result": "error", "error": "cant find tokenid"}));
+    let client = Client::with_transport(transport);
+
+    match client.token_transfer(&token_id(), PUBKEY, 100) {
+        Err(ApiError::CC(ref message)) => assert_eq!(message, "could not create transfer token transaction"),
+        other => panic!("expected a CC error, got {:?}", other),
+    }
+
+    let error = client.token_info(&token_id()).unwrap_err();
+    assert_eq!(error.to_string(), "CC error: cant find tokenid");
+}
+
+#[test]
+fn sends_created_token() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_method_response("tokencreate", json!({"result": "success", "hex": "0400008085202f89"}))
+        .add_response("sendrawtransaction", json!(["0400008085202f89"]), json!(TOKEN_ID));
+    let client = Client::with_transport(transport);
+
+    let tx = client.token_create("GOLD", Amount::from_sat(100_000_000), Some("gold backed"), None).unwrap();
+    assert_eq!(client.send_raw_transaction(&tx).unwrap(), token_id());
+}
+
+#[test]
+fn sends_prices_in_coins() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("tokenask", json!(["1000", TOKEN_ID, "0.01200000"]), json!({"result": "success", "hex": "01"}))
+        .add_response("tokenfillbid", json!([TOKEN_ID, TOKEN_ID, "10"]), json!({"result": "success", "hex": "02"}));
+    let client = Client::with_transport(transport);
+
+    assert_eq!(client.token_ask(1000, &token_id(), Amount::from_sat(1_200_000)).unwrap().hex, "01");
+    assert_eq!(client.token_fill_bid(&token_id(), &token_id(), 10).unwrap().hex, "02");
+}
+
+#[test]
+fn groups_orders_by_token() {
+    let mut transport = MockTransport::new();
+    transport.add_method_response("tokenorders", serde_json::from_str(include_str!("fixtures/rpc/tokenorders.json")).unwrap());
+    let client = Client::with_transport(transport);
+
+    let book = client.order_book(None).unwrap();
+    assert_eq!(book.len(), 2);
+
+    let orders = book.get(&token_id()).unwrap();
+    assert_eq!(orders.asks.len(), 1);
+    // the bid without a price is left out
+    assert_eq!(orders.bids.len(), 2);
+    // highest bid first
+    assert_eq!(orders.best_bid().unwrap().price, Some(Amount::from_sat(1_100_000)));
+    assert_eq!(orders.best_ask().unwrap().tokens, Some(1000));
+    assert_eq!(orders.spread(), Some(Amount::from_sat(100_000)));
+
+    let other: Txid = "9b4c1a6a1a0e7d7a4d4b6b3e0f5d9f2c8e6a5b3c1d0e9f8a7b6c5d4e3f2a1b0c".parse().unwrap();
+    assert!(book.get(&other).unwrap().spread().is_none());
+    assert!(book.tokenids().any(|tokenid| *tokenid == other));
+}
+
+#[test]
+fn rejects_unknown_orders() {
+    let order = json!({"funcid": "e", "txid": TOKEN_ID, "vout": 0, "amount": "1", "totalrequired": "1", "price": "1"});
+
+    assert!(serde_json::from_value::<TokenOrder>(order).is_err());
+}
+
+/// The error of `dicebet` when komodod answers it with `message`.
+fn dice_error(message: &str) -> ApiError {
+    let mut transport = MockTransport::new();
+    transport.add_method_response("dicebet", json!({"result": "error", "error": message}));
+
+    Client::with_transport(transport).dice_bet("KMDICE", &token_id(), Amount::from_sat(100_000_000), 2).unwrap_err()
+}
+
+#[test]
+fn maps_cc_error_messages() {
+    let error = dice_error("couldnt create dice bet transaction. make sure your address has funds");
+    assert_eq!(error.cc_error_kind(), Some(CCErrorKind::InsufficientFunds));
+    assert!(error.is_insufficient_funds());
+
+    let kind = |message: &str| dice_error(message).cc_error_kind().unwrap();
+    assert_eq!(kind("error adding normal inputs"), CCErrorKind::InsufficientFunds);
+    assert_eq!(kind("Diceinit error in bet, is your transaction confirmed?"), CCErrorKind::NotFound);
+    assert_eq!(kind("odds must be between 2 and 9999"), CCErrorKind::InvalidParameter);
+    assert_eq!(
+        kind("Dice plan KMDICE illegal bet 1000.00000000: minbet 1.00000000 maxbet 100.00000000 or odds 2 vs max.10\n"),
+        CCErrorKind::InvalidParameter
+    );
+    assert_eq!(kind("Rewards plan KMDREW doesnt exist"), CCErrorKind::NotFound);
+    assert_eq!(kind("reward 0.00001000 is <= the transaction fee"), CCErrorKind::NotReady);
+    assert_eq!(kind("cant find tokenid"), CCErrorKind::NotFound);
+    assert_eq!(kind("Token name must be up to 32 characters"), CCErrorKind::InvalidParameter);
+
+    // only whole messages are recognized
+    assert_eq!(kind("something else"), CCErrorKind::Other);
+    assert_eq!(kind("error adding normal inputs to the unlocked funds"), CCErrorKind::Other);
+    assert_eq!(kind("please wait"), CCErrorKind::Other);
+    assert_eq!(kind("invalid tokenid, not found"), CCErrorKind::Other);
+    assert_eq!(kind("Rewards plan  doesnt exist"), CCErrorKind::Other);
+    assert_eq!(kind("Error adding normal inputs"), CCErrorKind::Other);
+
+    // and the message is kept
+    match dice_error("invalid tokenid, not found") {
+        ApiError::CC(message) => assert_eq!(message, "invalid tokenid, not found"),
+        other => panic!("expected a CC error, got {:?}", other),
+    }
+
+    // RPC errors are no CC errors, but can be out of funds too
+    let mut transport = MockTransport::new();
+    transport.add_method_error("faucetfund", -6, "Insufficient funds");
+    let error = Client::with_transport(transport).faucet_fund(Amount::from_sat(100_000_000)).unwrap_err();
+    assert_eq!(error.cc_error_kind(), None);
+    assert!(error.is_insufficient_funds());
+}
+
+#[test]
+fn reads_dice_statuses() {
+    let status = |status: serde_json::Value| serde_json::from_value::<DiceStatus>(status).unwrap();
+
+    assert_eq!(status(json!({"result": "success", "status": "loss"})), DiceStatus::Loss);
+    assert_eq!(status(json!({"result": "success", "status": "bet still pending"})), DiceStatus::Pending);
+    assert_eq!(status(json!({"result": "success", "status": "finalized", "n": 3})), DiceStatus::Finalized(3));
+    assert_eq!(status(json!({"result": "success", "status": "no pending bets"})), DiceStatus::NoPendingBets);
+    assert!(serde_json::from_value::<DiceStatus>(json!({"status": "win"})).is_err());
+}
+
+#[test]
+fn sends_dice_and_rewards_parameters() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("dicefund", json!(["KMDICE", "1000.00000000", "0.00100000", "10.00000000", "100", "5"]), json!({"result": "success", "hex": "01"}))
+        .add_response("rewardscreatefunding", json!(["LOCK", "10000.00000000", "5.5", "1", "10", "1.00000000"]), json!({"result": "success", "hex": "02"}))
+        .add_response("dicestatus", json!(["KMDICE", TOKEN_ID]), json!({"result": "success", "status": "finalized", "n": 2}));
+    let client = Client::with_transport(transport);
+
+    let coins = |coins: i64| Amount::from_coins(coins).unwrap();
+    assert_eq!(client.dice_fund("KMDICE", coins(1000), Amount::from_sat(100_000), coins(10), 100, 5).unwrap().hex, "01");
+    assert_eq!(client.rewards_create_funding("LOCK", coins(10_000), 5.5, 1, 10, coins(1)).unwrap().hex, "02");
+    assert_eq!(client.dice_status("KMDICE", &token_id(), None).unwrap(), DiceStatus::Finalized(2));
+}
@@ -0,0 +1,76 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::*;
+use std::fs;
+use std::path::PathBuf;
+
+const OPEN_TXID: &str = "c1f3b6a5d8e0f2a4c6e8b0d2f4a6c8e0b2d4f6a8c0e2b4d6f8a0c2e4b6d8f0a2";
+/// The end of the hash chain of the channel in `channelsinfo.json`, from the seed `[7; 32]` and 10 payments.
+const HASHCHAIN: &str = "e2b254cb3514e233058e2b6fc297166b9e5c505842f894925dac0e0c69adf94e";
+
+fn channel_info() -> ChannelInfo {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/rpc/channelsinfo.json");
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+#[test]
+fn builds_hash_chain() {
+    let chain = HashChain::new(ChannelSecret::from_bytes([7; 32]), 10);
+    assert_eq!(chain.hashchain().to_string(), HASHCHAIN);
+
+    // nothing paid reveals the hashchain itself, all payments the seed
+    assert_eq!(chain.secret(0), Some(chain.hashchain()));
+    assert_eq!(chain.secret(10), Some(ChannelSecret::from_bytes([7; 32])));
+    assert_eq!(chain.secret(11), None);
+}
+
+#[test]
+fn verifies_revealed_secrets() {
+    let hashchain: ChannelSecret = HASHCHAIN.parse().unwrap();
+    let info = channel_info();
+
+    // each payment reveals the secret of all payments made so far
+    let mut paid = 0;
+    for payment in info.transactions.iter().filter(|tx| tx.kind == ChannelTxKind::Payment) {
+        paid += payment.payments.unwrap();
+        let secret = payment.secret.unwrap();
+
+        assert!(HashChain::verify(&hashchain, &secret, paid));
+        assert!(!HashChain::verify(&hashchain, &secret, paid + 1));
+        assert_eq!(info.numpayments - paid, payment.payments_left.unwrap());
+    }
+    assert_eq!(paid, 3);
+}
+
+#[test]
+fn pays_with_secret() {
+    let opentxid: Txid = OPEN_TXID.parse().unwrap();
+    let secret = HashChain::new(ChannelSecret::from_bytes([7; 32]), 10).secret(4).unwrap();
+
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("channelsopen", json!(["02aa", "10", "1000000"]), json!({"result": "success", "hex": "01"}))
+        .add_response("channelspayment", json!([OPEN_TXID, "1000000", secret.to_string()]), json!({"result": "success", "hex": "02"}))
+        .add_response("channelspayment", json!([OPEN_TXID, "2000000"]), json!({"result": "success", "hex": "03"}));
+    let client = Client::with_transport(transport);
+
+    assert_eq!(client.channels_open("02aa", 10, Amount::from_sat(1_000_000)).unwrap().hex, "01");
+    assert_eq!(client.channels_payment(&opentxid, Amount::from_sat(1_000_000), Some(&secret)).unwrap().hex, "02");
+    assert_eq!(client.channels_payment(&opentxid, Amount::from_sat(2_000_000), None).unwrap().hex, "03");
+}
+
+#[test]
+fn tracks_closed_channels() {
+    let mut info = channel_info();
+    assert_eq!(info.last_payment().unwrap().payments_left, Some(7));
+
+    let close = json!({"Close": "0df7c4d844f08dba08abd4bb174558739f17cfe268feb005fb6333b3761d9203"});
+    info.transactions.push(serde_json::from_value(close).unwrap());
+    assert!(info.is_closed());
+
+    let unknown = json!({"Open": OPEN_TXID, "Close": OPEN_TXID});
+    assert!(serde_json::from_value::<ChannelTransaction>(unknown).is_err());
+}
@@ -0,0 +1,116 @@
+extern crate komodo_rpc_client;
+
+use std::path::PathBuf;
+
+use komodo_rpc_client::{Chain, KomodoConf};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/conf")
+        .join(name)
+}
+
+#[test]
+fn parses_rpc_keys() {
+    let conf = KomodoConf::read(fixture("komodo.conf")).unwrap();
+
+    assert_eq!(conf.rpc_user(), Some("user3017201921"));
+    assert_eq!(conf.rpc_password(), Some("pass1dc9b7f3a9fa8ea2d5d0f3f5c6f35aa1b5f45d56"));
+    assert_eq!(conf.rpc_port().unwrap(), Some(7771));
+    assert_eq!(conf.rpc_connect(), Some("10.0.0.2"));
+    assert_eq!(conf.rpc_bind(), vec!["127.0.0.1", "10.0.0.2"]);
+    assert_eq!(conf.rpc_threads().unwrap(), Some(8));
+    assert_eq!(conf.rpc_work_queue().unwrap(), Some(64));
+    assert!(conf.server());
+    assert!(!conf.testnet());
+    assert!(!conf.regtest());
+}
+
+#[test]
+fn first_value_of_duplicate_key_wins() {
+    let conf = KomodoConf::read(fixture("komodo.conf")).unwrap();
+
+    assert_eq!(conf.get("rpcuser"), Some("user3017201921"));
+    assert_eq!(conf.get_all("rpcuser"), vec!["user3017201921", "ignored"]);
+}
+
+#[test]
+fn follows_includeconf() {
+    let conf = KomodoConf::read(fixture("komodo.conf")).unwrap();
+
+    assert_eq!(conf.include_conf(), vec!["included.conf"]);
+    assert_eq!(conf.get_all("addnode"), vec!["5.9.102.210", "78.47.196.146"]);
+    assert_eq!(conf.rpc_allow_ip(), vec!["127.0.0.1", "10.0.0.0/24", "192.168.1.0/24"]);
+}
+
+#[test]
+fn parse_does_not_follow_includeconf() {
+    let conf = KomodoConf::parse("includeconf=included.conf\nrpcuser=user").unwrap();
+
+    assert_eq!(conf.include_conf(), vec!["included.conf"]);
+    assert!(!conf.contains_key("addnode"));
+}
+
+#[test]
+fn strips_carriage_returns() {
+    let conf = KomodoConf::read(fixture("crlf.conf")).unwrap();
+
+    assert_eq!(conf.rpc_user(), Some("user3017201921"));
+    assert_eq!(conf.rpc_port().unwrap(), Some(7771));
+    assert_eq!(conf.get_all("addnode"), vec!["5.9.102.210", "78.47.196.146"]);
+}
+
+#[test]
+fn reads_assetchain_conf_from_datadir() {
+    let chain = Chain::Custom(String::from("MYCHAIN"));
+    let conf = KomodoConf::read_for(&chain, Some(&fixture("MYCHAIN"))).unwrap();
+
+    assert_eq!(conf.rpc_user(), Some("user2485924082"));
+    assert_eq!(conf.rpc_port().unwrap(), Some(27486));
+    assert!(conf.regtest());
+}
+
+#[test]
+fn interprets_booleans_like_komodod() {
+    let conf = KomodoConf::parse("a=1\nb=0\nc=\nd=2\ne=true\nf=-1").unwrap();
+
+    assert_eq!(conf.get_bool("a"), Some(true));
+    assert_eq!(conf.get_bool("b"), Some(false));
+    assert_eq!(conf.get_bool("c"), Some(true));
+    assert_eq!(conf.get_bool("d"), Some(true));
+    assert_eq!(conf.get_bool("e"), Some(false));
+    assert_eq!(conf.get_bool("f"), Some(true));
+    assert_eq!(conf.get_bool("g"), None);
+}
+
+#[test]
+fn rejects_lines_without_key_value() {
+    assert!(KomodoConf::read(fixture("invalid.conf")).is_err());
+    assert!(KomodoConf::parse("=value").is_err());
+}
+
+#[test]
+fn rejects_invalid_rpcport() {
+    let conf = KomodoConf::parse("rpcport=77710").unwrap();
+
+    assert!(conf.rpc_port().is_err());
+}
+
+#[test]
+fn stops_at_include_cycles() {
+    assert!(KomodoConf::read(fixture("loop.conf")).is_err());
+}
+
+#[test]
+fn finds_cookie_file() {
+    let datadir = PathBuf::from("/home/user/.komodo/DEX");
+
+    assert_eq!(KomodoConf::parse("").unwrap().cookie_file(&datadir), datadir.join(".cookie"));
+    assert_eq!(KomodoConf::parse("testnet=1").unwrap().cookie_file(&datadir), datadir.join("testnet3/.cookie"));
+    assert_eq!(KomodoConf::parse("regtest=1").unwrap().cookie_file(&datadir), datadir.join("regtest/.cookie"));
+    assert_eq!(KomodoConf::parse("rpccookiefile=auth").unwrap().cookie_file(&datadir), datadir.join("auth"));
+    assert_eq!(
+        KomodoConf::parse("rpccookiefile=/run/komodod/cookie").unwrap().cookie_file(&datadir),
+        PathBuf::from("/run/komodod/cookie")
+    );
+}
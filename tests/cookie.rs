@@ -0,0 +1,137 @@
+extern crate base64;
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::*;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A komodod that accepts the credentials in `cookie` and answers `getconnectioncount`. Returns its port.
+fn node(cookie: Arc<Mutex<String>>) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let text = loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+
+                let text = String::from_utf8_lossy(&request).to_string();
+                if n == 0 || text.ends_with('}') {
+                    break text;
+                }
+            };
+
+            let expected = format!("authorization: basic {}", base64::encode(&*cookie.lock().unwrap())).to_lowercase();
+            let response = if text.to_lowercase().contains(&expected) {
+                let body = json!({"result": 8, "error": null, "id": "1"}).to_string();
+                format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)
+            } else {
+                String::from("HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    port
+}
+
+/// A temporary directory that is removed when it is dropped, also when a test fails.
+struct TempDir(PathBuf);
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A data directory of the assetchain `COOKIE` with a config file without credentials.
+fn datadir(name: &str, port: u16) -> TempDir {
+    let datadir = TempDir(std::env::temp_dir().join(format!("komodo-rpc-client-{}-{}", process::id(), name)));
+    fs::create_dir_all(&*datadir).unwrap();
+    fs::write(datadir.join("COOKIE.conf"), format!("server=1\nrpcport={}\n", port)).unwrap();
+
+    datadir
+}
+
+fn client(datadir: &Path) -> Result<Client, ApiError> {
+    Client::builder()
+        .chain(Chain::Custom(String::from("COOKIE")))
+        .datadir(datadir)
+        .build()
+}
+
+#[test]
+fn falls_back_to_cookie_file() {
+    let cookie = Arc::new(Mutex::new(String::from("__cookie__:1d2c")));
+    let datadir = datadir("fallback", node(cookie.clone()));
+    fs::write(datadir.join(".cookie"), "__cookie__:1d2c").unwrap();
+
+    assert_eq!(client(&datadir).unwrap().get_connection_count().unwrap(), 8);
+}
+
+#[test]
+fn rereads_cookie_file_after_restart() {
+    let cookie = Arc::new(Mutex::new(String::from("__cookie__:1d2c")));
+    let datadir = datadir("restart", node(cookie.clone()));
+    fs::write(datadir.join(".cookie"), "__cookie__:1d2c").unwrap();
+
+    let client = client(&datadir).unwrap();
+    assert_eq!(client.get_connection_count().unwrap(), 8);
+
+    // komodod restarts with a new cookie
+    *cookie.lock().unwrap() = String::from("__cookie__:77ab");
+    fs::write(datadir.join(".cookie"), "__cookie__:77ab\n").unwrap();
+    assert_eq!(client.get_connection_count().unwrap(), 8);
+
+    // a cookie that did not change is not tried again
+    *cookie.lock().unwrap() = String::from("__cookie__:9f00");
+    assert!(client.get_connection_count().unwrap_err().is_unauthorized());
+}
+
+#[test]
+fn requires_credentials_or_cookie_file() {
+    let datadir = datadir("missing", 7771);
+
+    match client(&datadir) {
+        Err(ApiError::Config(message)) => assert!(message.contains(".cookie")),
+        other => panic!("expected a config error, got {:?}", other),
+    }
+}
+
+#[test]
+fn prefers_credentials_over_cookie_file() {
+    let cookie = Arc::new(Mutex::new(String::from("user:password")));
+    let datadir = datadir("credentials", node(cookie));
+    fs::write(datadir.join(".cookie"), "__cookie__:1d2c").unwrap();
+
+    let client = Client::builder()
+        .chain(Chain::Custom(String::from("COOKIE")))
+        .datadir(&*datadir)
+        .rpc_user("user")
+        .rpc_password("password")
+        .build()
+        .unwrap();
+
+    assert_eq!(client.get_connection_count().unwrap(), 8);
+}
@@ -0,0 +1,157 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::*;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// The error `method` returns when komodod answers it with `code` and `message`.
+fn rpc_error(code: i32, message: &str) -> ApiError {
+    let mut transport = MockTransport::new();
+    transport.add_method_error("getinfo", code, message);
+
+    Client::with_transport(transport).get_info().unwrap_err()
+}
+
+/// Answers a single HTTP request on a local port with `status` and `body`, and returns the port.
+fn serve(status: &'static str, body: &'static str) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        // read the headers and the body, so the client is done writing before it is answered
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text
+                    .lines()
+                    .find(|line| line.starts_with("content-length:"))
+                    .map(|line| line["content-length:".len()..].trim().parse::<usize>().unwrap())
+                    .unwrap_or(0);
+
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+
+    port
+}
+
+fn client(port: u16) -> Client {
+    Client::builder()
+        .port(port)
+        .rpc_user("user")
+        .rpc_password("password")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn maps_komodod_error_codes() {
+    let error = rpc_error(-28, "Verifying blocks...");
+    assert_eq!(error.rpc_error_code(), Some(RpcErrorCode::InWarmup));
+    assert!(error.is_warming_up());
+    assert!(error.is_retryable());
+
+    let error = rpc_error(-6, "Insufficient funds");
+    assert_eq!(error.rpc_error_code(), Some(RpcErrorCode::WalletInsufficientFunds));
+    assert!(!error.is_retryable());
+    assert!(!error.is_warming_up());
+
+    assert_eq!(
+        rpc_error(-13, "Error: Please enter the wallet passphrase with walletpassphrase first.").rpc_error_code(),
+        Some(RpcErrorCode::WalletUnlockNeeded)
+    );
+    assert_eq!(rpc_error(-5, "Invalid Komodo address").rpc_error_code(), Some(RpcErrorCode::InvalidAddressOrKey));
+    assert_eq!(rpc_error(-27, "transaction already in block chain").rpc_error_code(), Some(RpcErrorCode::VerifyAlreadyInChain));
+    // only -28 is retried for every request
+    let error = rpc_error(-10, "Komodo is downloading blocks...");
+    assert!(!error.is_retryable());
+    assert!(error.is_retryable_if_read_only());
+
+    let error = rpc_error(-25, "Missing inputs");
+    assert_eq!(error.rpc_error_code(), Some(RpcErrorCode::VerifyError));
+    assert!(error.is_missing_inputs());
+    assert!(!rpc_error(-25, "bad-txns-inputs-spent").is_missing_inputs());
+}
+
+#[test]
+fn keeps_unknown_codes() {
+    assert_eq!(RpcErrorCode::from(-32601), RpcErrorCode::MethodNotFound);
+    assert_eq!(RpcErrorCode::from(-999), RpcErrorCode::Unknown(-999));
+    assert_eq!(RpcErrorCode::Unknown(-999).code(), -999);
+
+    for code in &[-32700, -32603, -28, -17, -13, -6, -5, -4, -1] {
+        assert_eq!(RpcErrorCode::from(*code).code(), *code);
+    }
+}
+
+#[test]
+fn empty_response_is_an_error() {
+    let response: RpcResponse<u32> = serde_json::from_value(json!({"id": "1", "result": null, "error": null})).unwrap();
+
+    match response.into_result() {
+        Err(ApiError::Client(ClientError::EmptyResponse)) => {},
+        other => panic!("expected an empty response, got {:?}", other),
+    }
+}
+
+#[test]
+fn maps_http_statuses() {
+    let error = client(serve("401 Unauthorized", "")).get_connection_count().unwrap_err();
+    assert!(error.is_unauthorized());
+    assert!(!error.is_retryable());
+    assert!(error.to_string().contains("401"));
+
+    let error = client(serve("403 Forbidden", "")).get_connection_count().unwrap_err();
+    assert!(error.is_forbidden());
+
+    let error = client(serve("503 Service Unavailable", "Work queue depth exceeded")).get_connection_count().unwrap_err();
+    match error {
+        ApiError::Client(ClientError::Status(503)) => {},
+        ref other => panic!("expected HTTP 503, got {:?}", other),
+    }
+    assert!(!error.is_retryable());
+    assert!(error.is_retryable_if_read_only());
+}
+
+#[test]
+fn parses_rpc_errors_of_failed_http_requests() {
+    // komodod sends RPC errors with status 500
+    let body = r#"{"result":null,"error":{"code":-28,"message":"Loading block index..."},"id":"1"}"#;
+
+    let error = client(serve("500 Internal Server Error", body)).get_connection_count().unwrap_err();
+    assert!(error.is_warming_up());
+}
+
+#[test]
+fn unreachable_node_is_retryable() {
+    // nothing listens on a port that was just released
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+    let error = client(port).get_connection_count().unwrap_err();
+    assert!(error.is_connection_refused());
+    assert!(error.is_retryable());
+}
@@ -0,0 +1,207 @@
+extern crate komodo_rpc_client;
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::address::Address;
+use komodo_rpc_client::tx::*;
+use komodo_rpc_client::{Amount, NetworkInfo, Txid};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+const SCRIPT: &str = "76a914f8660d5f2b19c8e28981290efa1143c9021d0ced88ac";
+const TO: &str = "RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR";
+const CHANGE: &str = "RXvbuoZCZ1GpTNTfroFABvFUf6ce4s1J4L";
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{}.json", name));
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+fn txid() -> Txid {
+    Txid::from_hex("4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a").unwrap()
+}
+
+fn payment(input: i64, output: i64) -> TransactionBuilder {
+    let mut builder = TransactionBuilder::new();
+    builder
+        .add_input(txid(), 0, SCRIPT, Amount::from_sat(input))
+        .add_output(&Address::from(TO).unwrap(), Amount::from_sat(output));
+
+    builder
+}
+
+#[test]
+fn calculates_input_and_output_sizes() {
+    assert_eq!(InputKind::P2PKH { compressed: true }.size(), 148);
+    assert_eq!(InputKind::P2PKH { compressed: false }.size(), 180);
+    assert_eq!(InputKind::P2PK.size(), 114);
+    // OP_0, 2 signatures and the 105 byte redeem script with OP_PUSHDATA1, with a 3 byte length
+    assert_eq!(InputKind::P2SHMultisig { required: 2, keys: 3 }.size(), 32 + 4 + 3 + 254 + 4);
+    assert_eq!(InputKind::CryptoCondition { fulfillment_size: CC_FULFILLMENT_SIZE }.size(), 32 + 4 + 1 + 117 + 4);
+
+    assert_eq!(OutputKind::P2PKH.size(), 34);
+    assert_eq!(OutputKind::P2SH.size(), 32);
+    assert_eq!(OutputKind::P2PK.size(), 44);
+    assert_eq!(OutputKind::CryptoCondition { condition_size: CC_CONDITION_SIZE }.size(), 8 + 1 + 49);
+    assert_eq!(OutputKind::OpReturn { data_size: 80 }.size(), 8 + 1 + 83);
+
+    assert_eq!(InputKind::from_script_pubkey(SCRIPT), Some(InputKind::P2PKH { compressed: true }));
+    assert_eq!(
+        InputKind::from_script_pubkey("21029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac"),
+        Some(InputKind::P2PK)
+    );
+}
+
+#[test]
+fn does_not_guess_the_kind_of_other_scripts() {
+    // P2SH
+    assert_eq!(InputKind::from_script_pubkey("a914b4c1d6b8ab4e0a1a8d0a15d1b1c3eb5e9b1e7e5787"), None);
+    // a CC script
+    assert_eq!(
+        InputKind::from_script_pubkey("2ea22c8020c5b9f3b3d8d0c8a4d4e4c9f7d0b9f1a0d6ac3b4b1a4f0a9c4c8b6f9ab5e7b8103120c008203000401cc"),
+        None
+    );
+    assert_eq!(InputKind::from_script_pubkey(""), None);
+    assert_eq!(InputKind::from_script_pubkey("76a914f8660d5f2b19c8e28981290efa1143c9021d0cedzz88ac"), None);
+
+    let mut builder = TransactionBuilder::new();
+    builder.add_input(txid(), 0, "a914b4c1d6b8ab4e0a1a8d0a15d1b1c3eb5e9b1e7e5787", Amount::ONE_COIN);
+    assert!(builder.estimated_size().is_err());
+}
+
+#[test]
+fn derives_dust_threshold_from_relay_fee() {
+    assert_eq!(FeePolicy::default().dust_threshold, DEFAULT_DUST_THRESHOLD);
+    assert_eq!(FeePolicy::dust_threshold(DEFAULT_RELAY_FEE), Amount::from_sat(54));
+    assert_eq!(FeePolicy::dust_threshold(Amount::from_sat(1_000)), Amount::from_sat(546));
+    assert_eq!(FeePolicy::dust_threshold(Amount::ZERO), Amount::ZERO);
+
+    let mut info: NetworkInfo = serde_json::from_value(fixture("getnetworkinfo")).unwrap();
+    info.relayfee = Amount::from_sat(1_000);
+    assert_eq!(FeePolicy::from(&info).dust_threshold, Amount::from_sat(546));
+}
+
+#[test]
+fn calculates_transaction_sizes() {
+    assert_eq!(TransactionSize::new().size(), 29);
+
+    // the transaction of getrawtransaction.json has a 70 byte signature, one less than estimated
+    let hex: String = serde_json::from_value(fixture("getrawtransaction")).unwrap();
+    let size = TransactionSize::new()
+        .add_input(InputKind::P2PKH { compressed: true })
+        .add_output(OutputKind::P2PKH)
+        .add_output(OutputKind::P2PKH)
+        .size();
+    assert_eq!(size, hex.len() / 2 + 1);
+
+    // one Sapling spend and output, with the binding signature
+    let size = TransactionSize::new()
+        .add_shielded_spends(1)
+        .add_shielded_outputs(1)
+        .size();
+    assert_eq!(size, 29 + 384 + 948 + 64);
+
+    // 253 inputs need a 3 byte count
+    let mut size = TransactionSize::new();
+    for _ in 0..253 {
+        size.add_input(InputKind::P2PK);
+    }
+    assert_eq!(size.size(), 29 + 2 + 253 * 114);
+}
+
+#[test]
+fn applies_default_and_relay_fees() {
+    let info: NetworkInfo = serde_json::from_value(fixture("getnetworkinfo")).unwrap();
+    let policy = FeePolicy::from(&info);
+    assert_eq!(policy, FeePolicy::default());
+    assert_eq!(policy.relay_fee, Amount::from_sat(100));
+
+    assert_eq!(policy.fee(245), DEFAULT_FEE);
+    assert_eq!(policy.fee(100_000), DEFAULT_FEE);
+    assert_eq!(policy.fee(250_000), Amount::from_sat(25_000));
+
+    let policy = FeePolicy::from_rate(Amount::from_sat(100));
+    assert_eq!(policy.fee(245), Amount::from_sat(24));
+    // a rate that rounds to 0 pays the rate
+    assert_eq!(policy.fee(5), Amount::from_sat(100));
+    assert_eq!(policy.fee(0), Amount::ZERO);
+}
+
+#[test]
+fn adds_change() {
+    let change = Address::from(CHANGE).unwrap();
+
+    let mut builder = payment(100_000_000, 50_000_000);
+    assert_eq!(builder.estimated_size().unwrap(), 211);
+    builder.add_change(&change, &FeePolicy::default()).unwrap();
+
+    let tx = builder.build().unwrap();
+    assert_eq!(tx.outputs.len(), 2);
+    assert_eq!(tx.outputs[1].value, Amount::from_sat(50_000_000 - 10_000));
+
+    // with a rate the fee depends on the size with the change output
+    let mut builder = payment(100_000_000, 50_000_000);
+    builder.add_change(&change, &FeePolicy::from_rate(Amount::from_sat(1_000))).unwrap();
+    assert_eq!(builder.build().unwrap().outputs[1].value, Amount::from_sat(50_000_000 - 245));
+}
+
+#[test]
+fn adds_dust_change_to_fee() {
+    let change = Address::from(CHANGE).unwrap();
+
+    let mut builder = payment(50_010_050, 50_000_000);
+    builder.add_change(&change, &FeePolicy::default()).unwrap();
+    assert_eq!(builder.build().unwrap().outputs.len(), 1);
+
+    // 300 satoshis are not dust to komodod, but they are with bitcoind's threshold
+    let mut builder = payment(50_010_300, 50_000_000);
+    builder.add_change(&change, &FeePolicy::default()).unwrap();
+    assert_eq!(builder.build().unwrap().outputs[1].value, Amount::from_sat(300));
+
+    let policy = FeePolicy { dust_threshold: Amount::from_sat(546), ..FeePolicy::default() };
+    let mut builder = payment(50_010_300, 50_000_000);
+    builder.add_change(&change, &policy).unwrap();
+    assert_eq!(builder.build().unwrap().outputs.len(), 1);
+
+    let mut builder = payment(50_005_000, 50_000_000);
+    assert!(builder.add_change(&change, &FeePolicy::default()).is_err());
+}
+
+#[test]
+fn rejects_change_that_overflows() {
+    let change = Address::from(CHANGE).unwrap();
+
+    let mut builder = payment(i64::MAX, 50_000_000);
+    builder.set_rewards(Amount::ONE_COIN);
+    assert!(builder.add_change(&change, &FeePolicy::default()).is_err());
+
+    let mut builder = payment(100_000_000, 50_000_000);
+    builder.add_output(&Address::from(TO).unwrap(), Amount::from_sat(i64::MAX));
+    assert!(builder.add_change(&change, &FeePolicy::default()).is_err());
+
+    let mut builder = payment(100_000_000, i64::MIN);
+    assert!(builder.add_change(&change, &FeePolicy::default()).is_err());
+}
+
+#[test]
+fn selects_with_default_fee() {
+    let utxos = vec![Utxo {
+        txid: "4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a".parse().unwrap(),
+        vout: 0,
+        address: CHANGE.to_string(),
+        script_pub_key: SCRIPT.to_string(),
+        value: Amount::ONE_COIN,
+        height: Some(1_600_000),
+        confirmations: None,
+    }];
+
+    let selection = CoinSelector::new(utxos)
+        .select_with_policy(Amount::from_sat(50_000_000), &FeePolicy::default())
+        .unwrap();
+
+    assert_eq!(selection.fee, DEFAULT_FEE);
+    assert_eq!(selection.change, Amount::from_sat(50_000_000 - 10_000));
+}
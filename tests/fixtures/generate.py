@@ -0,0 +1,258 @@
+#!/usr/bin/env python3
+"""Writes the synthetic komodod responses in tests/fixtures/rpc, one file per RPC.
+
+These are not captured from a running komodod. They follow the shape of its output as
+komodo-cli prints it (field names, strings vs numbers, amounts with 8 decimals), but the data
+is made up: hashes and txids are sha256 hashes of labels such as 'tx3', pubkeys are '02' and
+such a hash, and amounts, heights and times are invented. So they check that the response
+types read that shape, not that it still matches the komodod of today. Captures from
+komodo-cli, with the komodod version that produced them, would replace them; none are here yet.
+
+Edit this script and run it, rather than editing the JSON files: python3 tests/fixtures/generate.py
+"""
+import json, hashlib, os
+OUT=os.path.join(os.path.dirname(os.path.abspath(__file__)),'rpc')
+def h(label): return hashlib.sha256(label.encode()).hexdigest()
+R=['REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4','RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR','RH9SLewAA6DdA9mkhxETjHSsMqVUMQB9kc','RPWpWHZB4tj2RaSFkPHi8FPsUGrLaZHoW6','REMVB6fnE5ZZcxv6HojVbKyRfFsCg8JX5H','RYGAJ7w2q1xmLcnqzECSctQqMMjtPbKkwV']
+B=['bUy5shS9vfE27TzhnLAcqWZYRT5Wfcecb6','bTNAxBC8FsqDyz4a5saZfyreNKz3tzkeui']
+Z=['zs1kfhjdt2fkj5hmtlgul27kegrm3rh6kuy5dazsg6nzn45s3zdd55wdr5hwxgj59nklmz3y2w77pp','zs12cvx9md2n2lr88fp5n0phxjtw65eqm52467ujcq2qge7jkfyuy4x6la7tnyyttwtplflk9eq5pv','zs15a48ssqs2s86x3a0m7zqwgyam5gu59ldjsk63y8ga4757v2g0u8w8v5veff75dxem0zwsna36yw']
+BLK=[h('block%d'%i) for i in range(6)]
+TX=[h('tx%d'%i) for i in range(10)]
+PUB=['02'+h('pub%d'%i) for i in range(4)]
+SPK_P2PKH='76a914'+h('spk')[:40]+'88ac'
+def spk(addr, hx=None):
+    return {"asm":"OP_DUP OP_HASH160 %s OP_EQUALVERIFY OP_CHECKSIG"%(hx or SPK_P2PKH[6:46]),"hex":SPK_P2PKH,"reqSigs":1,"type":"pubkeyhash","addresses":[addr]}
+# a DER signature with its r and s made up, so the transactions decode but do not verify
+SIG='3044022075a543997d84f12798350c09bdef2cdb171bf41ed3e4a5f808af2feb0c56263002203bf0c2e5e6ba6d8e2b7a4b7e8a1f0b3d2c6e4a9f1d7b5c3e2a1f0e9d8c7b6a59'
+def rawtx(script_sig):
+    return ('0400008085202f8901'+h('rawin')+'00000000'+script_sig+'feffffff0200e1f505000000001976a914'+h('o1')[:40]+'88ac'
+            +'50c3000000000000'+'1976a914'+h('o2')[:40]+'88ac'+'5fb3b35d'+'000000000000000000000000000000')
+RAWTX=rawtx('6a47'+SIG+'0121'+PUB[0])
+UNSIGNED_RAWTX=rawtx('00')
+F={}
+F['getaddressbalance']={"balance":1378296712,"received":25497310000}
+F['getaddressdeltas']=[
+ {"satoshis":25000000000,"txid":TX[0],"index":0,"blockindex":1,"height":1521833,"address":R[0]},
+ {"satoshis":-23621703288,"txid":TX[1],"index":0,"blockindex":3,"height":1522904,"address":R[0]}]
+F['getaddressmempool']=[
+ {"address":R[0],"txid":TX[2],"index":1,"satoshis":497310000,"timestamp":1569432871},
+ {"address":R[0],"txid":TX[3],"index":0,"satoshis":-1378296712,"timestamp":1569432906,"prevtxid":TX[1],"prevout":1}]
+F['getaddresstxids']=[TX[0],TX[1],TX[2]]
+F['getaddressutxos']=[
+ {"address":R[0],"txid":TX[1],"outputIndex":1,"script":SPK_P2PKH,"satoshis":1378296712,"height":1522904},
+ {"address":R[0],"txid":TX[2],"outputIndex":1,"script":SPK_P2PKH,"satoshis":497310000,"height":1523011}]
+F['getsnapshot']={"start_time":1569432950,"addresses":[{"addr":R[1],"amount":"10000000.00000000"},{"addr":R[2],"amount":"5231742.91031123"},{"addr":R[3],"amount":"1022.56000000"}],
+ "total":15232765.47031123,"average":5077588.49010374,"utxos":3,"total_addresses":3,"ignored_addresses":0,"skipped_cc_utxos":2,"cc_utxo_value":0.0002,"total_includeCCvouts":15232765.47051123,"ending_height":1523011,"end_time":1569432951}
+F['coinsupply']={"result":"success","coin":"KMD","height":1523011,"supply":113927283.70941325,"zfunds":28.29181018,"sprout":28.29181018,"total":113927312.00122343}
+F['getbestblockhash']=BLK[0]
+vp=lambda delta: [{"id":"sprout","monitored":True,"chainValue":28.29181018,"chainValueZat":2829181018,**({"valueDelta":0.0,"valueDeltaZat":0} if delta else {})},
+                  {"id":"sapling","monitored":True,"chainValue":1562.68716112,"chainValueZat":156268716112,**({"valueDelta":-0.0001,"valueDeltaZat":-10000} if delta else {})}]
+F['getblock']={"hash":BLK[1],"confirmations":3,"rawconfirmations":3,"size":5376,"height":1523009,"version":4,"merkleroot":h('merkle'),"segid":-1,
+ "finalsaplingroot":h('sapling'),"tx":[TX[4],TX[5]],"time":1569432728,"nonce":"0000bb1a2c0000000000000000000000000000000000000000000000057b3a3b","solution":"00a0e7c4"+h('sol')*2,
+ "bits":"1d0af7b6","difficulty":193148583.0993219,"chainwork":"00000000000000000000000000000000000000000000000004aa3af15b9f6fb4","anchor":h('anchor'),"blocktype":"mined","valuePools":vp(True),
+ "previousblockhash":BLK[2],"nextblockhash":BLK[3]}
+fork=lambda id,v: {"id":id,"version":v,"enforce":{"status":True,"found":4000,"required":750,"window":4000},"reject":{"status":True,"found":4000,"required":950,"window":4000}}
+F['getblockchaininfo']={"chain":"main","blocks":1523011,"headers":1523011,"bestblockhash":BLK[0],"difficulty":193148583.0993219,"verificationprogress":0.9999993117458432,
+ "chainwork":"00000000000000000000000000000000000000000000000004aa3af4e06a8e5d","pruned":False,"commitments":23811,"valuePools":vp(False),
+ "softforks":[fork("bip34",2),fork("bip66",3),fork("bip65",4)],
+ "upgrades":{"5ba81b19":{"name":"Overwinter","activationheight":1140000,"status":"active","info":"See https://z.cash/upgrade/overwinter.html for details."},
+             "76b809bb":{"name":"Sapling","activationheight":1140000,"status":"active","info":"See https://z.cash/upgrade/sapling.html for details."}},
+ "consensus":{"chaintip":"76b809bb","nextblock":"76b809bb"}}
+F['getblockcount']=1523011
+F['getblockhash']=BLK[1]
+F['getblockheader']={"hash":BLK[1],"confirmations":3,"height":1523009,"version":4,"merkleroot":h('merkle'),"finalsaplingroot":h('sapling'),"time":1569432728,
+ "nonce":"0000bb1a2c0000000000000000000000000000000000000000000000057b3a3b","solution":"00a0e7c4"+h('sol')*2,"bits":"1d0af7b6","difficulty":193148583.0993219,
+ "chainwork":"00000000000000000000000000000000000000000000000004aa3af15b9f6fb4","segid":-1,"previousblockhash":BLK[2],"nextblockhash":BLK[3]}
+F['getchaintips']=[{"height":1523011,"hash":BLK[0],"branchlen":0,"status":"active"},{"height":1522310,"hash":BLK[4],"branchlen":1,"status":"valid-fork"},{"height":1419288,"hash":BLK[5],"branchlen":1,"status":"valid-headers"}]
+F['getdifficulty']=193148583.0993219
+F['getmempoolinfo']={"size":2,"bytes":4521,"usage":10944}
+F['getrawmempool']=[TX[2],TX[3]]
+F['getrawmempool_verbose']={TX[2]:{"size":2261,"fee":0.0001,"time":1569432871,"height":1523011,"startingpriority":1069574.394736842,"currentpriority":1069574.394736842,"depends":[]},
+ TX[3]:{"size":2260,"fee":0.0001,"time":1569432906,"height":1523011,"startingpriority":0,"currentpriority":0,"depends":[TX[2]]}}
+F['gettxout']={"bestblock":BLK[0],"confirmations":108,"rawconfirmations":108,"value":13.78296712,"scriptPubKey":spk(R[0]),"version":4,"coinbase":False}
+F['gettxoutsetinfo']={"height":1523011,"bestblock":BLK[0],"transactions":2373822,"txouts":3520612,"bytes_serialized":246473915,"hash_serialized":h('utxoset'),"total_amount":113927283.70941325}
+F['minerids']={"mined":[{"notaryid":0,"KMDaddress":R[1],"pubkey":PUB[0],"blocks":21},{"notaryid":1,"KMDaddress":R[2],"pubkey":PUB[1],"blocks":19},{"pubkey":"external miners","blocks":1960}],"numnotaries":64}
+F['notaries']={"notaries":[{"pubkey":PUB[0],"BTCaddress":"1Ktz64YgKhEnfGxdYc8JwXdZaJqWqTEZSB","KMDaddress":R[1]},{"pubkey":PUB[1],"BTCaddress":"1HdMZ8CLWyVTDk6Zu3BhLYtmyAcBqT9fVF","KMDaddress":R[2]}],"numnotaries":64,"height":1523011,"timestamp":1569432950}
+F['getinfo']={"version":2001526,"protocolversion":170008,"KMDversion":"0.4.0a","synced":True,"notarized":1522990,"prevMoMheight":1522990,"notarizedhash":BLK[4],"notarizedtxid":TX[6],
+ "notarizedtxid_height":"mempool","KMDnotarized_height":0,"notarized_confirms":0,"walletversion":60000,"balance":13.78296712,"interest":0.01243521,"blocks":1523011,"longestchain":1523011,
+ "timeoffset":-1,"tiptime":1569432851,"connections":8,"proxy":"","difficulty":193148583.0993219,"testnet":False,"keypoololdest":1566295162,"keypoolsize":101,"paytxfee":0.0,
+ "relayfee":1e-06,"errors":"","CCid":2,"name":"KMD","sapling":61,"p2pport":7770,"rpcport":7771,"magic":-1992264702,"premine":0,"reward":"0","halving":"0","decay":"0","endsubsidy":"0","notarypay":"0","staked":0}
+F['getblocksubsidy']={"miner":3.0}
+F['getblocktemplate']={"capabilities":["proposal"],"version":4,"previousblockhash":BLK[0],"finalsaplingroothash":h('sapling'),
+ "transactions":[{"data":RAWTX,"hash":TX[2],"depends":[],"fee":10000,"sigops":1}],
+ "coinbasetxn":{"data":"0400008085202f89010000000000000000000000000000000000000000000000000000000000000000ffffffff0603c33c170101ffffffff01"+"e0a3bb11000000002321"+PUB[0]+"ac00000000000000000000000000000000000000",
+  "hash":TX[7],"depends":[],"fee":-10000,"sigops":1,"coinbasevalue":300010000,"required":True},
+ "longpollid":BLK[0]+"2","target":"0000000af7b60000000000000000000000000000000000000000000000000000","mintime":1569432500,"mutable":["time","transactions","prevblock"],
+ "noncerange":"00000000ffffffff","sigoplimit":60000,"sizelimit":4000000,"curtime":1569432960,"bits":"1d0af7b6","height":1523012}
+F['getlocalsolps']=0.0
+F['getmininginfo']={"blocks":1523011,"currentblocksize":0,"currentblocktx":0,"difficulty":193148583.0993219,"errors":"","genproclimit":-1,"localsolps":0,"networksolps":11587493,"networkhashps":11587493,
+ "pooledtx":2,"testnet":False,"chain":"main","staking":False,"generate":False,"numthreads":-1}
+F['getnetworkhashps']=11587493
+F['getnetworksolps']=11587493
+F['prioritisetransaction']=True
+F['submitblock']="duplicate"
+F['getaddednodeinfo']=[{"addednode":"78.47.196.146","connected":True,"addresses":[{"address":"78.47.196.146:7770","connected":"outbound"}]},{"addednode":"5.9.102.210","connected":False,"addresses":[]}]
+F['getconnectioncount']=8
+F['getdeprecationinfo']={"version":2001526,"subversion":"/MagicBean:2.0.15-rc2/","deprecationheight":2000000}
+F['getnettotals']={"totalbytesrecv":196721003,"totalbytessent":45338712,"timemillis":1569432962511,"uploadtarget":{"timeframe":86400,"target":0,"target_reached":False,"serve_historical_blocks":True,"bytes_left_in_cycle":0,"time_left_in_cycle":0}}
+F['getnetworkinfo']={"version":2001526,"subversion":"/MagicBean:2.0.15-rc2/","protocolversion":170008,"localservices":"0000000070000005","timeoffset":-1,"connections":8,
+ "networks":[{"name":"ipv4","limited":False,"reachable":True,"proxy":"","proxy_randomize_credentials":False},{"name":"ipv6","limited":False,"reachable":True,"proxy":"","proxy_randomize_credentials":False},{"name":"onion","limited":True,"reachable":False,"proxy":"","proxy_randomize_credentials":False}],
+ "relayfee":1e-06,"localaddresses":[{"address":"136.243.58.134","port":7770,"score":4}],"warnings":""}
+F['getpeerinfo']=[
+ {"id":1,"addr":"78.47.196.146:7770","addrlocal":"136.243.58.134:49632","services":"0000000070000005","lastsend":1569432960,"lastrecv":1569432961,"bytessent":5602771,"bytesrecv":24471206,"conntime":1569345571,"timeoffset":0,"pingtime":0.028403,"version":170008,"subver":"/MagicBean:2.0.15-rc2/","inbound":False,"startingheight":1521607,"banscore":0,"synced_headers":1523011,"synced_blocks":1523011,"inflight":[],"whitelisted":False},
+ {"id":7,"addr":"5.9.102.210:54174","services":"0000000070000005","lastsend":1569432958,"lastrecv":1569432958,"bytessent":1882,"bytesrecv":1514,"conntime":1569432950,"timeoffset":-1,"pingtime":0.0,"version":170008,"subver":"/MagicBean:2.0.15-rc2/","inbound":True,"startingheight":-1,"banscore":0,"synced_headers":-1,"synced_blocks":-1,"inflight":[],"whitelisted":False}]
+F['listbanned']=[{"address":"185.25.48.236/255.255.255.255","banned_until":1569519363}]
+F['setban']=None
+F['createrawtransaction']=UNSIGNED_RAWTX
+vin=[{"txid":TX[1],"vout":1,"scriptSig":{"asm":SIG+"[ALL] "+PUB[0],"hex":"47"+SIG+"0121"+PUB[0]},"sequence":4294967294}]
+vout=[{"value":1.0,"interest":0.0,"valueZat":100000000,"n":0,"scriptPubKey":spk(R[1])},
+      {"value":0.0005,"interest":0.0,"valueZat":50000,"n":1,"scriptPubKey":spk(R[0])},
+      {"value":0.0,"interest":0.0,"valueZat":0,"n":2,"scriptPubKey":{"asm":"OP_RETURN 6b6f6d6f646f","hex":"6a066b6f6d6f646f","type":"nulldata"}}]
+dec={"txid":TX[2],"overwintered":True,"version":4,"versiongroupid":"892f2085","locktime":1572057951,"expiryheight":1523031,"vin":vin,"vout":vout,"vjoinsplit":[],"valueBalance":0.0,"vShieldedSpend":[],"vShieldedOutput":[]}
+F['decoderawtransaction']=dec
+F['decodescript']={"asm":"2 "+PUB[0]+" "+PUB[1]+" 2 OP_CHECKMULTISIG","hex":"5221"+PUB[0]+"21"+PUB[1]+"52ae","reqSigs":2,"type":"multisig","addresses":[R[0],R[1]],"p2sh":B[0]}
+F['getrawtransaction']=RAWTX
+coinbase_vin=[{"coinbase":"03c13c170101","sequence":4294967295}]
+F['getrawtransaction_verbose']={"hex":RAWTX,"txid":TX[4],"overwintered":True,"version":4,"versiongroupid":"892f2085","locktime":0,"expiryheight":0,"vin":coinbase_vin,
+ "vout":[{"value":3.0001,"interest":0.0,"valueZat":300010000,"n":0,"scriptPubKey":{"asm":PUB[0]+" OP_CHECKSIG","hex":"21"+PUB[0]+"ac","reqSigs":1,"type":"pubkey","addresses":[R[0]]}}],
+ "vjoinsplit":[],"valueBalance":0.0,"vShieldedSpend":[],"vShieldedOutput":[],"blockhash":BLK[1],"height":1523009,"confirmations":3,"rawconfirmations":3,"time":1569432728,"blocktime":1569432728}
+F['signrawtransaction']={"hex":RAWTX,"complete":True}
+F['signrawtransaction_incomplete']={"hex":RAWTX,"complete":False,"errors":[{"txid":TX[1],"vout":1,"scriptSig":"","sequence":4294967294,"error":"Operation not valid with the current stack size"}]}
+F['sendrawtransaction']=TX[2]
+F['createmultisig']={"address":B[0],"redeemScript":"5221"+PUB[0]+"21"+PUB[1]+"52ae"}
+F['backupwallet']=None
+F['dumpprivkey']="UvjpBLS27ZhBdCyw2hQNrTksQkLWCEvybf4CiqyC6vJNM3cb6Qio"
+F['dumpwallet']="/home/komodo/wallet-backup-20190925"
+F['getbalance']=13.78296712
+F['getnewaddress']=R[3]
+F['getrawchangeaddress']=R[4]
+F['getreceivedbyaddress']=254.9731
+detail=lambda cat,amt,v,addr,fee=None: dict({"account":"","address":addr,"category":cat,"amount":amt,"vout":v,"size":245}, **({"fee":fee} if fee is not None else {}))
+F['gettransaction']={"amount":-1.0,"fee":-0.0001,"rawconfirmations":108,"confirmations":108,"blockhash":BLK[2],"blockindex":3,"blocktime":1569426305,"expiryheight":1522924,
+ "txid":TX[1],"walletconflicts":[],"time":1569426280,"timereceived":1569426280,"vjoinsplit":[],
+ "details":[detail("send",-1.0,0,R[1],-0.0001)],"hex":RAWTX}
+F['getunconfirmedbalance']=4.9731
+F['getwalletinfo']={"walletversion":60000,"balance":13.78296712,"unconfirmed_balance":4.9731,"immature_balance":0.0,"txcount":42,"keypoololdest":1566295162,"keypoolsize":101,"paytxfee":0.0,"seedfp":h('seed')}
+F['importprivkey']=R[5]
+F['listaddressgroupings']=[[[R[0],13.78296712,""],[R[4],0.0]],[[R[3],0.0,"savings"]]]
+F['listlockunspent']=[{"txid":TX[1],"vout":1}]
+F['listreceivedbyaddress']=[{"address":R[0],"account":"","amount":254.9731,"rawconfirmations":108,"confirmations":108,"txids":[TX[0],TX[2]]},
+ {"address":R[3],"account":"","amount":0.0,"rawconfirmations":0,"confirmations":0,"txids":[]}]
+ltx=lambda cat,amt,v,addr,tx,conf,fee=None,block=None: dict({"account":"","address":addr,"category":cat,"amount":amt,"vout":v,
+   **({"fee":fee} if fee is not None else {}),"rawconfirmations":conf,"confirmations":conf,
+   **({"blockhash":block[0],"blockindex":block[1],"blocktime":block[2]} if block else {}),
+   "expiryheight":0 if cat in ("generate","immature") else 1522924,"txid":tx,"walletconflicts":[],"time":1569426280,"timereceived":1569426280,"vjoinsplit":[],"size":245})
+txs=[ltx("receive",250.0,0,R[0],TX[0],1178,block=(BLK[5],1,1568794162)),
+     ltx("send",-1.0,0,R[1],TX[1],108,-0.0001,(BLK[2],3,1569426305)),
+     ltx("generate",3.0001,0,R[0],TX[8],150,block=(BLK[3],0,1569424014)),
+     ltx("immature",3.0001,0,R[0],TX[4],3,block=(BLK[1],0,1569432728)),
+     ltx("receive",4.9731,1,R[0],TX[2],0)]
+F['listsinceblock']={"transactions":txs[1:],"lastblock":BLK[0]}
+F['listtransactions']=txs
+F['listunspent']=[
+ {"txid":TX[1],"vout":1,"generated":False,"address":R[0],"account":"","amount":13.78296712,"interest":0.01243521,"scriptPubKey":SPK_P2PKH,"rawconfirmations":108,"confirmations":108,"spendable":True},
+ {"txid":TX[8],"vout":0,"generated":True,"address":R[4],"amount":3.0001,"interest":0.0,"scriptPubKey":"21"+PUB[0]+"ac","rawconfirmations":150,"confirmations":150,"spendable":True}]
+F['lockunspent']=True
+F['resendwallettransactions']=[TX[2]]
+F['sendmany']=TX[9]
+F['sendtoaddress']=TX[9]
+F['setpubkey']={"address":R[0],"ismine":True,"pubkey":PUB[0]}
+F['settxfee']=True
+F['signmessage']="H6sliOnVrT/V0HRAIpLpWnLvYuYvRkYCUjQZRFYlrzvXAjcCoILBuVjRuTTgyFXWfphUJfrU7GKBDkxCwCb4Z0w="
+F['z_exportkey']="secret-extended-key-main1qv2m8d7yqqqqpq9vx5k47zw0wmh0nl6fd3gyx5g4dmd4yt9yfnp7t5xqdtwccnsp3r5k6xhvr9wlpzmqhg6kuxgkg8vj8lavkg8jpx6s4gcefxk4ln03c9yu3qxxtasduwsy6fnkuwzzxtxvwk26my0lszqdn6tr8hjc7ykh2pjsdl2v0p9rpuaxkhsj4r5afxspzmfxgvh2lvgjyp6hdqm7esuvxhr3fz6yl6kgzslul43j3xs2lgc5prkwxuvmxrhg6snfk76lgq3tq3zf3"
+F['z_exportwallet']="/home/komodo/zwallet-backup-20190925"
+F['z_getbalance']=1.2345
+F['z_getnewaddress']=Z[2]
+op={"id":"opid-"+'a3f2c1d0-5b6e-4f7a-8c9d-0e1f2a3b4c5d',"status":"success","creation_time":1569432700,"result":{"txid":TX[9]},"execution_secs":48.327261253,"method":"z_sendmany",
+ "params":{"fromaddress":R[0],"amounts":[{"address":Z[0],"amount":1.0}],"minconf":1,"fee":0.0001}}
+failed={"id":"opid-"+'0b1c2d3e-4f5a-4b6c-9d7e-8f9a0b1c2d3e',"status":"failed","creation_time":1569432800,"error":{"code":-6,"message":"Insufficient funds, no UTXOs found for taddr from address."},"method":"z_sendmany",
+ "params":{"fromaddress":R[3],"amounts":[{"address":Z[1],"amount":100.0}],"minconf":1,"fee":0.0001}}
+F['z_getoperationresult']=[op]
+F['z_getoperationstatus']=[op,failed,{"id":"opid-"+'c2d3e4f5-a6b7-4c8d-9e0f-1a2b3c4d5e6f',"status":"executing","creation_time":1569432950,"method":"z_shieldcoinbase","params":{}}]
+F['z_gettotalbalance']={"transparent":"13.78296712","interest":"0.01243521","private":"1.2345","total":"15.01746712"}
+F['z_listaddresses']=[Z[0],Z[1],Z[2]]
+F['z_listoperationids']=[op['id'],failed['id']]
+F['z_listreceivedbyaddress']=[{"txid":TX[9],"amount":1.0,"memo":"f6"+"0"*1022,"outindex":0,"rawconfirmations":12,"confirmations":12,"change":False},
+ {"txid":TX[7],"amount":0.2345,"memo":"f6"+"0"*1022,"outindex":1,"rawconfirmations":4,"confirmations":4,"change":True}]
+F['z_mergetoaddress']={"remainingUTXOs":0,"remainingTransparentValue":0.0,"remainingNotes":0,"remainingShieldedValue":0.0,"mergingUTXOs":2,"mergingTransparentValue":16.78306712,"mergingNotes":0,"mergingShieldedValue":0.0,"opid":"opid-"+'d4e5f6a7-b8c9-4d0e-8f1a-2b3c4d5e6f7a'}
+F['z_sendmany']=op['id']
+F['z_shieldcoinbase']={"remainingUTXOs":0,"remainingValue":0.0,"shieldingUTXOs":2,"shieldingValue":6.0002,"opid":"opid-"+'e5f6a7b8-c9d0-4e1f-9a2b-3c4d5e6f7a8b'}
+import re
+AMOUNT_KEYS={"amount","value","fee","interest","balance","relayfee","paytxfee","chainValue","valueDelta","supply","zfunds","sprout","miner",
+ "unconfirmed_balance","immature_balance","total_amount","vpub_old","vpub_new","remainingTransparentValue","remainingShieldedValue",
+ "mergingTransparentValue","mergingShieldedValue","remainingValue","shieldingValue","valueBalance"}
+SCALAR_AMOUNTS={"getbalance","getreceivedbyaddress","getunconfirmedbalance","z_getbalance"}
+def amt(x): return "__AMT__%.8f__" % x
+CC={}
+# CC modules: Tokens, Assets, Oracles, Channels, Faucet, Rewards and Dice. These are written out as they are.
+CC['channelsclose']={'result': 'success', 'hex': '0400008085202f89011cc41a326bff5573e7ea422600a923652100bfc52ea03125000dd6de11216cda00000000ffffffff0240420f00000000002da22b80201cc41a326bff5573e7ea422600a923652100bfc52ea03125000dd6de11216cda8103020000af0382020000000000000000000000000000000000000000000000000000236a211cc41a326bff5573e7ea422600a923652100bfc52ea03125000dd6de11216cdae200000000000000000000000000000000000000'}
+CC['channelsinfo']={'result': 'success', 'Channel CC address': 'RQy3rwX3E5mD1j4GqaCJUF5PG5DhmKEVuF', 'Destination address': 'REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4', 'Number of payments': 10, 'Denomination (satoshi)': '1000000', 'Amount (satoshi)': '10000000', 'Transactions': [{'Open': 'c1f3b6a5d8e0f2a4c6e8b0d2f4a6c8e0b2d4f6a8c0e2b4d6f8a0c2e4b6d8f0a2'}, {'Payment': '5e8a1c3f6b9d2e4a7c0f3b6d9e1a4c7f0b3d6e9a2c5f8b1d4e7a0c3f6b9d2e4a', 'Number of payments': 2, 'Amount': 2000000, 'Destination': 'REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4', 'Revealed secret': '9b45f581189206678918bde6e0c3339da693c6476dd7e8a8df0a537febe4def9', 'Payments left': 8}, {'Payment': '9d2e4a7c0f3b6d9e1a4c7f0b3d6e9a2c5f8b1d4e7a0c3f6b9d2e4a5e8a1c3f6b', 'Number of payments': 1, 'Amount': 1000000, 'Destination': 'REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4', 'Revealed secret': 'd0814b08ee549cbe385be5ff200530bfe30638dbede0a865e8af2803b570dc29', 'Payments left': 7}]}
+CC['channelslist']=['c1f3b6a5d8e0f2a4c6e8b0d2f4a6c8e0b2d4f6a8c0e2b4d6f8a0c2e4b6d8f0a2', '0df7c4d844f08dba08abd4bb174558739f17cfe268feb005fb6333b3761d9203']
+CC['channelsopen']={'result': 'success', 'hex': '0400008085202f89018877b2971c3eaa3e198656db1bc253a7203449a72d0661e24ec6dc572e515d7000000000ffffffff0240420f00000000002da22b80208877b2971c3eaa3e198656db1bc253a7203449a72d0661e24ec6dc572e515d708103020000af0382020000000000000000000000000000000000000000000000000000236a218877b2971c3eaa3e198656db1bc253a7203449a72d0661e24ec6dc572e515d70e200000000000000000000000000000000000000'}
+CC['channelspayment']={'result': 'success', 'hex': '0400008085202f8901a68adda2d28ad438b298d49b84c49ff29219dc7a8ac201a794c165b957841f1500000000ffffffff0240420f00000000002da22b8020a68adda2d28ad438b298d49b84c49ff29219dc7a8ac201a794c165b957841f158103020000af0382020000000000000000000000000000000000000000000000000000236a21a68adda2d28ad438b298d49b84c49ff29219dc7a8ac201a794c165b957841f15e200000000000000000000000000000000000000'}
+CC['channelsrefund']={'result': 'success', 'hex': '0400008085202f8901aba30ff2b463183d16d097298623b1e440647e51b0e77489accd7b3a5edfeb9c00000000ffffffff0240420f00000000002da22b8020aba30ff2b463183d16d097298623b1e440647e51b0e77489accd7b3a5edfeb9c8103020000af0382020000000000000000000000000000000000000000000000000000236a21aba30ff2b463183d16d097298623b1e440647e51b0e77489accd7b3a5edfeb9ce200000000000000000000000000000000000000'}
+CC['diceaddfunds']={'result': 'success', 'hex': '0400008085202f8901f31ccfe94ee9dc47fda841f9fd64c72c305d92300041bf169a197605830a277000000000ffffffff0200e1f505000000002da22b8020f31ccfe94ee9dc47fda841f9fd64c72c305d92300041bf169a197605830a27708103020000af038202000000000000000000000000000000000000000000000000000000236a21f31ccfe94ee9dc47fda841f9fd64c72c305d92300041bf169a197605830a2770e500000000000000000000000000000000000000'}
+CC['dicebet']={'result': 'success', 'hex': '0400008085202f89016c78e22f47033c0176691de8a2b3d2ae2ab1747458e3a72b1fc5517b5911516d00000000ffffffff0200e1f505000000002da22b80206c78e22f47033c0176691de8a2b3d2ae2ab1747458e3a72b1fc5517b5911516d8103020000af038202000000000000000000000000000000000000000000000000000000236a216c78e22f47033c0176691de8a2b3d2ae2ab1747458e3a72b1fc5517b5911516de500000000000000000000000000000000000000'}
+CC['dicefinish']={'result': 'success', 'hex': '0400008085202f89017a173c8d7dd502c2d64e2ae6ca7b4a7bace1813ea9e4970fdfb1faf95a38cfe600000000ffffffff0200e1f505000000002da22b80207a173c8d7dd502c2d64e2ae6ca7b4a7bace1813ea9e4970fdfb1faf95a38cfe68103020000af038202000000000000000000000000000000000000000000000000000000236a217a173c8d7dd502c2d64e2ae6ca7b4a7bace1813ea9e4970fdfb1faf95a38cfe6e500000000000000000000000000000000000000'}
+CC['dicefund']={'result': 'success', 'hex': '0400008085202f8901e5ded0497f494429a22be0ab15b33c1cbd4279af587b23ae4ec262f96e23567f00000000ffffffff0200e1f505000000002da22b8020e5ded0497f494429a22be0ab15b33c1cbd4279af587b23ae4ec262f96e23567f8103020000af038202000000000000000000000000000000000000000000000000000000236a21e5ded0497f494429a22be0ab15b33c1cbd4279af587b23ae4ec262f96e23567fe500000000000000000000000000000000000000'}
+CC['diceinfo']={'result': 'success', 'fundingtxid': '7d1a2b3c4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b', 'name': 'KMDICE', 'sbits': 76228676291403, 'minbet': '0.00100000', 'maxbet': '10.00000000', 'maxodds': 100, 'timeoutblocks': 5, 'funding': '18932.10450000'}
+CC['dicelist']=['7d1a2b3c4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b']
+CC['dicestatus']={'result': 'success', 'status': 'win', 'won': amt(1.98)}
+CC['faucetfund']={'result': 'success', 'hex': '0400008085202f8901f8181b7f21ecd0ed628d4719acaebe8f37fdd40def29261c787ec8a83d96db9800000000ffffffff0200e1f505000000002da22b8020f8181b7f21ecd0ed628d4719acaebe8f37fdd40def29261c787ec8a83d96db988103020000af038202000000000000000000000000000000000000000000000000000000236a21f8181b7f21ecd0ed628d4719acaebe8f37fdd40def29261c787ec8a83d96db98e500000000000000000000000000000000000000'}
+CC['faucetget']={'result': 'success', 'hex': '0400008085202f8901fd75da3c9c5c92fbbebe8f3f6abfcd3c5d7e7fd0ffc96948fee8fbbf5efe407b00000000ffffffff0200e1f505000000002da22b8020fd75da3c9c5c92fbbebe8f3f6abfcd3c5d7e7fd0ffc96948fee8fbbf5efe407b8103020000af038202000000000000000000000000000000000000000000000000000000236a21fd75da3c9c5c92fbbebe8f3f6abfcd3c5d7e7fd0ffc96948fee8fbbf5efe407be500000000000000000000000000000000000000'}
+CC['faucetinfo']={'result': 'success', 'name': 'Faucet', 'funding': '6.95500000'}
+CC['mytokenorders']=[{'funcid': 's', 'txid': '4e5d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d', 'vout': 0, 'amount': '1000', 'askamount': '1000', 'origaddress': 'CeP8Pzq8pHNF5RiTQJNyRTa6oAmFEf9Wbf', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', 'totalrequired': '12.00000000', 'price': '0.01200000'}]
+CC['oraclescreate']={'result': 'success', 'hex': '0400008085202f89010dc8b2ffb115da3c2033684293f9cd96abb447279163cfe878663e7d1ff043b101000000ffffffff0210270000000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac0000000000000000226a200dc8b2ffb115da3c2033684293f9cd96abb447279163cfe878663e7d1ff043b100000000000000000000000000000000000000'}
+CC['oraclesdata']={'result': 'success', 'hex': '0400008085202f8901a5899a47a03e7ba6c6c7f5f6317ced0c5af6eb2a16e86254a04319a218430e8b01000000ffffffff0210270000000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac0000000000000000226a20a5899a47a03e7ba6c6c7f5f6317ced0c5af6eb2a16e86254a04319a218430e8b00000000000000000000000000000000000000'}
+CC['oraclesfund']={'result': 'success', 'hex': '0400008085202f890186bd0209d8b8ea06058e29f46aebbb2d1cfc1a5f8c4386450c8f27be6d04e3da01000000ffffffff0210270000000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac0000000000000000226a2086bd0209d8b8ea06058e29f46aebbb2d1cfc1a5f8c4386450c8f27be6d04e3da00000000000000000000000000000000000000'}
+CC['oraclesinfo']={'result': 'success', 'txid': '4895f631316a649e216153aee7a574bd281686265dc4e8d37597f72353facac3', 'name': 'KMDBTC', 'description': 'KMD/BTC price in satoshis', 'format': 'IhL', 'marker': 'RVqJCSrdBm1gYJZS1h7dgtHioA5TEYzNRk', 'registered': [{'publisher': '029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abf', 'baton': 'RKY4zmHJZ5mNtf6tfKE5VMsKoV71Euej3i', 'batontxid': '8a1c5e2e0f6b3d9d4e1c7a2b5f8e3c6d9a0b4e7f1c2d5a8b3e6f9c0d1a4b7e2f', 'lifetime': '0.30000000', 'funds': '1.97000000', 'datafee': '0.01000000'}]}
+CC['oracleslist']=['4895f631316a649e216153aee7a574bd281686265dc4e8d37597f72353facac3', '0df7c4d844f08dba08abd4bb174558739f17cfe268feb005fb6333b3761d9203']
+CC['oraclesregister']={'result': 'success', 'hex': '0400008085202f8901774aea2a507e460cda44e91b3ebd60ca0ff4a44563fdd290f3397805cf98b9a601000000ffffffff0210270000000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac0000000000000000226a20774aea2a507e460cda44e91b3ebd60ca0ff4a44563fdd290f3397805cf98b9a600000000000000000000000000000000000000'}
+CC['oraclessample']={'result': 'success', 'data': ['1571112', '0000000003a74b4ad1be3b78d6fe9a52f4ad47e3c4ab7a0a4b7d12a41a5bf4b6', '6482']}
+CC['oraclessamples']={'result': 'success', 'samples': [{'txid': '8a1c5e2e0f6b3d9d4e1c7a2b5f8e3c6d9a0b4e7f1c2d5a8b3e6f9c0d1a4b7e2f', 'data': ['1571112', '0000000003a74b4ad1be3b78d6fe9a52f4ad47e3c4ab7a0a4b7d12a41a5bf4b6', '6482']}, {'txid': 'd7e4b1a9c3f6e2d5a8b0c4e7f1a3d6b9e2c5f8a1d4b7e0c3f6a9d2b5e8c1f4a7', 'data': ['1571111', '00000000019b8c4e77a2c45f3b1d6e2a5c8f9d0e1b4a7c3d6f9e2b5a8c1d4e7f', '6479']}]}
+CC['oraclessubscribe']={'result': 'success', 'hex': '0400008085202f89016a365282a54a014e3926c4418b482dd99b99c48d3f30eee41898e5ab1113ad6301000000ffffffff0210270000000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac0000000000000000226a206a365282a54a014e3926c4418b482dd99b99c48d3f30eee41898e5ab1113ad6300000000000000000000000000000000000000'}
+CC['rewardscreatefunding']={'result': 'success', 'hex': '0400008085202f890127c54e36fa538bce52f2152b161b0851e91a0bd867e887da2ab9de82315fecb800000000ffffffff0200e1f505000000002da22b802027c54e36fa538bce52f2152b161b0851e91a0bd867e887da2ab9de82315fecb88103020000af038202000000000000000000000000000000000000000000000000000000236a2127c54e36fa538bce52f2152b161b0851e91a0bd867e887da2ab9de82315fecb8e500000000000000000000000000000000000000'}
+CC['rewardsinfo']={'result': 'success', 'fundingtxid': '3a0c1e2f4b6d8a9c7e5f3b1d2a4c6e8f0b9d7a5c3e1f2b4d6a8c0e9f7b5d3a1c', 'name': 'LOCK', 'sbits': 1262702412, 'APR': '5.00000000', 'minseconds': 86400, 'maxseconds': 864000, 'mindeposit': '1.00000000', 'funding': '9990.00000000', 'locked': '150.00000000'}
+CC['rewardslist']=['3a0c1e2f4b6d8a9c7e5f3b1d2a4c6e8f0b9d7a5c3e1f2b4d6a8c0e9f7b5d3a1c']
+CC['rewardslock']={'result': 'success', 'hex': '0400008085202f890175e6db485265aa8b364229520ca0cd6c4241571d2fdc8ec1ce90cded47dcc96b00000000ffffffff0200e1f505000000002da22b802075e6db485265aa8b364229520ca0cd6c4241571d2fdc8ec1ce90cded47dcc96b8103020000af038202000000000000000000000000000000000000000000000000000000236a2175e6db485265aa8b364229520ca0cd6c4241571d2fdc8ec1ce90cded47dcc96be500000000000000000000000000000000000000'}
+CC['rewardsunlock']={'result': 'success', 'hex': '0400008085202f8901eb5c2b9ec7d4e08e9070e7e9d1de1dd31bfed749642a0f993c570626565d520600000000ffffffff0200e1f505000000002da22b8020eb5c2b9ec7d4e08e9070e7e9d1de1dd31bfed749642a0f993c570626565d52068103020000af038202000000000000000000000000000000000000000000000000000000236a21eb5c2b9ec7d4e08e9070e7e9d1de1dd31bfed749642a0f993c570626565d5206e500000000000000000000000000000000000000'}
+CC['tokenask']={'result': 'success', 'hex': '0400008085202f8901ccb6ad9dc78b0c8bc14202a40ec47770bdbe5f55ee44590f3b2ba4b5119be56400000000ffffffff0100000000000000002da22b8020ccb6ad9dc78b0c8bc14202a40ec47770bdbe5f55ee44590f3b2ba4b5119be5648103020000af038202000000000000000000000000000000000000'}
+CC['tokenbalance']={'result': 'success', 'CCaddress': 'CeP8Pzq8pHNF5RiTQJNyRTa6oAmFEf9Wbf', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', 'balance': 99999000}
+CC['tokenbid']={'result': 'success', 'hex': '0400008085202f890165af9f08a21e00049062c39bfe95d00db623e534b64c2263e9b0054d9818422900000000ffffffff0100000000000000002da22b802065af9f08a21e00049062c39bfe95d00db623e534b64c2263e9b0054d981842298103020000af038202000000000000000000000000000000000000'}
+CC['tokencancelask']={'result': 'success', 'hex': '0400008085202f8901ff57d3c92f1f39838ebd4ce7064c7960c4edf4cf294795d41217599cc9701b9400000000ffffffff0100000000000000002da22b8020ff57d3c92f1f39838ebd4ce7064c7960c4edf4cf294795d41217599cc9701b948103020000af038202000000000000000000000000000000000000'}
+CC['tokencancelbid']={'result': 'success', 'hex': '0400008085202f8901927ddfe8b31368bc21f085aa9537d099bcb7ff7e5860293ccd7c90a93426d26700000000ffffffff0100000000000000002da22b8020927ddfe8b31368bc21f085aa9537d099bcb7ff7e5860293ccd7c90a93426d2678103020000af038202000000000000000000000000000000000000'}
+CC['tokencreate']={'result': 'success', 'hex': '0400008085202f890164b8bc35ae6bd74c6d1bf1d7e1e2a68bf5b0a08c2e1f8f8a87c74cb0ae01e31a0000000049483045022100d7a7c8f2d2e8dc0a0b1c6a3d4b53d1a9ee8f1b0d2a3c6e95f0c9c2f3b71a8e4c02205e2e1e3a4c7c2a6d1b8f7f8f0f3b2a1c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f401ffffffff0400e1f505000000002da22b8020e6f6ee2bef2c3f9b8cd1e1a3d2e5c1b2f0e9a8d7c6b5a4f3e2d1c0b9a8f7e6d58103020000af0382020000000000000000000000000000000000000000000000000000000000000000000000000000000010270000000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfacf0b9f505000000002321029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abfac00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000'}
+CC['tokenfillask']={'result': 'success', 'hex': '0400008085202f8901b94a81c020b84ab1747327f5b0521464fea36b7e6dfe005158e155b89f33c2df00000000ffffffff0100000000000000002da22b8020b94a81c020b84ab1747327f5b0521464fea36b7e6dfe005158e155b89f33c2df8103020000af038202000000000000000000000000000000000000'}
+CC['tokenfillbid']={'result': 'success', 'hex': '0400008085202f8901daad8a951dfeea5fa8e7e040cb191cf7ab8fa462d55ced259c8084c7ba3a171600000000ffffffff0100000000000000002da22b8020daad8a951dfeea5fa8e7e040cb191cf7ab8fa462d55ced259c8084c7ba3a17168103020000af038202000000000000000000000000000000000000'}
+CC['tokeninfo']={'result': 'success', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', 'owner': '029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abf', 'name': 'SUPERLITE', 'supply': 100000000, 'description': 'Tokens for the SUPERLITE loyalty program'}
+CC['tokenlist']=['1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', '9b4c1a6a1a0e7d7a4d4b6b3e0f5d9f2c8e6a5b3c1d0e9f8a7b6c5d4e3f2a1b0c']
+# komodod leaves out totalrequired and price of an order whose price is not positive, as of the last bid
+CC['tokenorders']=[{'funcid': 's', 'txid': '4e5d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d', 'vout': 0, 'amount': '1000', 'askamount': '1000', 'origaddress': 'CeP8Pzq8pHNF5RiTQJNyRTa6oAmFEf9Wbf', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', 'totalrequired': '12.00000000', 'price': '0.01200000'}, {'funcid': 'B', 'txid': '8f2a4c6e8a0c2e4f6a8c0e2a4c6e8f0a2c4e6a8c0e2f4a6c8e0a2c4e6f8a0c2e', 'vout': 0, 'amount': '4.00000000', 'bidamount': '4.00000000', 'origaddress': 'CVLUtuKBkzcmPmPBuSyu1DAz8N6G7ENdQ8', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', 'totalrequired': 400, 'price': '0.01000000'}, {'funcid': 'b', 'txid': '2b7d9f1a3c5e7a9b1d3f5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b7d9f1a3c5e7a', 'vout': 0, 'amount': '5.50000000', 'bidamount': '5.50000000', 'origaddress': 'CeP8Pzq8pHNF5RiTQJNyRTa6oAmFEf9Wbf', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864', 'totalrequired': 500, 'price': '0.01100000'}, {'funcid': 's', 'txid': '6c1e3a5c7e9a1c3e5a7c9e1a3c5e7a9c1e3a5c7e9a1c3e5a7c9e1a3c5e7a9c1e', 'vout': 0, 'amount': '20', 'askamount': '20', 'origaddress': 'CVLUtuKBkzcmPmPBuSyu1DAz8N6G7ENdQ8', 'tokenid': '9b4c1a6a1a0e7d7a4d4b6b3e0f5d9f2c8e6a5b3c1d0e9f8a7b6c5d4e3f2a1b0c', 'totalrequired': '2.00000000', 'price': '0.10000000'}, {'funcid': 'b', 'txid': '5a3c1e9f7d5b3a1c9e7f5d3b1a9c7e5f3d1b9a7c5e3f1d9b7a5c3e1f9d7b5a3c', 'vout': 1, 'amount': '0.00010000', 'bidamount': '0.00010000', 'origaddress': 'CVLUtuKBkzcmPmPBuSyu1DAz8N6G7ENdQ8', 'tokenid': '1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864'}]
+CC['tokentransfer']={'result': 'success', 'hex': '0400008085202f8902b5b9f1b2d1a1e51c3c3f3f1a5ce1a5b0c5e6f2b3a3e4d2c9f1b2d1a1e51c3c00000000a74ca5a281a1a0819ca28194a067a5658021029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abf8140ffffffff0300000000000000002da22b8020e6f6ee2bef2c3f9b8cd1e1a3d2e5c1b2f0e9a8d7c6b5a4f3e2d1c0b9a8f7e6d58103020000af03820200000000000000000000000000000000000000'}
+def fmt(obj, key=None, file=None):
+    if isinstance(obj, dict):
+        return {k: fmt(v, k, file) for k, v in obj.items()}
+    if isinstance(obj, list):
+        if file == 'listaddressgroupings' and len(obj) in (2, 3) and isinstance(obj[0], str):
+            return [obj[0], amt(obj[1])] + obj[2:]
+        return [fmt(v, key, file) for v in obj]
+    if isinstance(obj, (int, float)) and not isinstance(obj, bool) and key in AMOUNT_KEYS:
+        if file == 'getblocktemplate':
+            return obj  # satoshis
+        return amt(obj)
+    return obj
+for k,v in F.items():
+    if k in SCALAR_AMOUNTS:
+        v = amt(v)
+    elif k == 'coinsupply':
+        v = dict(fmt(v, file=k), total=amt(v['total']))
+    elif k not in ('getsnapshot', 'getaddressbalance'):  # satoshis and doubles
+        v = fmt(v, file=k)
+    text = json.dumps(v, indent=2)
+    text = re.sub(r'"__AMT__(-?[0-9.]+)__"', r'\1', text)
+    with open(os.path.join(OUT,k+'.json'),'w') as f:
+        f.write(text + '\n')
+for k,v in CC.items():
+    with open(os.path.join(OUT,k+'.json'),'w') as f:
+        f.write(re.sub(r'"__AMT__(-?[0-9.]+)__"', r'\1', json.dumps(v, indent=2)) + '\n')
+print(len(F) + len(CC))
@@ -0,0 +1,117 @@
+extern crate komodo_rpc_client;
+
+use komodo_rpc_client::{komodo_interest, Amount, Client, KomodoRpcApi, KIP0001_ACTIVATION_HEIGHT, KOMODO_ENDOFERA};
+
+const LOCK_TIME: u32 = 1_570_000_000;
+const HOUR: u32 = 60 * 60;
+const DAY: u32 = 24 * HOUR;
+
+fn kmd(coins: i64) -> Amount {
+    Amount::from_coins(coins).unwrap()
+}
+
+#[test]
+fn pays_five_percent_a_year() {
+    // (10_000_000_000 / 10_512_000) * (10 * 1440 - 59) satoshis
+    assert_eq!(komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME + 10 * DAY), Amount::from_sat(13_638_291));
+    // from the first hour on: 951 * (60 - 59)
+    assert_eq!(komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME + HOUR), Amount::from_sat(951));
+}
+
+#[test]
+fn requires_minimum_age_value_and_locktime() {
+    assert_eq!(komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME + HOUR - 1), Amount::ZERO);
+    assert_eq!(komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME), Amount::ZERO);
+    assert_eq!(komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME - DAY), Amount::ZERO);
+
+    // 10 KMD is the minimum
+    assert_eq!(komodo_interest(1_600_000, Amount::from_sat(999_999_999), LOCK_TIME, LOCK_TIME + DAY), Amount::ZERO);
+    assert_eq!(komodo_interest(1_600_000, kmd(10), LOCK_TIME, LOCK_TIME + DAY), Amount::from_sat(95 * 1381));
+
+    // a locktime that is a block height
+    assert_eq!(komodo_interest(1_600_000, kmd(100), 1_599_990, LOCK_TIME), Amount::ZERO);
+    assert_eq!(komodo_interest(1_600_000, kmd(100), 0, LOCK_TIME), Amount::ZERO);
+}
+
+#[test]
+fn caps_at_one_month() {
+    let month = komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME + 31 * DAY);
+    // 951 * (31 * 1440 - 59)
+    assert_eq!(month, Amount::from_sat(42_396_531));
+
+    assert_eq!(komodo_interest(1_600_000, kmd(100), LOCK_TIME, LOCK_TIME + 200 * DAY), month);
+}
+
+#[test]
+fn applies_kip0001() {
+    let interest = komodo_interest(KIP0001_ACTIVATION_HEIGHT, kmd(100), LOCK_TIME, LOCK_TIME + 31 * DAY);
+    assert_eq!(interest, Amount::from_sat(42_396_531 / 500));
+
+    let before = komodo_interest(KIP0001_ACTIVATION_HEIGHT - 1, kmd(100), LOCK_TIME, LOCK_TIME + 31 * DAY);
+    assert_eq!(before, Amount::from_sat(42_396_531));
+}
+
+#[test]
+fn ends_with_the_era() {
+    assert_eq!(komodo_interest(KOMODO_ENDOFERA, kmd(100), LOCK_TIME, LOCK_TIME + DAY), Amount::ZERO);
+    assert!(komodo_interest(KOMODO_ENDOFERA - 1, kmd(100), LOCK_TIME, LOCK_TIME + DAY).is_positive());
+}
+
+#[test]
+fn uses_rules_of_output_height() {
+    // below 1000000: 1000 KMD / 20 * (10 * 1440 - 59) / 525600
+    assert_eq!(komodo_interest(500_000, kmd(1_000), LOCK_TIME, LOCK_TIME + 10 * DAY), Amount::from_sat(136_425_038));
+    // more than 25000 KMD: 30000 KMD / 20 * (40 * 1440 - 59) / 525600, capped at a year, not a month
+    assert_eq!(komodo_interest(500_000, kmd(30_000), LOCK_TIME, LOCK_TIME + 40 * DAY), Amount::from_sat(16_421_518_264));
+
+    // below 250000 rewards are paid per fraction of a year, without taking off the first 59 minutes:
+    // 100 KMD * 5% / (525600 / (100 * 1440))
+    let old_lock_time = 1_480_000_000;
+    assert_eq!(komodo_interest(100_000, kmd(100), old_lock_time, old_lock_time + 100 * DAY), Amount::from_sat(166_666_666));
+    assert_eq!(komodo_interest(100_000, kmd(100), old_lock_time, old_lock_time + 400 * DAY), Amount::from_sat(500_000_000));
+    // an hour: 100 KMD * 5% / (525600 / 60)
+    assert_eq!(komodo_interest(100_000, kmd(100), old_lock_time, old_lock_time + HOUR), Amount::from_sat(57_077));
+}
+
+/// Cross-checks `komodo_interest` with the `interest` komodod reports in `listunspent`, for every UTXO of
+/// 10 KMD or more in the wallet of a running KMD node, and needs one from height 3484958 (KIP-0001) on.
+/// The other tests only check the port against values worked out by hand.
+///
+/// Run it with `cargo test --test interest -- --ignored` against a synced node with a funded wallet.
+#[test]
+#[ignore]
+fn agrees_with_komodod() {
+    let client = Client::new_komodo_client().unwrap();
+
+    let (info, unspents) = loop {
+        let info = client.get_info().unwrap();
+        let unspents = client.list_unspent(None, None, None).unwrap();
+
+        // the interest of `listunspent` is of the tip `getinfo` was asked for
+        if client.get_info().unwrap().blocks == info.blocks {
+            break (info, unspents);
+        }
+    };
+
+    let mut checked = 0;
+    let mut checked_since_kip0001 = 0;
+    for unspent in unspents.iter().filter(|unspent| unspent.amount >= kmd(10) && unspent.rawconfirmations > 0) {
+        let tx = client.get_raw_transaction(unspent.txid).unwrap().decode().unwrap();
+        let tx_height = info.blocks + 1 - unspent.rawconfirmations as u32;
+
+        assert_eq!(
+            komodo_interest(tx_height, unspent.amount, tx.lock_time, info.tiptime),
+            unspent.interest.unwrap_or(Amount::ZERO),
+            "interest of {}:{}, {} KMD at height {} with locktime {}, tip time {}",
+            unspent.txid, unspent.vout, unspent.amount, tx_height, tx.lock_time, info.tiptime
+        );
+
+        checked += 1;
+        if tx_height >= KIP0001_ACTIVATION_HEIGHT {
+            checked_since_kip0001 += 1;
+        }
+    }
+
+    assert!(checked > 0, "the wallet has no UTXOs of 10 KMD or more");
+    assert!(checked_since_kip0001 > 0, "the wallet has no UTXOs of 10 KMD or more from height {} on", KIP0001_ACTIVATION_HEIGHT);
+}
@@ -0,0 +1,145 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::{ApiError, BlockHash, Client, KomodoRpcApi, MockTransport, RpcBatch, RpcErrorCode, RpcRequest};
+use serde_json::Value;
+
+#[test]
+fn replays_response_by_method_and_params() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("getblockhash", json!([1]), json!("0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"))
+        .add_response("getblockhash", json!([2]), json!("0cae3c9ad9fe7b1af6e5c9adf1e4eb1a4eee60f1da2b7e71bd4b7cc1d81e6e7e"));
+
+    let client = Client::with_transport(transport);
+
+    assert_eq!(
+        client.get_block_hash(2).unwrap().to_string(),
+        "0cae3c9ad9fe7b1af6e5c9adf1e4eb1a4eee60f1da2b7e71bd4b7cc1d81e6e7e"
+    );
+}
+
+#[test]
+fn falls_back_to_method_response() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("getblockhash", json!([1]), json!("0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"))
+        .add_method_response("getblockhash", json!("0cae3c9ad9fe7b1af6e5c9adf1e4eb1a4eee60f1da2b7e71bd4b7cc1d81e6e7e"));
+
+    let client = Client::with_transport(transport);
+
+    assert_eq!(
+        client.get_block_hash(1).unwrap().to_string(),
+        "0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"
+    );
+    assert_eq!(
+        client.get_block_hash(1234).unwrap().to_string(),
+        "0cae3c9ad9fe7b1af6e5c9adf1e4eb1a4eee60f1da2b7e71bd4b7cc1d81e6e7e"
+    );
+}
+
+#[test]
+fn returns_rpc_errors() {
+    let mut transport = MockTransport::new();
+    transport.add_error("getblockhash", json!([99999999]), -8, "Block height out of range");
+
+    let client = Client::with_transport(transport);
+
+    match client.get_block_hash(99999999) {
+        Err(ApiError::RPC(error)) => {
+            assert_eq!(error.code, -8);
+            assert_eq!(error.message, "Block height out of range");
+        },
+        other => panic!("expected RPC error, got {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_method_is_not_found() {
+    let client = Client::with_transport(MockTransport::new());
+
+    match client.get_connection_count() {
+        Err(ApiError::RPC(error)) => assert_eq!(error.code, -32601),
+        other => panic!("expected RPC error, got {:?}", other),
+    }
+}
+
+#[test]
+fn records_requests() {
+    let mut transport = MockTransport::new();
+    transport.add_method_response("getblockhash", json!("0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"));
+
+    let client = Client::with_transport(transport);
+    client.get_block_hash(1).unwrap();
+
+    let requests = client.transport().requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["method"], "getblockhash");
+    assert_eq!(requests[0]["params"], json!([1]));
+}
+
+#[test]
+fn sends_batches() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("getblockhash", json!([1]), json!("0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"))
+        .add_error("getblockhash", json!([99999999]), -8, "Block height out of range")
+        .add_response("getblockcount", json!([]), json!(1234567));
+
+    let client = Client::with_transport(transport);
+
+    let mut batch = RpcBatch::new();
+    let hash = batch.add::<BlockHash, _>(&RpcRequest::new1("getblockhash", 1)).unwrap();
+    let out_of_range = batch.add::<BlockHash, _>(&RpcRequest::new1("getblockhash", 99999999)).unwrap();
+    let count = batch.add::<u32, _>(&RpcRequest::new0("getblockcount")).unwrap();
+
+    let response = client.send_batch(&batch).unwrap();
+
+    assert_eq!(
+        response.get(&hash).unwrap().to_string(),
+        "0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"
+    );
+    assert!(response.get(&out_of_range).is_err());
+    assert_eq!(response.get(&count).unwrap(), 1234567);
+    assert_eq!(client.transport().requests().len(), 3);
+}
+
+#[test]
+fn calls_any_method() {
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("addnode", json!(["5.9.102.210", "onetry"]), Value::Null)
+        .add_response("getblockhash", json!([1]), json!("0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6"))
+        .add_method_response("paxprice", json!({"base": "KMD", "rel": "BTC", "price": "0.00012000"}));
+
+    let client = Client::with_transport(transport);
+
+    // a RPC without a result
+    client.call::<()>("addnode", vec![json!("5.9.102.210"), json!("onetry")]).unwrap();
+    assert_eq!(client.transport().requests()[0]["params"], json!(["5.9.102.210", "onetry"]));
+
+    // into any type of the crate
+    let hash: BlockHash = client.call("getblockhash", vec![json!(1)]).unwrap();
+    assert_eq!(hash.to_string(), "0a47c1323f393650f7221c217d19d149d002d35444f47fde61be2dd90fbde8e6");
+
+    // or as JSON
+    let price = client.call_raw("paxprice", vec![json!("KMD"), json!("BTC")]).unwrap();
+    assert_eq!(price["price"], "0.00012000");
+    assert_eq!(client.call_raw("addnode", vec![json!("5.9.102.210"), json!("onetry")]).unwrap(), Value::Null);
+
+    // the same errors as the other RPCs
+    let error = client.call_raw("notamethod", vec![]).unwrap_err();
+    assert_eq!(error.rpc_error_code(), Some(RpcErrorCode::MethodNotFound));
+    assert!(client.call::<u32>("paxprice", vec![]).is_err());
+}
+
+#[test]
+fn unimplemented_methods_fail_without_a_request() {
+    let client = Client::with_transport(MockTransport::new());
+
+    match client.z_importwallet() {
+        Err(ApiError::Other(message)) => assert_eq!(message, "not implemented"),
+        other => panic!("expected an error, got {:?}", other),
+    }
+}
@@ -0,0 +1,82 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::{OracleData, OracleFormat, OracleValue};
+use komodo_rpc_client::*;
+
+fn encode(format: &str, values: &[OracleValue]) -> Result<String, ApiError> {
+    OracleFormat::new(format).unwrap().encode(values).map(|data| data.0)
+}
+
+#[test]
+fn encodes_strings_and_bytes_with_their_length() {
+    assert_eq!(encode("s", &["HelloWorld".into()]).unwrap(), "0a48656c6c6f576f726c64");
+    assert_eq!(encode("S", &["Hi".into()]).unwrap(), "02004869");
+    assert_eq!(encode("d", &[vec![0xde, 0xad].into()]).unwrap(), "02dead");
+    assert_eq!(encode("D", &[vec![0xff; 300].into()]).unwrap(), format!("2c01{}", "ff".repeat(300)));
+
+    assert!(encode("s", &["x".repeat(256).into()]).is_err());
+    assert!(encode("S", &["x".repeat(256).into()]).is_ok());
+}
+
+#[test]
+fn encodes_integers_little_endian() {
+    assert_eq!(encode("c", &[(-1i8).into()]).unwrap(), "ff");
+    assert_eq!(encode("C", &[200u8.into()]).unwrap(), "c8");
+    assert_eq!(encode("t", &[(-2i16).into()]).unwrap(), "feff");
+    assert_eq!(encode("T", &[0x1234u16.into()]).unwrap(), "3412");
+    assert_eq!(encode("i", &[(-1i32).into()]).unwrap(), "ffffffff");
+    assert_eq!(encode("I", &[1571112u32.into()]).unwrap(), "28f91700");
+    assert_eq!(encode("l", &[(-1i64).into()]).unwrap(), "ffffffffffffffff");
+    assert_eq!(encode("L", &[u64::MAX.into()]).unwrap(), "ffffffffffffffff");
+
+    // any integer that fits
+    assert_eq!(encode("C", &[7i64.into()]).unwrap(), "07");
+    assert!(encode("C", &[256u32.into()]).is_err());
+    assert!(encode("I", &[(-1i32).into()]).is_err());
+    assert!(encode("l", &[u64::MAX.into()]).is_err());
+}
+
+#[test]
+fn encodes_block_header() {
+    let blockhash: BlockHash = "0000000003a74b4ad1be3b78d6fe9a52f4ad47e3c4ab7a0a4b7d12a41a5bf4b6".parse().unwrap();
+    let merkleroot: BlockHash = "6ae3d3fa2b1a6a0c5d7fce7be1e9bd2b0c9a7a8cb3f8f3d4b7d3e52d1e4a0c4e".parse().unwrap();
+
+    let data = encode("Ihh", &[1571112u32.into(), blockhash.into(), merkleroot.into()]).unwrap();
+
+    // a uint256 is stored with its least significant byte first
+    let mut expected = String::from("28f91700");
+    expected.push_str("b6f45b1aa4127d4b0a7aabc4e347adf4529afed6783bbed14a4ba70300000000");
+    expected.push_str("4e0c4a1e2de5d3b7d4f3f8b38c7a9a0c2bbde9e17bce7f5d0c6a1a2bfad3e36a");
+    assert_eq!(data, expected);
+}
+
+#[test]
+fn rejects_invalid_formats_and_values() {
+    assert!(OracleFormat::new("").is_err());
+    assert!(OracleFormat::new("sx").is_err());
+
+    // too few values, and a value of the wrong kind
+    assert!(encode("sL", &["KMD".into()]).is_err());
+    assert!(encode("s", &[1u8.into()]).is_err());
+    assert!(encode("L", &["1".into()]).is_err());
+    assert!(encode("h", &[vec![0u8; 32].into()]).is_err());
+}
+
+#[test]
+fn publishes_encoded_data() {
+    let oracletxid: Txid = "4895f631316a649e216153aee7a574bd281686265dc4e8d37597f72353facac3".parse().unwrap();
+    let data = OracleFormat::new("L").unwrap().encode(&[6482u64.into()]).unwrap();
+    assert_eq!(data, OracleData(String::from("5219000000000000")));
+
+    let mut transport = MockTransport::new();
+    transport
+        .add_response("oraclesdata", json!([oracletxid, "5219000000000000"]), json!({"result": "success", "hex": "01"}))
+        // the data fee in satoshis, as komodod reads "1.00000000" as 1 satoshi
+        .add_response("oraclesregister", json!([oracletxid, "100000000"]), json!({"result": "success", "hex": "02"}));
+    let client = Client::with_transport(transport);
+
+    assert_eq!(client.oracles_data(&oracletxid, &data).unwrap().hex, "01");
+    assert_eq!(client.oracles_register(&oracletxid, Amount::from_sat(100_000_000)).unwrap().hex, "02");
+}
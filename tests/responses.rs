@@ -0,0 +1,1164 @@
+//! Deserializes synthetic komodod responses, one fixture in `tests/fixtures/rpc` per RPC.
+//! A fixture holds the `result` of the RPC in the shape `komodo-cli` prints it, with made-up data.
+//! The fixtures are written by `tests/fixtures/generate.py`; none are captured from a komodod,
+//! so these tests do not catch changes in the output of a newer komodod.
+
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use std::fs;
+use std::path::PathBuf;
+
+use komodo_rpc_client::arguments::address::{Address, FromAddresses, SendAmount};
+use komodo_rpc_client::arguments::{
+    AddressList, CreateRawTransactionInputs, CreateRawTransactionOutputs, SendManyAmounts,
+};
+use komodo_rpc_client::*;
+use serde_json::Value;
+
+const R_ADDRESS: &str = "REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4";
+const Z_ADDRESS: &str = "zs1kfhjdt2fkj5hmtlgul27kegrm3rh6kuy5dazsg6nzn45s3zdd55wdr5hwxgj59nklmz3y2w77pp";
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{}.json", name));
+    let json = fs::read_to_string(&path).unwrap();
+
+    serde_json::from_str(&json).unwrap()
+}
+
+/// A client that answers `method` with the fixture `name`.
+fn client(method: &str, name: &str) -> Client<MockTransport> {
+    let mut transport = MockTransport::new();
+    transport.add_method_response(method, fixture(name));
+
+    Client::with_transport(transport)
+}
+
+fn hash(hex: &str) -> Txid {
+    serde_json::from_value(json!(hex)).unwrap()
+}
+
+fn address(addr: &str) -> Address {
+    Address::from(addr).unwrap()
+}
+
+fn amount(coins: &str) -> Amount {
+    coins.parse().unwrap()
+}
+
+#[test]
+fn get_address_balance() {
+    let balance = client("getaddressbalance", "getaddressbalance")
+        .get_address_balance(&AddressList::from(R_ADDRESS)).unwrap();
+
+    assert_eq!(balance.balance, 1378296712);
+    assert_eq!(balance.received, 25497310000);
+}
+
+#[test]
+fn get_address_deltas() {
+    let deltas = client("getaddressdeltas", "getaddressdeltas")
+        .get_address_deltas(&AddressList::from(R_ADDRESS)).unwrap();
+
+    assert_eq!(deltas.0.len(), 2);
+    assert_eq!(deltas.0[1].satoshis, -23621703288);
+}
+
+#[test]
+fn get_address_mempool() {
+    let mempool = client("getaddressmempool", "getaddressmempool")
+        .get_address_mempool(&AddressList::from(R_ADDRESS)).unwrap();
+
+    assert_eq!(mempool.0[0].prevtxid, None);
+    assert_eq!(mempool.0[1].prevout, Some(1));
+}
+
+#[test]
+fn get_address_tx_ids() {
+    let txids = client("getaddresstxids", "getaddresstxids")
+        .get_address_tx_ids(&AddressList::from(R_ADDRESS)).unwrap();
+
+    assert_eq!(txids.0.len(), 3);
+}
+
+#[test]
+fn get_address_utxos() {
+    let utxos = client("getaddressutxos", "getaddressutxos")
+        .get_address_utxos(&AddressList::from(R_ADDRESS)).unwrap();
+
+    assert_eq!(utxos.0[0].output_index, 1);
+    assert_eq!(utxos.0[1].satoshis, 497310000);
+}
+
+#[test]
+fn get_snapshot() {
+    let snapshot = client("getsnapshot", "getsnapshot").get_snapshot().unwrap();
+
+    assert_eq!(snapshot.addresses.len(), 3);
+    assert_eq!(snapshot.addresses[0].amount, amount("10000000"));
+    assert_eq!(snapshot.cc_utxo_value, 0.0002);
+
+    client("getsnapshot", "getsnapshot").get_snapshot_max(3).unwrap();
+}
+
+#[test]
+fn coinsupply() {
+    let supply = client("coinsupply", "coinsupply").coinsupply(1523011).unwrap();
+
+    assert_eq!(supply.coin, "KMD");
+    assert_eq!(supply.zfunds, amount("28.29181018"));
+}
+
+#[test]
+fn get_best_block_hash() {
+    let hash = client("getbestblockhash", "getbestblockhash").get_best_block_hash().unwrap();
+
+    assert_eq!(hash.to_string(), "3da2892d37823d9298e1d5011d7dcfaaf2d9d9a6d465e99be33af5be1d87c12b");
+}
+
+#[test]
+fn malformed_hash_is_an_error() {
+    for malformed in &["3da2892d", "zz", "3da2892d37823d9298e1d5011d7dcfaaf2d9d9a6d465e99be33af5be1d87c12z"] {
+        let mut transport = MockTransport::new();
+        transport.add_method_response("getbestblockhash", json!(malformed));
+
+        assert!(Client::with_transport(transport).get_best_block_hash().is_err());
+    }
+
+    let mut mempool = fixture("getrawmempool");
+    mempool[0] = json!("not a txid");
+    let mut transport = MockTransport::new();
+    transport.add_method_response("getrawmempool", mempool);
+    assert!(Client::with_transport(transport).get_raw_mempool().is_err());
+}
+
+#[test]
+fn get_block() {
+    let block = client("getblock", "getblock").get_block(String::from("1523009")).unwrap();
+
+    assert_eq!(block.height, 1523009);
+    assert_eq!(block.segid, -1);
+    assert_eq!(block.difficulty, 193148583.0993219);
+    assert_eq!(block.tx.len(), 2);
+    assert_eq!(block.value_pools[1].value_delta, Some(amount("-0.0001")));
+    assert_eq!(block.value_pools[1].value_delta_zat, Some(-10000));
+}
+
+#[test]
+fn get_blockchain_info() {
+    let info = client("getblockchaininfo", "getblockchaininfo").get_blockchain_info().unwrap();
+
+    assert_eq!(info.chain, "main");
+    assert_eq!(info.softforks.len(), 3);
+    assert_eq!(info.upgrades.unwrap()["76b809bb"].name, "Sapling");
+    assert_eq!(info.value_pools[0].value_delta, None);
+    assert_eq!(info.consensus.chaintip, "76b809bb");
+}
+
+#[test]
+fn get_block_count() {
+    let count = client("getblockcount", "getblockcount").get_block_count().unwrap();
+
+    assert_eq!(count.0, 1523011);
+}
+
+#[test]
+fn get_block_hash() {
+    let hash = client("getblockhash", "getblockhash").get_block_hash(1523009).unwrap();
+
+    assert_eq!(hash.to_string(), "9a59c5f8229aab55e9f855173ef94485aab8497eea0588f365c871d6d0561722");
+}
+
+#[test]
+fn get_block_header() {
+    let header = client("getblockheader", "getblockheader")
+        .get_block_header(&"9a59c5f8229aab55e9f855173ef94485aab8497eea0588f365c871d6d0561722".parse().unwrap()).unwrap();
+
+    assert_eq!(header.height, 1523009);
+    assert!(header.nextblockhash.is_some());
+}
+
+#[test]
+fn get_chaintips() {
+    let tips = client("getchaintips", "getchaintips").get_chaintips().unwrap();
+
+    assert_eq!(tips.0.len(), 3);
+    assert_eq!(tips.0[1].branchlen, 1);
+}
+
+#[test]
+fn get_difficulty() {
+    let difficulty = client("getdifficulty", "getdifficulty").get_difficulty().unwrap();
+
+    assert_eq!(difficulty, 193148583.0993219);
+}
+
+#[test]
+fn get_mempool_info() {
+    let info = client("getmempoolinfo", "getmempoolinfo").get_mempool_info().unwrap();
+
+    assert_eq!(info.size, 2);
+}
+
+#[test]
+fn get_raw_mempool() {
+    let mempool = client("getrawmempool", "getrawmempool").get_raw_mempool().unwrap();
+
+    assert_eq!(mempool.0.len(), 2);
+}
+
+#[test]
+fn get_raw_mempool_verbose() {
+    let mempool = client("getrawmempool", "getrawmempool_verbose").get_raw_mempool_verbose().unwrap();
+    let tx = &mempool.0[&hash("1f3cb18e896256d7d6bb8c11a6ec71f005c75de05e39beae5d93bbd1e2c8b7a9")];
+
+    assert_eq!(tx.fee, amount("0.0001"));
+    assert_eq!(tx.depends, vec![hash("27ca64c092a959c7edc525ed45e845b1de6a7590d173fd2fad9133c8a779a1e3")]);
+}
+
+#[test]
+fn get_tx_out() {
+    let txout = client("gettxout", "gettxout")
+        .get_tx_out(&hash("709b55bd3da0f5a838125bd0ee20c5bfdd7caba173912d4281cae816b79a201b"), 1).unwrap()
+        .unwrap();
+
+    assert_eq!(txout.value, amount("13.78296712"));
+    assert_eq!(txout.script_pubkey.req_sigs, Some(1));
+    assert_eq!(txout.script_pubkey.addresses, vec![R_ADDRESS]);
+}
+
+#[test]
+fn get_tx_out_of_spent_output() {
+    let mut transport = MockTransport::new();
+    transport.add_method_response("gettxout", Value::Null);
+
+    let txout = Client::with_transport(transport)
+        .get_tx_out(&hash("709b55bd3da0f5a838125bd0ee20c5bfdd7caba173912d4281cae816b79a201b"), 0).unwrap();
+
+    assert!(txout.is_none());
+}
+
+#[test]
+fn get_tx_out_set_info() {
+    let info = client("gettxoutsetinfo", "gettxoutsetinfo").get_tx_out_set_info().unwrap();
+
+    assert_eq!(info.bytes_serialized, 246473915);
+}
+
+#[test]
+fn minerids() {
+    let ids = client("minerids", "minerids").minerids(String::from("1523011")).unwrap();
+
+    assert_eq!(ids.mined.len(), 3);
+    assert_eq!(ids.mined[2].notaryid, None);
+    assert_eq!(ids.mined[2].pubkey, "external miners");
+}
+
+#[test]
+fn notaries() {
+    let notaries = client("notaries", "notaries").notaries(String::from("1523011")).unwrap();
+
+    assert_eq!(notaries.numnotaries, 64);
+    assert_eq!(notaries.notaries[0].kmd_address, "RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR");
+}
+
+#[test]
+fn get_info() {
+    let info = client("getinfo", "getinfo").get_info().unwrap();
+
+    assert_eq!(info.name, "KMD");
+    assert_eq!(info.balance, amount("13.78296712"));
+    assert_eq!(info.interest, Some(amount("0.01243521")));
+    assert_eq!(info.timeoffset, -1);
+    assert_eq!(info.relayfee, amount("0.000001"));
+}
+
+#[test]
+fn get_block_subsidy() {
+    let subsidy = client("getblocksubsidy", "getblocksubsidy").get_block_subsidy(None).unwrap();
+
+    assert_eq!(subsidy.miner, amount("3"));
+}
+
+#[test]
+fn get_block_template() {
+    let template = client("getblocktemplate", "getblocktemplate").get_block_template(None).unwrap();
+
+    assert_eq!(template.height, 1523012);
+    assert_eq!(template.coinbasetxn.fee, -10000);
+    assert_eq!(template.transactions.len(), 1);
+}
+
+#[test]
+fn get_local_sol_ps() {
+    let solps = client("getlocalsolps", "getlocalsolps").get_local_sol_ps().unwrap();
+
+    assert_eq!(solps, 0.0);
+}
+
+#[test]
+fn get_mining_info() {
+    let info = client("getmininginfo", "getmininginfo").get_mining_info().unwrap();
+
+    assert_eq!(info.genproclimit, -1);
+    assert_eq!(info.networksolps, 11587493);
+}
+
+#[test]
+fn get_network_hash_ps() {
+    let hashps = client("getnetworkhashps", "getnetworkhashps").get_network_hash_ps().unwrap();
+
+    assert_eq!(hashps, 11587493);
+}
+
+#[test]
+fn get_network_sol_ps() {
+    let solps = client("getnetworksolps", "getnetworksolps").get_network_sol_ps().unwrap();
+
+    assert_eq!(solps, 11587493);
+}
+
+#[test]
+fn prioritise_transaction() {
+    let txid = hash("27ca64c092a959c7edc525ed45e845b1de6a7590d173fd2fad9133c8a779a1e3");
+
+    assert!(client("prioritisetransaction", "prioritisetransaction").prioritise_transaction(txid, 0.0, 10000).unwrap());
+}
+
+#[test]
+fn submit_block() {
+    match client("submitblock", "submitblock").submit_block(String::from("04000000"), None).unwrap() {
+        Some(SubmitBlockResult::Duplicate) => {},
+        other => panic!("expected duplicate, got {:?}", other),
+    }
+}
+
+#[test]
+fn submit_accepted_block() {
+    let mut transport = MockTransport::new();
+    transport.add_method_response("submitblock", Value::Null);
+
+    let result = Client::with_transport(transport).submit_block(String::from("04000000"), None).unwrap();
+
+    assert!(result.is_none());
+}
+
+#[test]
+fn get_added_node_info() {
+    let info = client("getaddednodeinfo", "getaddednodeinfo").get_added_node_info(true, None).unwrap();
+
+    assert_eq!(info[0].connected, Some(true));
+    assert_eq!(info[0].addresses.as_ref().unwrap()[0].connected, "outbound");
+}
+
+#[test]
+fn get_connection_count() {
+    assert_eq!(client("getconnectioncount", "getconnectioncount").get_connection_count().unwrap(), 8);
+}
+
+#[test]
+fn get_deprecation_info() {
+    let info = client("getdeprecationinfo", "getdeprecationinfo").get_deprecation_info().unwrap();
+
+    assert_eq!(info.deprecationheight, 2000000);
+}
+
+#[test]
+fn get_net_totals() {
+    let totals = client("getnettotals", "getnettotals").get_net_totals().unwrap();
+
+    assert_eq!(totals.totalbytesrecv, 196721003);
+}
+
+#[test]
+fn get_network_info() {
+    let info = client("getnetworkinfo", "getnetworkinfo").get_network_info().unwrap();
+
+    assert_eq!(info.networks.len(), 3);
+    assert_eq!(info.timeoffset, -1);
+}
+
+#[test]
+fn get_peer_info() {
+    let peers = client("getpeerinfo", "getpeerinfo").get_peer_info().unwrap();
+
+    assert_eq!(peers[0].addrlocal, Some(String::from("136.243.58.134:49632")));
+    assert_eq!(peers[1].addrlocal, None);
+    assert_eq!(peers[1].synced_headers, -1);
+    assert_eq!(peers[1].startingheight, -1);
+}
+
+#[test]
+fn list_banned() {
+    let banned = client("listbanned", "listbanned").list_banned().unwrap();
+
+    assert_eq!(banned[0].as_ref().unwrap().banned_until, 1569519363);
+}
+
+#[test]
+fn set_ban() {
+    client("setban", "setban")
+        .set_ban(String::from("185.25.48.236"), String::from("add"), None, None).unwrap();
+}
+
+#[test]
+fn create_raw_transaction() {
+    let mut inputs = CreateRawTransactionInputs::new();
+    inputs.add(hash("709b55bd3da0f5a838125bd0ee20c5bfdd7caba173912d4281cae816b79a201b"), 1);
+    let mut outputs = CreateRawTransactionOutputs::new();
+    outputs.add(&address("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR"), amount("1"));
+
+    let tx = client("createrawtransaction", "createrawtransaction").create_raw_transaction(inputs, outputs).unwrap();
+
+    assert!(tx.0.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn decode_raw_transaction() {
+    let tx = client("decoderawtransaction", "decoderawtransaction").decode_raw_transaction("0400008085202f89").unwrap();
+
+    assert_eq!(tx.version, 4);
+    assert_eq!(tx.overwintered, Some(true));
+    assert_eq!(tx.vin[0].vout, Some(1));
+    assert!(tx.vin[0].coinbase.is_none());
+    assert_eq!(tx.vout.len(), 3);
+    assert_eq!(tx.vout[2].script_pubkey.req_sigs, None);
+    assert!(tx.vout[2].script_pubkey.addresses.is_empty());
+}
+
+#[test]
+fn decode_script() {
+    let script = client("decodescript", "decodescript").decode_script("5221").unwrap();
+
+    assert_eq!(script.script_type, ScriptType::MultiSig);
+    assert_eq!(script.req_sigs, Some(2));
+    assert_eq!(script.addresses.len(), 2);
+    assert_eq!(script.p2sh, "bUy5shS9vfE27TzhnLAcqWZYRT5Wfcecb6");
+}
+
+#[test]
+fn get_raw_transaction() {
+    let txid = hash("27ca64c092a959c7edc525ed45e845b1de6a7590d173fd2fad9133c8a779a1e3");
+    let tx = client("getrawtransaction", "getrawtransaction").get_raw_transaction(txid).unwrap();
+
+    assert!(tx.0.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn get_raw_transaction_verbose_of_coinbase() {
+    let txid = hash("41b637cfd9eb3e2f60f734f9ca44e5c1559c6f481d49d6ed6891f3e9a086ac78");
+    let tx = client("getrawtransaction", "getrawtransaction_verbose").get_raw_transaction_verbose(txid).unwrap();
+
+    assert_eq!(tx.vin[0].coinbase, Some(String::from("03c13c170101")));
+    assert!(tx.vin[0].txid.is_none());
+    assert!(tx.vin[0].script_sig.is_none());
+    assert_eq!(tx.confirmations, Some(3));
+}
+
+#[test]
+fn sign_raw_transaction_with_wallet() {
+    let tx = SerializedRawTransaction::from_hex(String::from("0400008085202f89"));
+    let signed = client("signrawtransaction", "signrawtransaction").sign_raw_transaction_with_wallet(tx).unwrap();
+
+    assert!(signed.complete);
+    assert!(signed.errors.is_none());
+}
+
+#[test]
+fn sign_raw_transaction_with_key_incomplete() {
+    let tx = SerializedRawTransaction::from_hex(String::from("0400008085202f89"));
+    let signed = client("signrawtransaction", "signrawtransaction_incomplete")
+        .sign_raw_transaction_with_key(&tx, None, None, None).unwrap();
+
+    assert!(!signed.complete);
+    assert_eq!(signed.errors.unwrap()[0].vout, 1);
+}
+
+#[test]
+fn send_raw_transaction() {
+    let signed: SignedRawTransaction = serde_json::from_value(fixture("signrawtransaction")).unwrap();
+    let txid = client("sendrawtransaction", "sendrawtransaction").send_raw_transaction(&signed).unwrap();
+
+    assert_eq!(txid.to_string(), "27ca64c092a959c7edc525ed45e845b1de6a7590d173fd2fad9133c8a779a1e3");
+}
+
+#[test]
+fn create_multisig() {
+    let multisig = client("createmultisig", "createmultisig").create_multisig(2, vec!["02", "03"]).unwrap();
+
+    assert_eq!(multisig.address, address("bUy5shS9vfE27TzhnLAcqWZYRT5Wfcecb6"));
+}
+
+#[test]
+fn backup_wallet() {
+    client("backupwallet", "backupwallet").backup_wallet("wallet-backup").unwrap();
+}
+
+#[test]
+fn dump_privkey() {
+    let key = client("dumpprivkey", "dumpprivkey").dump_privkey(R_ADDRESS).unwrap();
+
+    assert_eq!(key.len(), 52);
+}
+
+#[test]
+fn dump_wallet() {
+    let path = client("dumpwallet", "dumpwallet").dump_wallet("wallet-backup-20190925").unwrap();
+
+    assert_eq!(path, "/home/komodo/wallet-backup-20190925");
+}
+
+#[test]
+fn get_balance() {
+    assert_eq!(client("getbalance", "getbalance").get_balance(None, None).unwrap(), amount("13.78296712"));
+}
+
+#[test]
+fn get_new_address() {
+    assert_eq!(client("getnewaddress", "getnewaddress").get_new_address().unwrap(), "RPWpWHZB4tj2RaSFkPHi8FPsUGrLaZHoW6");
+}
+
+#[test]
+fn get_raw_change_address() {
+    let change = client("getrawchangeaddress", "getrawchangeaddress").get_raw_change_address().unwrap();
+
+    assert_eq!(change, address("REMVB6fnE5ZZcxv6HojVbKyRfFsCg8JX5H"));
+}
+
+#[test]
+fn get_received_by_address() {
+    let received = client("getreceivedbyaddress", "getreceivedbyaddress")
+        .get_received_by_address(&address(R_ADDRESS)).unwrap();
+
+    assert_eq!(received, amount("254.9731"));
+}
+
+#[test]
+fn get_transaction() {
+    let txid = hash("709b55bd3da0f5a838125bd0ee20c5bfdd7caba173912d4281cae816b79a201b");
+    let tx = client("gettransaction", "gettransaction").get_transaction(&txid).unwrap();
+
+    assert_eq!(tx.amount, amount("-1"));
+    assert_eq!(tx.details[0].category, "send");
+    assert_eq!(tx.details[0].fee, Some(amount("-0.0001")));
+}
+
+#[test]
+fn get_unconfirmed_balance() {
+    assert_eq!(client("getunconfirmedbalance", "getunconfirmedbalance").get_unconfirmed_balance().unwrap(), amount("4.9731"));
+}
+
+#[test]
+fn get_wallet_info() {
+    let info = client("getwalletinfo", "getwalletinfo").get_wallet_info().unwrap();
+
+    assert_eq!(info.txcount, 42);
+    assert_eq!(info.paytxfee, amount("0"));
+}
+
+#[test]
+fn import_privkey() {
+    let imported = client("importprivkey", "importprivkey")
+        .import_privkey("UvjpBLS27ZhBdCyw2hQNrTksQkLWCEvybf4CiqyC6vJNM3cb6Qio", None, false).unwrap();
+
+    assert_eq!(imported, address("RYGAJ7w2q1xmLcnqzECSctQqMMjtPbKkwV"));
+}
+
+#[test]
+fn list_address_groupings() {
+    let groupings = client("listaddressgroupings", "listaddressgroupings").list_address_groupings().unwrap();
+
+    assert_eq!(groupings.0.len(), 2);
+    assert_eq!(groupings.0[0][0].address, address(R_ADDRESS));
+    assert_eq!(groupings.0[0][0].amount, amount("13.78296712"));
+    assert_eq!(groupings.0[0][1].account, None);
+    assert_eq!(groupings.0[1][0].account, Some(String::from("savings")));
+}
+
+#[test]
+fn list_lock_unspent() {
+    let locked = client("listlockunspent", "listlockunspent").list_lock_unspent().unwrap();
+
+    assert_eq!(locked[0].vout, 1);
+}
+
+#[test]
+fn list_received_by_address() {
+    let received = client("listreceivedbyaddress", "listreceivedbyaddress")
+        .list_received_by_address(None, Some(true), None).unwrap();
+
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[0].txids.len(), 2);
+}
+
+#[test]
+fn list_since_block() {
+    let since = client("listsinceblock", "listsinceblock").list_since_block(None, None, None).unwrap();
+
+    assert_eq!(since.transactions.len(), 4);
+    assert_eq!(since.lastblock.to_string(), "3da2892d37823d9298e1d5011d7dcfaaf2d9d9a6d465e99be33af5be1d87c12b");
+}
+
+#[test]
+fn list_transactions() {
+    let txs = client("listtransactions", "listtransactions").list_transactions(None, None, None).unwrap();
+
+    assert_eq!(txs.0.len(), 5);
+    match txs.0[2].category {
+        TxCategory::Generate => {},
+        ref other => panic!("expected generate, got {:?}", other),
+    }
+    match txs.0[3].category {
+        TxCategory::Immature => {},
+        ref other => panic!("expected immature, got {:?}", other),
+    }
+    // unconfirmed transactions are not in a block yet
+    assert_eq!(txs.0[4].confirmations, 0);
+    assert!(txs.0[4].blockhash.is_none());
+}
+
+#[test]
+fn list_unspent() {
+    let unspent = client("listunspent", "listunspent").list_unspent(None, None, None).unwrap();
+
+    assert_eq!(unspent[0].vout, 1);
+    assert_eq!(unspent[0].interest, Some(amount("0.01243521")));
+    assert!(unspent[1].generated);
+    assert!(unspent[1].account.is_none());
+}
+
+#[test]
+fn lock_unspent() {
+    let locked: Vec<LockedUnspent> = serde_json::from_value(fixture("listlockunspent")).unwrap();
+
+    assert!(client("lockunspent", "lockunspent").lock_unspent(false, locked).unwrap());
+}
+
+#[test]
+fn resend_wallet_transactions() {
+    let resent = client("resendwallettransactions", "resendwallettransactions").resend_wallet_transactions().unwrap();
+
+    assert_eq!(resent.0.len(), 1);
+}
+
+#[test]
+fn send_many() {
+    let mut amounts = SendManyAmounts::new();
+    amounts.add("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR", amount("1"));
+
+    let txid = client("sendmany", "sendmany").send_many(amounts, None, None, None).unwrap();
+
+    assert_eq!(txid.to_string(), "3e812f40cd8e4ca3a92972610409922dedf1c0dbc68394fcb1c8f188a42655e2");
+}
+
+#[test]
+fn send_to_address() {
+    let txid = client("sendtoaddress", "sendtoaddress")
+        .send_to_address(address("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR"), amount("1"), None, None, None).unwrap();
+
+    assert_eq!(txid.to_string(), "3e812f40cd8e4ca3a92972610409922dedf1c0dbc68394fcb1c8f188a42655e2");
+}
+
+#[test]
+fn set_pubkey() {
+    let pubkey = client("setpubkey", "setpubkey")
+        .set_pubkey("029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abf").unwrap();
+
+    assert!(pubkey.ismine);
+    assert_eq!(pubkey.address, address(R_ADDRESS));
+}
+
+#[test]
+fn set_txfee() {
+    assert!(client("settxfee", "settxfee").set_txfee(amount("0.0001")).unwrap());
+}
+
+#[test]
+fn sign_message() {
+    let signature = client("signmessage", "signmessage").sign_message(address(R_ADDRESS), "komodo").unwrap();
+
+    assert_eq!(signature.len(), 88);
+}
+
+#[test]
+fn z_exportkey() {
+    let key = client("z_exportkey", "z_exportkey").z_exportkey(&address(Z_ADDRESS)).unwrap();
+
+    assert!(key.0.starts_with("secret-extended-key-main"));
+}
+
+#[test]
+fn z_exportwallet() {
+    let path = client("z_exportwallet", "z_exportwallet").z_exportwallet("zwallet-backup-20190925").unwrap();
+
+    assert_eq!(path, "/home/komodo/zwallet-backup-20190925");
+}
+
+#[test]
+fn z_getbalance() {
+    assert_eq!(client("z_getbalance", "z_getbalance").z_getbalance(&address(Z_ADDRESS), None).unwrap(), amount("1.2345"));
+}
+
+#[test]
+fn z_getnewaddress() {
+    let new = client("z_getnewaddress", "z_getnewaddress").z_getnewaddress().unwrap();
+
+    assert_eq!(new.to_string().len(), 78);
+}
+
+#[test]
+fn z_getoperationresult() {
+    let operations = client("z_getoperationresult", "z_getoperationresult").z_getoperationresult(vec![]).unwrap();
+    let operation = &operations.0[0];
+
+    assert_eq!(operation.status, "success");
+    assert_eq!(
+        operation.result.as_ref().unwrap()["txid"],
+        "3e812f40cd8e4ca3a92972610409922dedf1c0dbc68394fcb1c8f188a42655e2"
+    );
+    assert_eq!(operation.params.amounts.as_ref().unwrap()[0].amount, amount("1"));
+}
+
+#[test]
+fn z_getoperationstatus() {
+    let operations = client("z_getoperationstatus", "z_getoperationstatus").z_getoperationstatus(vec![]).unwrap();
+
+    assert_eq!(operations.0.len(), 3);
+    assert_eq!(operations.0[1].error.as_ref().unwrap().code, -6);
+    assert!(operations.0[1].result.is_none());
+    // an executing operation has neither a result nor an execution time yet
+    assert!(operations.0[2].execution_secs.is_none());
+    assert!(operations.0[2].params.fromaddress.is_none());
+}
+
+#[test]
+fn z_gettotalbalance() {
+    let balance = client("z_gettotalbalance", "z_gettotalbalance").z_gettotalbalance().unwrap();
+
+    assert_eq!(balance.private, amount("1.2345"));
+    assert_eq!(balance.total, amount("15.01746712"));
+}
+
+#[test]
+fn z_listaddresses() {
+    let addresses = client("z_listaddresses", "z_listaddresses").z_listaddresses(None).unwrap();
+
+    assert_eq!(addresses[0], address(Z_ADDRESS));
+}
+
+#[test]
+fn z_listoperationids() {
+    let ids = client("z_listoperationids", "z_listoperationids").z_listoperationids().unwrap();
+
+    assert_eq!(ids.len(), 2);
+}
+
+#[test]
+fn z_listreceivedbyaddress() {
+    let received = client("z_listreceivedbyaddress", "z_listreceivedbyaddress")
+        .z_listreceivedbyaddress(&address(Z_ADDRESS), None).unwrap();
+
+    assert_eq!(received.0.len(), 2);
+    assert!(received.0[1].change);
+}
+
+#[test]
+fn z_mergetoaddress() {
+    let result = client("z_mergetoaddress", "z_mergetoaddress")
+        .z_mergetoaddress(&FromAddresses::any_taddr(), &address(Z_ADDRESS), None, None, None, None, None).unwrap();
+
+    assert_eq!(result.merging_utxos, 2);
+    assert_eq!(result.merging_transparent_value, amount("16.78306712"));
+}
+
+#[test]
+fn z_sendmany() {
+    let amounts = vec![SendAmount { address: address(Z_ADDRESS), amount: amount("1"), memo: None }].into();
+    let opid = client("z_sendmany", "z_sendmany").z_sendmany(&address(R_ADDRESS), &amounts, None, None).unwrap();
+
+    assert!(opid.starts_with("opid-"));
+}
+
+#[test]
+fn z_shieldcoinbase() {
+    let result = client("z_shieldcoinbase", "z_shieldcoinbase")
+        .z_shieldcoinbase(&Address::any(), &address(Z_ADDRESS), None, None).unwrap();
+
+    assert_eq!(result.shielding_utxos, 2);
+    assert_eq!(result.shielding_value, amount("6.0002"));
+    assert_eq!(result.remaining_value, amount("0"));
+}
+
+const TOKEN_ID: &str = "1ae301aeb04cc7878a8f1f2e8ca0b0f58ba6e2e1d7f11b6d4cd76bae35bcb864";
+const PUBKEY: &str = "029c9f4163ca8ebe2c25b4f20ba7194b5f8cce7dad64acaff8186bb70125160abf";
+
+#[test]
+fn token_create() {
+    let tx = client("tokencreate", "tokencreate").token_create("SUPERLITE", amount("1"), None, None).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+    assert!(tx.complete);
+}
+
+#[test]
+fn token_transfer() {
+    let tx = client("tokentransfer", "tokentransfer").token_transfer(&TOKEN_ID.parse().unwrap(), PUBKEY, 1000).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn token_balance() {
+    let balance = client("tokenbalance", "tokenbalance").token_balance(&TOKEN_ID.parse().unwrap(), None).unwrap();
+
+    assert_eq!(balance.tokenid.to_string(), TOKEN_ID);
+    assert_eq!(balance.balance, 99999000);
+    assert!(balance.cc_address.starts_with('C'));
+}
+
+#[test]
+fn token_info() {
+    let info = client("tokeninfo", "tokeninfo").token_info(&TOKEN_ID.parse().unwrap()).unwrap();
+
+    assert_eq!(info.name, "SUPERLITE");
+    assert_eq!(info.owner, PUBKEY);
+    assert_eq!(info.supply, 100000000);
+    assert!(info.data.is_none());
+}
+
+#[test]
+fn token_list() {
+    let tokens = client("tokenlist", "tokenlist").token_list().unwrap();
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].to_string(), TOKEN_ID);
+}
+
+#[test]
+fn token_ask() {
+    let tx = client("tokenask", "tokenask").token_ask(1000, &TOKEN_ID.parse().unwrap(), amount("0.012")).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn token_bid() {
+    let tx = client("tokenbid", "tokenbid").token_bid(500, &TOKEN_ID.parse().unwrap(), amount("0.011")).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn token_fill_ask() {
+    let asktxid = hash("4e5d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d");
+    let tx = client("tokenfillask", "tokenfillask").token_fill_ask(&TOKEN_ID.parse().unwrap(), &asktxid, 100).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn token_fill_bid() {
+    let bidtxid = hash("2b7d9f1a3c5e7a9b1d3f5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b7d9f1a3c5e7a");
+    let tx = client("tokenfillbid", "tokenfillbid").token_fill_bid(&TOKEN_ID.parse().unwrap(), &bidtxid, 100).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn token_cancel_ask() {
+    let asktxid = hash("4e5d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d");
+    let tx = client("tokencancelask", "tokencancelask").token_cancel_ask(&TOKEN_ID.parse().unwrap(), &asktxid).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn token_cancel_bid() {
+    let bidtxid = hash("2b7d9f1a3c5e7a9b1d3f5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b7d9f1a3c5e7a");
+    let tx = client("tokencancelbid", "tokencancelbid").token_cancel_bid(&TOKEN_ID.parse().unwrap(), &bidtxid).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn token_orders() {
+    let orders = client("tokenorders", "tokenorders").token_orders(None).unwrap();
+    assert_eq!(orders.len(), 5);
+
+    // asks list their tokens as `amount` and the coins as `totalrequired`
+    assert_eq!(orders[0].side, OrderSide::Ask);
+    assert_eq!(orders[0].tokens, Some(1000));
+    assert_eq!(orders[0].coins, Some(amount("12")));
+    assert_eq!(orders[0].price, Some(amount("0.012")));
+
+    // bids the other way around
+    assert_eq!(orders[1].side, OrderSide::Bid);
+    assert_eq!(orders[1].funcid, "B");
+    assert_eq!(orders[1].tokens, Some(400));
+    assert_eq!(orders[1].coins, Some(amount("4")));
+    assert_eq!(orders[1].tokenid.unwrap().to_string(), TOKEN_ID);
+
+    // an order without a price has no `totalrequired` either
+    assert_eq!(orders[4].side, OrderSide::Bid);
+    assert_eq!(orders[4].tokens, None);
+    assert_eq!(orders[4].coins, Some(amount("0.0001")));
+    assert_eq!(orders[4].price, None);
+}
+
+#[test]
+fn my_token_orders() {
+    let orders = client("mytokenorders", "mytokenorders").my_token_orders().unwrap();
+
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].origaddress.as_ref().unwrap(), "CeP8Pzq8pHNF5RiTQJNyRTa6oAmFEf9Wbf");
+}
+
+const ORACLE_TXID: &str = "4895f631316a649e216153aee7a574bd281686265dc4e8d37597f72353facac3";
+
+#[test]
+fn oracles_create() {
+    let format = arguments::OracleFormat::new("IhL").unwrap();
+    let tx = client("oraclescreate", "oraclescreate").oracles_create("KMDBTC", "KMD/BTC price in satoshis", &format).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn oracles_fund() {
+    let tx = client("oraclesfund", "oraclesfund").oracles_fund(&hash(ORACLE_TXID)).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn oracles_register() {
+    let tx = client("oraclesregister", "oraclesregister").oracles_register(&hash(ORACLE_TXID), amount("0.01")).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn oracles_subscribe() {
+    let tx = client("oraclessubscribe", "oraclessubscribe").oracles_subscribe(&hash(ORACLE_TXID), PUBKEY, amount("1")).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn oracles_data() {
+    let data = arguments::OracleData(String::from("0100"));
+    let tx = client("oraclesdata", "oraclesdata").oracles_data(&hash(ORACLE_TXID), &data).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn oracles_sample() {
+    let txid = hash("8a1c5e2e0f6b3d9d4e1c7a2b5f8e3c6d9a0b4e7f1c2d5a8b3e6f9c0d1a4b7e2f");
+    let sample = client("oraclessample", "oraclessample").oracles_sample(&hash(ORACLE_TXID), &txid).unwrap();
+
+    assert!(sample.txid.is_none());
+    assert_eq!(sample.data.len(), 3);
+    assert_eq!(sample.data[0], "1571112");
+}
+
+#[test]
+fn oracles_samples() {
+    let baton = hash("8a1c5e2e0f6b3d9d4e1c7a2b5f8e3c6d9a0b4e7f1c2d5a8b3e6f9c0d1a4b7e2f");
+    let samples = client("oraclessamples", "oraclessamples").oracles_samples(&hash(ORACLE_TXID), &baton, 2).unwrap();
+
+    assert_eq!(samples.samples.len(), 2);
+    assert_eq!(samples.samples[0].txid, Some(baton));
+    assert_eq!(samples.samples[1].data[2], "6479");
+}
+
+#[test]
+fn oracles_info() {
+    let info = client("oraclesinfo", "oraclesinfo").oracles_info(&hash(ORACLE_TXID)).unwrap();
+
+    assert_eq!(info.format, "IhL");
+    assert_eq!(info.registered.len(), 1);
+    assert_eq!(info.registered[0].publisher, PUBKEY);
+    assert_eq!(info.registered[0].datafee, amount("0.01"));
+    assert_eq!(info.registered[0].funds, amount("1.97"));
+}
+
+#[test]
+fn oracles_list() {
+    let oracles = client("oracleslist", "oracleslist").oracles_list().unwrap();
+
+    assert_eq!(oracles[0].to_string(), ORACLE_TXID);
+}
+
+const CHANNEL_TXID: &str = "c1f3b6a5d8e0f2a4c6e8b0d2f4a6c8e0b2d4f6a8c0e2b4d6f8a0c2e4b6d8f0a2";
+
+#[test]
+fn channels_open() {
+    let tx = client("channelsopen", "channelsopen").channels_open(PUBKEY, 10, amount("0.01")).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn channels_payment() {
+    let tx = client("channelspayment", "channelspayment").channels_payment(&hash(CHANNEL_TXID), amount("0.02"), None).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn channels_close() {
+    let tx = client("channelsclose", "channelsclose").channels_close(&hash(CHANNEL_TXID)).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn channels_refund() {
+    let closetxid = hash("5e8a1c3f6b9d2e4a7c0f3b6d9e1a4c7f0b3d6e9a2c5f8b1d4e7a0c3f6b9d2e4a");
+    let tx = client("channelsrefund", "channelsrefund").channels_refund(&hash(CHANNEL_TXID), &closetxid).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn channels_info() {
+    let info = client("channelsinfo", "channelsinfo").channels_info(&hash(CHANNEL_TXID)).unwrap();
+
+    assert_eq!(info.numpayments, 10);
+    assert_eq!(info.denomination, 1000000);
+    assert!(info.tokenid.is_none());
+    assert_eq!(info.transactions.len(), 3);
+    assert_eq!(info.transactions[0].kind, ChannelTxKind::Open);
+    assert_eq!(info.transactions[0].txid, hash(CHANNEL_TXID));
+    assert_eq!(info.transactions[1].kind, ChannelTxKind::Payment);
+    assert_eq!(info.transactions[1].amount, Some(2000000));
+    assert_eq!(info.payments_left(), 7);
+    assert!(!info.is_closed());
+}
+
+#[test]
+fn channels_list() {
+    let channels = client("channelslist", "channelslist").channels_list().unwrap();
+
+    assert_eq!(channels[0], hash(CHANNEL_TXID));
+}
+
+const REWARDS_TXID: &str = "3a0c1e2f4b6d8a9c7e5f3b1d2a4c6e8f0b9d7a5c3e1f2b4d6a8c0e9f7b5d3a1c";
+const DICE_TXID: &str = "7d1a2b3c4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b";
+
+#[test]
+fn faucet_fund() {
+    let tx = client("faucetfund", "faucetfund").faucet_fund(amount("10")).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn faucet_get() {
+    let tx = client("faucetget", "faucetget").faucet_get().unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn faucet_info() {
+    let info = client("faucetinfo", "faucetinfo").faucet_info().unwrap();
+
+    assert_eq!(info.name, "Faucet");
+    assert_eq!(info.funding, amount("6.955"));
+}
+
+#[test]
+fn rewards_create_funding() {
+    let tx = client("rewardscreatefunding", "rewardscreatefunding")
+        .rewards_create_funding("LOCK", amount("10000"), 5.0, 1, 10, amount("1")).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn rewards_lock() {
+    let tx = client("rewardslock", "rewardslock").rewards_lock("LOCK", &hash(REWARDS_TXID), amount("150")).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn rewards_unlock() {
+    let tx = client("rewardsunlock", "rewardsunlock").rewards_unlock("LOCK", &hash(REWARDS_TXID), None).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn rewards_info() {
+    let info = client("rewardsinfo", "rewardsinfo").rewards_info(&hash(REWARDS_TXID)).unwrap();
+
+    assert_eq!(info.name, "LOCK");
+    assert_eq!(info.apr, 5.0);
+    assert_eq!(info.minseconds, 86400);
+    assert_eq!(info.funding, amount("9990"));
+    assert_eq!(info.locked, Some(amount("150")));
+}
+
+#[test]
+fn rewards_list() {
+    assert_eq!(client("rewardslist", "rewardslist").rewards_list().unwrap(), vec![hash(REWARDS_TXID)]);
+}
+
+#[test]
+fn dice_fund() {
+    let tx = client("dicefund", "dicefund")
+        .dice_fund("KMDICE", amount("1000"), amount("0.001"), amount("10"), 100, 5).unwrap();
+
+    assert!(tx.hex.starts_with("0400008085202f89"));
+}
+
+#[test]
+fn dice_add_funds() {
+    let tx = client("diceaddfunds", "diceaddfunds").dice_add_funds("KMDICE", &hash(DICE_TXID), amount("100")).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn dice_bet() {
+    let tx = client("dicebet", "dicebet").dice_bet("KMDICE", &hash(DICE_TXID), amount("1"), 2).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn dice_finish() {
+    let bettxid = hash("9d2e4a7c0f3b6d9e1a4c7f0b3d6e9a2c5f8b1d4e7a0c3f6b9d2e4a5e8a1c3f6b");
+    let tx = client("dicefinish", "dicefinish").dice_finish("KMDICE", &hash(DICE_TXID), &bettxid).unwrap();
+
+    assert!(tx.complete);
+}
+
+#[test]
+fn dice_status() {
+    let bettxid = hash("9d2e4a7c0f3b6d9e1a4c7f0b3d6e9a2c5f8b1d4e7a0c3f6b9d2e4a5e8a1c3f6b");
+    let status = client("dicestatus", "dicestatus").dice_status("KMDICE", &hash(DICE_TXID), Some(&bettxid)).unwrap();
+
+    assert_eq!(status, DiceStatus::Win(amount("1.98")));
+}
+
+#[test]
+fn dice_info() {
+    let info = client("diceinfo", "diceinfo").dice_info(&hash(DICE_TXID)).unwrap();
+
+    assert_eq!(info.name, "KMDICE");
+    assert_eq!(info.maxodds, 100);
+    assert_eq!(info.minbet, amount("0.001"));
+    assert_eq!(info.funding, amount("18932.1045"));
+}
+
+#[test]
+fn dice_list() {
+    assert_eq!(client("dicelist", "dicelist").dice_list().unwrap(), vec![hash(DICE_TXID)]);
+}
@@ -0,0 +1,250 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::address::Address;
+use komodo_rpc_client::{Amount, ApiError, Client, ClientError, KomodoRpcApi, RetryPolicy, RpcBatch, RpcRequest, Transport};
+use serde_json::Value;
+use std::cell::Cell;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{}.json", name));
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+/// A komodod that answers with RPC error `code` to the first `failures` requests, and with `getinfo` after that.
+struct StartingNode {
+    failures: usize,
+    code: i32,
+    requests: Cell<usize>,
+}
+
+impl StartingNode {
+    fn new(failures: usize, code: i32) -> Self {
+        StartingNode {
+            failures,
+            code,
+            requests: Cell::new(0),
+        }
+    }
+}
+
+impl Transport for StartingNode {
+    fn send(&self, body: &Value) -> Result<Value, ApiError> {
+        let requests = self.requests.get() + 1;
+        self.requests.set(requests);
+
+        if requests <= self.failures {
+            Ok(json!({"result": null, "error": {"code": self.code, "message": "Verifying blocks..."}, "id": body["id"]}))
+        } else {
+            Ok(json!({"result": fixture("getinfo"), "error": null, "id": body["id"]}))
+        }
+    }
+}
+
+/// A komodod that fails the first `failures` requests with `error`, and answers `result` to every request after that.
+struct FlakyNode {
+    failures: usize,
+    error: fn() -> ApiError,
+    result: Value,
+    requests: Cell<usize>,
+}
+
+impl FlakyNode {
+    fn new(failures: usize, error: fn() -> ApiError, result: Value) -> Self {
+        FlakyNode {
+            failures,
+            error,
+            result,
+            requests: Cell::new(0),
+        }
+    }
+}
+
+impl Transport for FlakyNode {
+    fn send(&self, body: &Value) -> Result<Value, ApiError> {
+        let requests = self.requests.get() + 1;
+        self.requests.set(requests);
+
+        if requests <= self.failures {
+            return Err((self.error)());
+        }
+        match body.as_array() {
+            Some(batch) => Ok(batch.iter()
+                .map(|request| json!({"result": self.result, "error": null, "id": request["id"]}))
+                .collect()),
+            None => Ok(json!({"result": self.result, "error": null, "id": body["id"]})),
+        }
+    }
+}
+
+fn refused() -> ApiError {
+    ApiError::IO(io::Error::from(io::ErrorKind::ConnectionRefused))
+}
+
+fn unavailable() -> ApiError {
+    ApiError::Client(ClientError::Status(503))
+}
+
+const TXID: &str = "4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a";
+
+fn send(client: &Client<FlakyNode>) -> Result<(), ApiError> {
+    let address = Address::from("RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR").unwrap();
+
+    client.send_to_address(address, Amount::ONE_COIN, None, None, None).map(|_| ())
+}
+
+fn fast() -> RetryPolicy {
+    RetryPolicy {
+        initial_interval: Duration::from_millis(1),
+        max_interval: Duration::from_millis(4),
+        max_elapsed_time: Duration::from_millis(200),
+        ..RetryPolicy::default()
+    }
+}
+
+#[test]
+fn does_not_retry_by_default() {
+    let client = Client::with_transport(StartingNode::new(1, -28));
+
+    assert!(client.get_info().unwrap_err().is_warming_up());
+    assert_eq!(client.transport().requests.get(), 1);
+}
+
+#[test]
+fn retries_until_warmed_up() {
+    let mut client = Client::with_transport(StartingNode::new(3, -28));
+    client.set_retry_policy(fast());
+
+    assert_eq!(client.get_info().unwrap().name, "KMD");
+    assert_eq!(client.transport().requests.get(), 4);
+}
+
+#[test]
+fn does_not_retry_other_errors() {
+    let mut client = Client::with_transport(StartingNode::new(3, -6));
+    client.set_retry_policy(fast());
+
+    assert!(client.get_info().is_err());
+    assert_eq!(client.transport().requests.get(), 1);
+}
+
+#[test]
+fn retries_reads_after_server_errors() {
+    let mut client = Client::with_transport(FlakyNode::new(2, unavailable, json!(1_500_000)));
+    client.set_retry_policy(fast());
+
+    assert_eq!(client.get_block_count().unwrap().0, 1_500_000);
+    assert_eq!(client.transport().requests.get(), 3);
+}
+
+#[test]
+fn does_not_resend_writes_komodod_may_have_executed() {
+    let mut client = Client::with_transport(FlakyNode::new(2, unavailable, json!(TXID)));
+    client.set_retry_policy(fast());
+
+    match send(&client) {
+        Err(ApiError::Client(ClientError::Status(503))) => {},
+        other => panic!("expected HTTP 503, got {:?}", other),
+    }
+    assert_eq!(client.transport().requests.get(), 1);
+
+    // a request that could not be sent is sent again
+    let mut client = Client::with_transport(FlakyNode::new(2, refused, json!(TXID)));
+    client.set_retry_policy(fast());
+
+    send(&client).unwrap();
+    assert_eq!(client.transport().requests.get(), 3);
+}
+
+#[test]
+fn retries_only_read_only_batches() {
+    let mut client = Client::with_transport(FlakyNode::new(2, unavailable, json!(1)));
+    client.set_retry_policy(fast());
+
+    let mut batch = RpcBatch::new();
+    batch.add::<u64, _>(&RpcRequest::new0("getblockcount")).unwrap();
+    batch.add::<u64, _>(&RpcRequest::new0("getconnectioncount")).unwrap();
+    assert!(client.send_batch(&batch).is_ok());
+    assert_eq!(client.transport().requests.get(), 3);
+
+    // not even when komodod refused the connection
+    let mut client = Client::with_transport(FlakyNode::new(2, refused, json!(1)));
+    client.set_retry_policy(fast());
+
+    batch.add::<Value, _>(&RpcRequest::new2("sendtoaddress", "RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR", 1)).unwrap();
+    assert!(client.send_batch(&batch).unwrap_err().is_connection_refused());
+    assert_eq!(client.transport().requests.get(), 1);
+}
+
+#[test]
+fn gives_up_after_max_elapsed_time() {
+    let mut client = Client::with_transport(StartingNode::new(usize::MAX, -28));
+    client.set_retry_policy(RetryPolicy {
+        max_elapsed_time: Duration::from_millis(50),
+        ..fast()
+    });
+
+    let start = Instant::now();
+    assert!(client.get_info().unwrap_err().is_warming_up());
+    assert!(start.elapsed() < Duration::from_millis(500));
+    assert!(client.transport().requests.get() > 5);
+}
+
+#[test]
+fn backs_off_exponentially_with_jitter() {
+    let policy = RetryPolicy {
+        initial_interval: Duration::from_millis(100),
+        multiplier: 3.0,
+        max_interval: Duration::from_secs(2),
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.interval(0), Duration::from_millis(100));
+    assert_eq!(policy.interval(1), Duration::from_millis(300));
+    assert_eq!(policy.interval(2), Duration::from_millis(900));
+    assert_eq!(policy.interval(3), Duration::from_secs(2));
+
+    let policy = RetryPolicy { jitter: 0.5, ..policy };
+    let intervals: Vec<Duration> = (0..20).map(|_| policy.interval(1)).collect();
+    for interval in &intervals {
+        assert!(*interval >= Duration::from_millis(150) && *interval <= Duration::from_millis(450));
+    }
+    assert!(intervals.iter().any(|interval| *interval != intervals[0]));
+}
+
+#[test]
+fn clamps_huge_intervals() {
+    let policy = RetryPolicy {
+        initial_interval: Duration::from_secs(1),
+        multiplier: 1e300,
+        max_interval: Duration::from_secs(u64::MAX),
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.interval(10), Duration::from_secs(u64::from(u32::MAX)));
+
+    let policy = RetryPolicy { jitter: 1.0, ..policy };
+    assert!(policy.interval(10) <= Duration::from_secs(2 * u64::from(u32::MAX)));
+
+    let policy = RetryPolicy { multiplier: f64::NAN, jitter: f64::NAN, ..policy };
+    policy.interval(1);
+}
+
+#[test]
+fn waits_until_ready() {
+    // waits as long as the default policy does, half a second
+    let client = Client::with_transport(StartingNode::new(1, -28));
+    assert_eq!(client.wait_until_ready(Duration::from_secs(10)).unwrap().name, "KMD");
+    assert_eq!(client.transport().requests.get(), 2);
+
+    let client = Client::with_transport(StartingNode::new(usize::MAX, -28));
+    assert!(client.wait_until_ready(Duration::from_millis(10)).unwrap_err().is_warming_up());
+}
@@ -0,0 +1,107 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::address::Address;
+use komodo_rpc_client::{Amount, ApiError, Client, KomodoTransaction, MockTransport};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ADDRESS: &str = "REcENiAbY4U6PXwoPp8DaxxEWCh5P3dZP4";
+/// The rewards `listunspent.json` reports: 0.01243521 KMD for a UTXO of 13.78296712 KMD,
+/// and nothing for one of 3.0001 KMD.
+const INTEREST: i64 = 1_243_521;
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{}.json", name));
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+/// A wallet on a chain called `name`, at the tip of `getinfo.json`, holding the UTXOs of `listunspent.json`.
+fn wallet(name: &str) -> MockTransport {
+    let mut info = fixture("getinfo");
+    info["name"] = json!(name);
+
+    let mut transport = MockTransport::new();
+    transport
+        .add_method_response("getinfo", info)
+        .add_method_response("listunspent", fixture("listunspent"))
+        .add_method_response("signrawtransaction", fixture("signrawtransaction"))
+        .add_method_response("sendrawtransaction", fixture("sendrawtransaction"));
+
+    transport
+}
+
+#[test]
+fn claims_rewards_of_all_utxos() {
+    let client = Client::with_transport(wallet("KMD"));
+    let address = Address::from(ADDRESS).unwrap();
+    let fee = Amount::from_sat(10_000);
+
+    let claimed = client.claim_rewards(&address, fee).unwrap();
+
+    // what komodod says the UTXOs earned, not a calculation of its own
+    assert_eq!(claimed.interest, Amount::from_sat(INTEREST));
+    assert_eq!(claimed.amount, Amount::from_sat(1_378_296_712 + 300_010_000 + INTEREST - 10_000));
+    assert_eq!(claimed.inputs, 2);
+    assert_eq!(claimed.txid.to_string(), "27ca64c092a959c7edc525ed45e845b1de6a7590d173fd2fad9133c8a779a1e3");
+
+    let requests = client.transport().requests();
+    let methods: Vec<&str> = requests.iter().map(|request| request["method"].as_str().unwrap()).collect();
+    assert_eq!(
+        methods,
+        ["getinfo", "listunspent", "signrawtransaction", "sendrawtransaction"]
+    );
+    assert_eq!(requests[1]["params"], json!([1, 9999999, [ADDRESS]]));
+
+    // the wallet signs a self-send of everything, with a locktime that earns rewards again
+    let unsigned = KomodoTransaction::from_hex(requests[2]["params"][0].as_str().unwrap()).unwrap();
+    assert_eq!(unsigned.inputs.len(), 2);
+    assert_eq!(unsigned.outputs.len(), 1);
+    assert_eq!(unsigned.outputs[0].value, claimed.amount);
+    assert_eq!(unsigned.outputs[0].script_pubkey, [&[0x76, 0xa9, 0x14][..], address.payload(), &[0x88, 0xac]].concat());
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as u32;
+    assert!(unsigned.lock_time <= now - 777 && unsigned.lock_time >= now - 787);
+}
+
+#[test]
+fn refuses_assetchains() {
+    let client = Client::with_transport(wallet("MYCHAIN"));
+    let address = Address::from(ADDRESS).unwrap();
+
+    assert!(client.claim_rewards(&address, Amount::from_sat(10_000)).is_err());
+    // nothing else was asked
+    assert_eq!(client.transport().requests().len(), 1);
+}
+
+#[test]
+fn refuses_rewards_below_fee() {
+    let client = Client::with_transport(wallet("KMD"));
+    let address = Address::from(ADDRESS).unwrap();
+
+    assert!(client.claim_rewards(&address, Amount::from_sat(INTEREST)).is_err());
+    assert!(client.transport().requests().iter().all(|request| request["method"] != "sendrawtransaction"));
+}
+
+#[test]
+fn refuses_amounts_that_overflow() {
+    let mut transport = wallet("KMD");
+    let mut unspents = fixture("listunspent");
+    unspents[1]["amount"] = json!(92_233_720_368i64);
+    transport.add_method_response("listunspent", unspents);
+
+    let client = Client::with_transport(transport);
+    let address = Address::from(ADDRESS).unwrap();
+
+    match client.claim_rewards(&address, Amount::from_sat(10_000)) {
+        Err(ApiError::Other(message)) => assert!(message.contains("more than an amount can hold"), "{}", message),
+        other => panic!("expected an overflow, got {:?}", other),
+    }
+    assert!(client.transport().requests().iter().all(|request| request["method"] != "signrawtransaction"));
+}
@@ -0,0 +1,253 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::arguments::CreateRawTransactionInputs;
+use komodo_rpc_client::tx::{InputKind, Utxo};
+use komodo_rpc_client::{AddressUtxos, Amount, CoinSelector, SelectionStrategy, Unspent};
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::PathBuf;
+
+const ADDRESS: &str = "RXvbuoZCZ1GpTNTfroFABvFUf6ce4s1J4L";
+const SCRIPT: &str = "76a914f8660d5f2b19c8e28981290efa1143c9021d0ced88ac";
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{}.json", name));
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+fn utxo(address: &str, vout: u32, satoshis: i64) -> Utxo {
+    Utxo {
+        txid: "4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a".parse().unwrap(),
+        vout,
+        address: address.to_string(),
+        script_pub_key: SCRIPT.to_string(),
+        value: Amount::from_sat(satoshis),
+        height: Some(1_600_000),
+        confirmations: None,
+    }
+}
+
+fn kmd(coins: i64) -> i64 {
+    coins * 100_000_000
+}
+
+fn vouts(selection: &komodo_rpc_client::tx::Selection) -> Vec<u32> {
+    selection.inputs.iter().map(|utxo| utxo.vout).collect()
+}
+
+#[test]
+fn selects_largest_first_with_change() {
+    let utxos: AddressUtxos = serde_json::from_value(fixture("getaddressutxos")).unwrap();
+
+    let selection = CoinSelector::try_from(&utxos).unwrap()
+        .select(Amount::from_coins(5).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+
+    // the 13.78296712 KMD UTXO, 1 input and 2 outputs are 245 bytes
+    assert_eq!(selection.inputs.len(), 1);
+    assert_eq!(selection.inputs[0].value, Amount::from_sat(1_378_296_712));
+    assert_eq!(selection.fee, Amount::from_sat(245));
+    assert_eq!(selection.change, Amount::from_sat(1_378_296_712 - kmd(5) - 245));
+    assert_eq!(selection.total(), selection.change + selection.fee + Amount::from_coins(5).unwrap());
+}
+
+#[test]
+fn selects_oldest_first() {
+    let unspents: Vec<Unspent> = serde_json::from_value(fixture("listunspent")).unwrap();
+
+    let selection = CoinSelector::from(&unspents[..])
+        .strategy(SelectionStrategy::OldestFirst)
+        .select(Amount::ONE_COIN, Amount::from_sat(1_000))
+        .unwrap();
+
+    // the UTXO with 150 confirmations before the one with 108, even though it is smaller
+    assert_eq!(selection.inputs.len(), 1);
+    assert_eq!(selection.inputs[0].txid.to_string(), "df743dd1973e1c7d46968720b931af0afa8ec5e8412f9420006b7b4fa660ba8d");
+
+    let utxos = vec![
+        Utxo { height: Some(1_600_000), ..utxo(ADDRESS, 0, kmd(1)) },
+        Utxo { height: Some(1_400_000), ..utxo(ADDRESS, 1, kmd(1)) },
+        Utxo { height: Some(1_500_000), ..utxo(ADDRESS, 2, kmd(1)) },
+    ];
+    let selection = CoinSelector::new(utxos)
+        .strategy(SelectionStrategy::OldestFirst)
+        .select(Amount::from_sat(kmd(2) - 10_000), Amount::from_sat(1_000))
+        .unwrap();
+    assert_eq!(vouts(&selection), [1, 2]);
+}
+
+#[test]
+fn finds_exact_match_without_change() {
+    // 1 input costs 148 satoshis at 1000 per kB, the transaction without inputs and change 63
+    let utxos = vec![
+        utxo(ADDRESS, 0, kmd(6)),
+        utxo(ADDRESS, 1, kmd(5) + 111),
+        utxo(ADDRESS, 2, kmd(3) + 248),
+        utxo(ADDRESS, 3, kmd(1)),
+    ];
+
+    let selection = CoinSelector::new(utxos.clone())
+        .strategy(SelectionStrategy::BranchAndBound)
+        .select(Amount::from_coins(8).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+
+    assert_eq!(vouts(&selection), [1, 2]);
+    assert_eq!(selection.change, Amount::ZERO);
+    // 2 inputs and 1 output are 359 bytes
+    assert_eq!(selection.fee, Amount::from_sat(359));
+
+    // largest first needs change for the same target
+    let selection = CoinSelector::new(utxos)
+        .select(Amount::from_coins(8).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+    assert_eq!(vouts(&selection), [0, 1]);
+    assert!(selection.change.is_positive());
+}
+
+#[test]
+fn falls_back_to_largest_first_without_exact_match() {
+    let utxos = vec![utxo(ADDRESS, 0, kmd(6)), utxo(ADDRESS, 1, kmd(4))];
+
+    let selection = CoinSelector::new(utxos)
+        .strategy(SelectionStrategy::BranchAndBound)
+        .select(Amount::from_coins(7).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+
+    assert_eq!(vouts(&selection), [0, 1]);
+    assert_eq!(selection.change, Amount::from_sat(kmd(3) - 393));
+}
+
+#[test]
+fn avoids_mixing_addresses() {
+    let other = "RG1pwHQgZBkRnsF5Csg7b6ZhqQQ6jkGxWR";
+    let utxos = vec![
+        utxo(ADDRESS, 0, kmd(5)),
+        utxo(ADDRESS, 1, kmd(5)),
+        utxo(other, 2, kmd(7)),
+        utxo(other, 3, kmd(1)),
+    ];
+
+    let selection = CoinSelector::new(utxos.clone())
+        .strategy(SelectionStrategy::AvoidMixingAddresses)
+        .select(Amount::from_coins(9).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+    assert_eq!(vouts(&selection), [0, 1]);
+
+    // both addresses can pay 6 KMD, the one with the single UTXO is used
+    let selection = CoinSelector::new(utxos.clone())
+        .strategy(SelectionStrategy::AvoidMixingAddresses)
+        .select(Amount::from_coins(6).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+    assert_eq!(vouts(&selection), [2]);
+
+    // 15 KMD needs UTXOs of both addresses
+    assert!(CoinSelector::new(utxos.clone())
+        .strategy(SelectionStrategy::AvoidMixingAddresses)
+        .select(Amount::from_coins(15).unwrap(), Amount::from_sat(1_000))
+        .is_err());
+    assert_eq!(
+        CoinSelector::new(utxos)
+            .select(Amount::from_coins(15).unwrap(), Amount::from_sat(1_000))
+            .unwrap()
+            .inputs
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn adds_dust_change_to_fee() {
+    // 1 input and 2 outputs cost 245 satoshis, which leaves 50 satoshis of change
+    let utxos = vec![utxo(ADDRESS, 0, kmd(1) + 295)];
+
+    let selection = CoinSelector::new(utxos)
+        .select(Amount::ONE_COIN, Amount::from_sat(1_000))
+        .unwrap();
+
+    assert_eq!(selection.change, Amount::ZERO);
+    assert_eq!(selection.fee, Amount::from_sat(295));
+}
+
+#[test]
+fn rejects_insufficient_funds_and_invalid_targets() {
+    let utxos = vec![utxo(ADDRESS, 0, kmd(1))];
+
+    assert!(CoinSelector::new(utxos.clone()).select(Amount::ONE_COIN, Amount::from_sat(1_000)).is_err());
+    assert!(CoinSelector::new(utxos.clone()).select(Amount::ZERO, Amount::from_sat(1_000)).is_err());
+    assert!(CoinSelector::new(utxos.clone()).select(Amount::from_sat(1_000), Amount::from_sat(-1)).is_err());
+    assert!(CoinSelector::new(vec![]).select(Amount::ONE_COIN, Amount::ZERO).is_err());
+
+    // without a fee everything can be spent
+    assert_eq!(CoinSelector::new(utxos).select(Amount::ONE_COIN, Amount::ZERO).unwrap().fee, Amount::ZERO);
+}
+
+#[test]
+fn rejects_amounts_that_overflow() {
+    let utxos: AddressUtxos = serde_json::from_value(json!([{
+        "address": ADDRESS,
+        "txid": "4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a",
+        "outputIndex": 0,
+        "script": SCRIPT,
+        "satoshis": i64::MAX as u64 + 1,
+        "height": 1_600_000
+    }])).unwrap();
+    assert!(CoinSelector::try_from(&utxos).is_err());
+
+    let utxos = vec![utxo(ADDRESS, 0, i64::MAX), utxo(ADDRESS, 1, i64::MAX)];
+    for &strategy in &[SelectionStrategy::LargestFirst, SelectionStrategy::BranchAndBound,
+                       SelectionStrategy::OldestFirst, SelectionStrategy::AvoidMixingAddresses] {
+        assert!(CoinSelector::new(utxos.clone()).strategy(strategy).select(Amount::ONE_COIN, Amount::ZERO).is_err());
+    }
+
+    // a fee rate whose fee does not fit in an amount
+    let utxos = vec![utxo(ADDRESS, 0, kmd(1))];
+    for &strategy in &[SelectionStrategy::LargestFirst, SelectionStrategy::BranchAndBound] {
+        assert!(CoinSelector::new(utxos.clone())
+            .strategy(strategy)
+            .select(Amount::from_sat(1_000), Amount::from_sat(i64::MAX))
+            .is_err());
+    }
+    // a target whose sum with the fee does not fit
+    assert!(CoinSelector::new(utxos)
+        .strategy(SelectionStrategy::BranchAndBound)
+        .select(Amount::from_sat(i64::MAX), Amount::from_sat(1_000))
+        .is_err());
+}
+
+#[test]
+fn needs_the_input_kind_of_other_scripts() {
+    let p2sh = "a914b4c1d6b8ab4e0a1a8d0a15d1b1c3eb5e9b1e7e5787";
+    let utxos = vec![Utxo { script_pub_key: p2sh.to_string(), ..utxo(ADDRESS, 0, kmd(2)) }];
+
+    assert!(CoinSelector::new(utxos.clone()).select(Amount::ONE_COIN, Amount::from_sat(1_000)).is_err());
+
+    // OP_0, 2 signatures and the redeem script make a 2-of-3 input 297 bytes, and the transaction 394
+    let selection = CoinSelector::new(utxos)
+        .input_kind(p2sh, InputKind::P2SHMultisig { required: 2, keys: 3 })
+        .select(Amount::ONE_COIN, Amount::from_sat(1_000))
+        .unwrap();
+    assert_eq!(selection.fee, Amount::from_sat(394));
+}
+
+#[test]
+fn converts_selection_to_inputs() {
+    let utxos = vec![utxo(ADDRESS, 0, kmd(6)), utxo(ADDRESS, 1, kmd(4))];
+    let selection = CoinSelector::new(utxos)
+        .select(Amount::from_coins(7).unwrap(), Amount::from_sat(1_000))
+        .unwrap();
+
+    let inputs = CreateRawTransactionInputs::from(&selection);
+    assert_eq!(
+        serde_json::to_value(&inputs).unwrap(),
+        json!([
+            {"txid": "4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a", "vout": 0},
+            {"txid": "4ae3ba0c30c41cb6fbd5ed06fefc7e9e1fbd0aba5dd0d9c1d8a4f3b8ad3d9c1a", "vout": 1}
+        ])
+    );
+}
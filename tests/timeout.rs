@@ -0,0 +1,193 @@
+extern crate komodo_rpc_client;
+#[macro_use]
+extern crate serde_json;
+
+use komodo_rpc_client::*;
+use serde_json::Value;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+fn fixture(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{}.json", name));
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+/// A komodod that answers every request after `delay`, with the fixture named after its method.
+/// Returns its port.
+fn slow_node(delay: Duration) -> u16 {
+    node(delay, false)
+}
+
+/// A komodod that sends the headers and part of the body of every response at once, and the rest after `delay`.
+fn stalling_node(delay: Duration) -> u16 {
+    node(delay, true)
+}
+
+fn node(delay: Duration, stall_in_body: bool) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            thread::spawn(move || answer(stream, delay, stall_in_body));
+        }
+    });
+
+    port
+}
+
+fn answer(mut stream: TcpStream, delay: Duration, stall_in_body: bool) {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    let body = loop {
+        let n = match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(n) => n,
+        };
+        request.extend_from_slice(&buf[..n]);
+
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some(end) = text.find("\r\n\r\n") {
+            if let Ok(body) = serde_json::from_str::<Value>(&text[end + 4..]) {
+                break body;
+            }
+        }
+    };
+
+    let respond = |request: &Value| {
+        let method = request["method"].as_str().unwrap();
+        json!({"result": fixture(method), "error": null, "id": request["id"]})
+    };
+    let response = match body {
+        Value::Array(ref requests) => Value::Array(requests.iter().map(respond).collect()),
+        ref request => respond(request),
+    }
+    .to_string();
+
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.len(), response
+    );
+    let (now, later) = if stall_in_body {
+        response.split_at(response.len() - response.len() / 4)
+    } else {
+        ("", &response[..])
+    };
+
+    let _ = stream.write_all(now.as_bytes()).and_then(|_| stream.flush());
+    thread::sleep(delay);
+    let _ = stream.write_all(later.as_bytes());
+}
+
+fn builder(port: u16) -> ClientBuilder {
+    let mut builder = Client::builder();
+    builder
+        .port(port)
+        .rpc_user("user")
+        .rpc_password("password")
+        .timeout(Duration::from_millis(200));
+
+    builder
+}
+
+#[test]
+fn times_out() {
+    let client = builder(slow_node(Duration::from_millis(1_000))).build().unwrap();
+
+    let error = client.get_connection_count().unwrap_err();
+    match error {
+        ApiError::Client(ClientError::Transport(ref err)) => assert!(err.is_timeout()),
+        ref other => panic!("expected a timeout, got {:?}", other),
+    }
+    // komodod may have executed a request that timed out
+    assert!(!error.is_retryable());
+    assert!(error.is_retryable_if_read_only());
+
+    let client = builder(slow_node(Duration::from_millis(10))).build().unwrap();
+    assert_eq!(client.get_connection_count().unwrap(), 8);
+}
+
+#[test]
+fn times_out_reading_the_body() {
+    let client = builder(stalling_node(Duration::from_millis(1_000))).build().unwrap();
+
+    let error = client.get_connection_count().unwrap_err();
+    match error {
+        ApiError::Client(ClientError::Transport(ref err)) => assert!(err.is_timeout()),
+        ref other => panic!("expected a timeout, got {:?}", other),
+    }
+    assert!(!error.is_retryable());
+    assert!(error.is_retryable_if_read_only());
+
+    let client = builder(stalling_node(Duration::from_millis(10))).build().unwrap();
+    assert_eq!(client.get_connection_count().unwrap(), 8);
+}
+
+#[test]
+fn slow_methods_have_longer_timeouts() {
+    let port = slow_node(Duration::from_millis(500));
+    let client = builder(port).build().unwrap();
+
+    assert!(client.get_connection_count().is_err());
+    assert!(client.get_tx_out_set_info().is_ok());
+
+    let client = builder(port)
+        .method_timeout("getconnectioncount", Duration::from_secs(5))
+        .build()
+        .unwrap();
+    assert_eq!(client.get_connection_count().unwrap(), 8);
+
+    // a timeout of its own can also be shorter
+    let client = builder(port)
+        .method_timeout("gettxoutsetinfo", Duration::from_millis(100))
+        .build()
+        .unwrap();
+    assert!(client.get_tx_out_set_info().is_err());
+}
+
+#[test]
+fn batch_gets_longest_timeout() {
+    let client = builder(slow_node(Duration::from_millis(500))).build().unwrap();
+
+    let mut batch = RpcBatch::new();
+    let count = batch.add::<u32, _>(&RpcRequest::new0("getconnectioncount")).unwrap();
+    let info = batch.add::<TxOutSetInfo, _>(&RpcRequest::new0("gettxoutsetinfo")).unwrap();
+
+    let response = client.send_batch(&batch).unwrap();
+    assert_eq!(response.get(&count).unwrap(), 8);
+    assert!(response.get(&info).is_ok());
+}
+
+#[test]
+fn overrides_timeout_per_call() {
+    let client = builder(slow_node(Duration::from_millis(500))).build().unwrap();
+
+    assert!(client.get_connection_count().is_err());
+    assert_eq!(client.with_timeout(Duration::from_secs(5)).unwrap().get_connection_count().unwrap(), 8);
+
+    // without the settings of a `ClientBuilder` there is nothing to build a new client from
+    let client = Client::with_transport(RpcClient::new(HTTPClient::new(), "http://127.0.0.1:7771"));
+    assert!(client.with_timeout(Duration::from_secs(5)).is_err());
+}
+
+#[test]
+fn configures_connection_pool() {
+    let client = builder(slow_node(Duration::from_millis(0)))
+        .keep_alive(false)
+        .pool_max_idle_per_host(4)
+        .connect_timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        assert_eq!(client.get_connection_count().unwrap(), 8);
+    }
+}
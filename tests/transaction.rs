@@ -0,0 +1,180 @@
+extern crate komodo_rpc_client;
+extern crate serde_json;
+
+use komodo_rpc_client::tx::*;
+use komodo_rpc_client::{Amount, SerializedRawTransaction};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The coinbase of Bitcoin's genesis block, a v1 transaction.
+const GENESIS_COINBASE: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+
+/// The transparent v4 transaction of `tests/fixtures/rpc/getrawtransaction.json`.
+fn sapling_tx() -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/rpc/getrawtransaction.json");
+
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+fn joinsplit(proof: JoinSplitProof) -> JoinSplit {
+    JoinSplit {
+        vpub_old: Amount::from_sat(0),
+        vpub_new: Amount::from_sat(5_000_000),
+        anchor: [1; 32],
+        nullifiers: [[2; 32], [3; 32]],
+        commitments: [[4; 32], [5; 32]],
+        ephemeral_key: [6; 32],
+        random_seed: [7; 32],
+        macs: [[8; 32], [9; 32]],
+        proof,
+        ciphertexts: [[10; 601], [11; 601]],
+    }
+}
+
+fn roundtrip(tx: &KomodoTransaction) -> Vec<u8> {
+    let bytes = tx.serialize();
+    assert_eq!(&KomodoTransaction::deserialize(&bytes).unwrap(), tx);
+
+    bytes
+}
+
+#[test]
+fn decodes_sapling_transaction() {
+    let tx = KomodoTransaction::from_hex(&sapling_tx()).unwrap();
+
+    assert_eq!(tx.version, 4);
+    assert!(tx.overwintered);
+    assert!(tx.is_sapling());
+    assert_eq!(tx.inputs.len(), 1);
+    assert_eq!(tx.inputs[0].prevout.txid.to_string(), "16d8175a07229e65b21bbbfc52a7909fdb10df9a85b1348a0f35c61e81cb55d2");
+    assert_eq!(tx.inputs[0].prevout.vout, 0);
+    assert_eq!(tx.inputs[0].sequence, SEQUENCE_LOCKTIME);
+    assert_eq!(tx.outputs[0].value, Amount::ONE_COIN);
+    assert_eq!(tx.outputs[1].value, Amount::from_sat(50_000));
+    assert_eq!(tx.lock_time, 1_572_057_951);
+    assert_eq!(tx.expiry_height, 0);
+
+    assert_eq!(tx.to_hex(), sapling_tx());
+}
+
+#[test]
+fn decodes_v1_transaction() {
+    let tx = KomodoTransaction::from_hex(GENESIS_COINBASE).unwrap();
+
+    assert_eq!(tx.version, 1);
+    assert!(!tx.overwintered);
+    assert_eq!(tx.outputs[0].value, Amount::from_coins(50).unwrap());
+    assert_eq!(tx.txid().to_string(), "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b");
+    assert_eq!(tx.to_hex(), GENESIS_COINBASE);
+}
+
+#[test]
+fn roundtrips_sprout_joinsplits() {
+    let tx = KomodoTransaction {
+        version: 2,
+        overwintered: false,
+        version_group_id: 0,
+        joinsplits: vec![joinsplit(JoinSplitProof::PHGR13([12; 296]))],
+        joinsplit_pubkey: [13; 32],
+        joinsplit_sig: [14; 64],
+        ..KomodoTransaction::default()
+    };
+
+    // header, no inputs and outputs, locktime, 1 joinsplit of 1802 bytes, its pubkey and signature
+    assert_eq!(roundtrip(&tx).len(), 4 + 1 + 1 + 4 + 1 + 1802 + 32 + 64);
+}
+
+#[test]
+fn roundtrips_overwinter_transaction() {
+    let tx = KomodoTransaction {
+        version: OVERWINTER_TX_VERSION,
+        version_group_id: OVERWINTER_VERSION_GROUP_ID,
+        lock_time: 1_540_000_000,
+        expiry_height: 1_100_000,
+        ..KomodoTransaction::default()
+    };
+
+    let bytes = roundtrip(&tx);
+    assert_eq!(&bytes[..8], &[0x03, 0x00, 0x00, 0x80, 0x70, 0x82, 0xc4, 0x03]);
+    // no valueBalance or Sapling parts, only the empty list of joinsplits
+    assert_eq!(bytes.len(), 8 + 1 + 1 + 4 + 4 + 1);
+}
+
+#[test]
+fn roundtrips_shielded_sapling_transaction() {
+    let tx = KomodoTransaction {
+        value_balance: Amount::from_sat(-10_000),
+        shielded_spends: vec![SpendDescription {
+            cv: [1; 32],
+            anchor: [2; 32],
+            nullifier: [3; 32],
+            rk: [4; 32],
+            zkproof: [5; 192],
+            spend_auth_sig: [6; 64],
+        }],
+        shielded_outputs: vec![OutputDescription {
+            cv: [7; 32],
+            cmu: [8; 32],
+            ephemeral_key: [9; 32],
+            enc_ciphertext: [10; 580],
+            out_ciphertext: [11; 80],
+            zkproof: [12; 192],
+        }],
+        joinsplits: vec![joinsplit(JoinSplitProof::Groth16([13; 192]))],
+        joinsplit_pubkey: [14; 32],
+        joinsplit_sig: [15; 64],
+        binding_sig: [16; 64],
+        expiry_height: 3_000_000,
+        ..KomodoTransaction::default()
+    };
+
+    let bytes = roundtrip(&tx);
+    // a spend is 384 bytes, an output 948 bytes and a Groth16 joinsplit 1698 bytes
+    assert_eq!(bytes.len(), 8 + 1 + 1 + 4 + 4 + 8 + 1 + 384 + 1 + 948 + 1 + 1698 + 32 + 64 + 64);
+    assert_eq!(&bytes[bytes.len() - 64..], &[16; 64][..]);
+}
+
+#[test]
+fn rejects_invalid_transactions() {
+    let hex = sapling_tx();
+
+    // data after the transaction
+    assert!(KomodoTransaction::from_hex(&format!("{}00", hex)).is_err());
+    // truncated
+    assert!(KomodoTransaction::from_hex(&hex[..hex.len() - 2]).is_err());
+    // a v4 header with the Overwinter version group id
+    assert!(KomodoTransaction::from_hex(&hex.replace("85202f89", "7082c403")).is_err());
+    // one input, with the count written in 3 bytes instead of 1
+    assert!(KomodoTransaction::from_hex(&hex.replacen("85202f8901", "85202f89fd0100", 1)).is_err());
+    assert!(KomodoTransaction::from_hex("not hex").is_err());
+}
+
+#[test]
+fn edits_fields_and_reserializes() {
+    let mut tx = KomodoTransaction::from_hex(&sapling_tx()).unwrap();
+    tx.expiry_height = 1_600_000;
+    tx.lock_time = 0;
+
+    let edited = KomodoTransaction::from_hex(&tx.to_hex()).unwrap();
+    assert_eq!(edited.expiry_height, 1_600_000);
+    assert_eq!(edited.lock_time, 0);
+    assert_eq!(edited.inputs, tx.inputs);
+    assert_eq!(edited.outputs, tx.outputs);
+}
+
+#[test]
+fn sets_komodo_locktime() {
+    let mut raw = SerializedRawTransaction::from_hex(sapling_tx());
+    raw.set_locktime().unwrap();
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as u32;
+    let tx = raw.decode().unwrap();
+    assert!(tx.lock_time <= now - 777 && tx.lock_time >= now - 787);
+
+    let mut original = KomodoTransaction::from_hex(&sapling_tx()).unwrap();
+    original.lock_time = tx.lock_time;
+    assert_eq!(tx, original);
+
+    assert!(SerializedRawTransaction::from_hex("0400008085202f89".to_string()).set_locktime().is_err());
+}